@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, Bencher, Criterion};
 use futures_util::Future;
-use std::time::Duration;
+use std::{future::IntoFuture, time::Duration};
 
 pub fn current_thread_runtime() -> tokio::runtime::Runtime {
     let mut builder = tokio::runtime::Builder::new_current_thread();
@@ -123,15 +123,88 @@ fn bench_rustis_parallel(b: &mut Bencher) {
     });
 }
 
+fn bench_rustis_parallel_auto_pipeline(b: &mut Bencher) {
+    use rustis::{client::IntoConfig, commands::StringCommands};
+
+    let runtime = current_thread_runtime();
+
+    let mut config = "127.0.0.1:6379".into_config().unwrap();
+    config.auto_pipeline = Some(Duration::from_millis(1));
+    let client = runtime
+        .block_on(rustis::client::Client::connect(config))
+        .unwrap();
+
+    b.iter(|| {
+        runtime.block_on(async {
+            let tasks: Vec<_> = (0..PARALLEL_QUERIES)
+                .map(|i| {
+                    let client = client.clone();
+                    tokio::spawn(async move {
+                        for _ in 0..ITERATIONS {
+                            let key = format!("key{i}");
+                            let value = format!("value{i}");
+                            let _ = client.set(key, value).await;
+                        }
+                    })
+                })
+                .collect();
+
+            futures_util::future::join_all(tasks).await;
+        })
+    });
+}
+
+fn bench_rustis_back_to_back_sets(b: &mut Bencher, write_buffer_size: usize) {
+    use rustis::{client::IntoConfig, commands::StringCommands};
+
+    const NUM_SETS: usize = 10_000;
+
+    let runtime = current_thread_runtime();
+
+    let mut config = "127.0.0.1:6379".into_config().unwrap();
+    config.write_buffer_size = write_buffer_size;
+    let client = runtime
+        .block_on(rustis::client::Client::connect(config))
+        .unwrap();
+
+    b.iter(|| {
+        runtime.block_on(async {
+            let futures =
+                (0..NUM_SETS).map(|i| client.set(format!("key{i}"), "value").into_future());
+            futures_util::future::join_all(futures).await;
+        })
+    });
+}
+
 fn bench_parallel(c: &mut Criterion) {
     let mut group = c.benchmark_group("parallel");
     group
         .measurement_time(Duration::from_secs(15))
         .bench_function("redis_parallel", bench_redis_parallel)
         .bench_function("fred_parallel", bench_fred_parallel)
-        .bench_function("rustis_parallel", bench_rustis_parallel);
+        .bench_function("rustis_parallel", bench_rustis_parallel)
+        .bench_function(
+            "rustis_parallel_auto_pipeline",
+            bench_rustis_parallel_auto_pipeline,
+        );
+    group.finish();
+}
+
+fn bench_write_buffer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("write_buffer");
+    group
+        .measurement_time(Duration::from_secs(15))
+        .bench_function("rustis_back_to_back_sets_1k_buffer", |b| {
+            bench_rustis_back_to_back_sets(b, 1024)
+        })
+        .bench_function("rustis_back_to_back_sets_64k_buffer", |b| {
+            bench_rustis_back_to_back_sets(b, 64 * 1024)
+        })
+        .bench_function("rustis_back_to_back_sets_1m_buffer", |b| {
+            bench_rustis_back_to_back_sets(b, 1024 * 1024)
+        });
     group.finish();
 }
 
-criterion_group!(bench, bench_parallel);
+criterion_group!(bench, bench_parallel, bench_write_buffer);
 criterion_main!(bench);
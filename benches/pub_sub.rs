@@ -0,0 +1,75 @@
+use criterion::{criterion_group, criterion_main, Bencher, Criterion};
+use futures_util::{Future, StreamExt};
+use rustis::commands::PubSubCommands;
+use std::time::Duration;
+
+const NUM_MESSAGES: usize = 100;
+
+pub fn current_thread_runtime() -> tokio::runtime::Runtime {
+    let mut builder = tokio::runtime::Builder::new_current_thread();
+    builder.enable_io();
+    builder.enable_time();
+    builder.build().unwrap()
+}
+
+pub fn block_on_all<F>(f: F) -> F::Output
+where
+    F: Future,
+{
+    current_thread_runtime().block_on(f)
+}
+
+async fn get_rustis_client() -> rustis::client::Client {
+    rustis::client::Client::connect("127.0.0.1:6379")
+        .await
+        .unwrap()
+}
+
+fn bench_rustis_pub_sub_fan_out(b: &mut Bencher, num_subscribers: usize) {
+    let runtime = current_thread_runtime();
+    let (publisher, _subscribers, mut streams) = runtime.block_on(async {
+        let publisher = get_rustis_client().await;
+        let mut subscribers = Vec::with_capacity(num_subscribers);
+        let mut streams = Vec::with_capacity(num_subscribers);
+        for _ in 0..num_subscribers {
+            let subscriber = get_rustis_client().await;
+            streams.push(subscriber.subscribe("bench_channel").await.unwrap());
+            subscribers.push(subscriber);
+        }
+        (publisher, subscribers, streams)
+    });
+
+    b.iter(|| {
+        runtime.block_on(async {
+            for i in 0..NUM_MESSAGES {
+                publisher.publish("bench_channel", i).await.unwrap();
+            }
+
+            for stream in streams.iter_mut() {
+                for _ in 0..NUM_MESSAGES {
+                    stream.next().await.unwrap().unwrap();
+                }
+            }
+        })
+    });
+
+    runtime.block_on(async {
+        for stream in streams {
+            stream.close().await.unwrap();
+        }
+    });
+}
+
+fn bench_fan_out(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pub_sub_fan_out");
+    group.measurement_time(Duration::from_secs(10));
+    for num_subscribers in [1, 10, 50] {
+        group.bench_function(format!("rustis_pub_sub_fan_out_{num_subscribers}"), |b| {
+            bench_rustis_pub_sub_fan_out(b, num_subscribers)
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(bench, bench_fan_out);
+criterion_main!(bench);
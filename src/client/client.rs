@@ -1,3 +1,5 @@
+#[cfg(feature = "metrics")]
+use crate::client::Metrics;
 #[cfg(test)]
 use crate::commands::DebugCommands;
 #[cfg(feature = "redis-graph")]
@@ -14,33 +16,41 @@ use crate::commands::{
 };
 use crate::{
     client::{
-        ClientState, ClientTrackingInvalidationStream, IntoConfig, Message, MonitorStream,
-        Pipeline, PreparedCommand, PubSubStream, Transaction,
+        ClientState, ClientTrackingInvalidationStream, Config, IntoConfig, Message, MonitorStream,
+        Pipeline, PreparedCommand, PubSubMessage, PubSubOverflowPolicy, PubSubStream, ScanStream,
+        ScanStreamOptions, ServerConfig, Transaction, Transport,
     },
     commands::{
-        BitmapCommands, BlockingCommands, ClusterCommands, ConnectionCommands, GenericCommands,
-        GeoCommands, HashCommands, HyperLogLogCommands, InternalPubSubCommands, ListCommands,
-        PubSubCommands, ScriptingCommands, SentinelCommands, ServerCommands, SetCommands,
-        SortedSetCommands, StreamCommands, StringCommands, TransactionCommands,
+        BitmapCommands, BlockingCommands, ClusterCommands, ClusterHealthStatus, ClusterNodeResult,
+        ClusterShardResult, ConnectionCommands, GenericCommands, GeoCommands, HashCommands,
+        HyperLogLogCommands, InternalPubSubCommands, ListCommands, MemoryUsageOptions,
+        NodeSelector, PubSubCommands, ScriptingCommands, SentinelCommands, ServerCommands,
+        SetCommands, SortedSetCommands, StreamCommands, StringCommands, TransactionCommands,
     },
     network::{
-        timeout, JoinHandle, MsgSender, NetworkHandler, PubSubReceiver, PubSubSender, PushReceiver,
-        PushSender, ReconnectReceiver, ReconnectSender, ResultReceiver, ResultSender,
-        ResultsReceiver, ResultsSender,
+        pub_sub_channel, sleep, timeout, JoinHandle, MsgSender, NetworkHandler, PubSubReceiver,
+        PubSubSender, PushReceiver, PushSender, ReconnectReceiver, ReconnectSender, ResultReceiver,
+        ResultSender, ResultsReceiver, ResultsSender, TransportHandler,
     },
-    resp::{cmd, Command, CommandArgs, RespBuf, Response, SingleArg, SingleArgCollection},
+    resp::{cmd, Command, CommandArgs, RespBuf, Response, SingleArg, SingleArgCollection, Value},
     Error, Future, Result,
 };
 use futures_channel::{mpsc, oneshot};
-use futures_util::Stream;
-use log::trace;
+use futures_util::{future, Stream, StreamExt};
+use log::{trace, warn};
 use serde::de::DeserializeOwned;
 use std::{
+    collections::HashMap,
     future::IntoFuture,
-    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
-    time::Duration,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock, RwLockReadGuard, RwLockWriteGuard,
+    },
+    time::{Duration, Instant},
 };
 
+const PUBLISH_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
 /// Client with a unique connection to a Redis server.
 #[derive(Clone)]
 pub struct Client {
@@ -49,7 +59,16 @@ pub struct Client {
     reconnect_sender: ReconnectSender,
     client_state: Arc<RwLock<ClientState>>,
     command_timeout: Duration,
+    subscribe_timeout: Duration,
     retry_on_error: bool,
+    pub_sub_buffer_size: usize,
+    pub_sub_overflow_policy: PubSubOverflowPolicy,
+    /// config used to open new connections, `None` for a client with a custom transport
+    config: Option<Arc<Config>>,
+    /// database currently selected on the connection, kept up to date by the network handler
+    selected_database: Arc<AtomicUsize>,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<dyn Metrics>>,
 }
 
 impl Drop for Client {
@@ -82,11 +101,23 @@ impl Client {
     /// Any Redis driver [`Error`](crate::Error) that occurs during the connection operation
     #[inline]
     pub async fn connect(config: impl IntoConfig) -> Result<Self> {
-        let config = config.into_config()?;
+        Self::connect_with(config.into_config()?, false).await
+    }
+
+    /// Connects to the Redis server, with a connection which is never reestablished
+    /// when `pinned` is `true`
+    async fn connect_with(config: Config, pinned: bool) -> Result<Self> {
         let command_timeout = config.command_timeout;
+        let subscribe_timeout = config.subscribe_timeout.unwrap_or(command_timeout);
         let retry_on_error = config.retry_on_error;
+        let pub_sub_buffer_size = config.pub_sub_buffer_size;
+        let pub_sub_overflow_policy = config.pub_sub_overflow_policy;
+        #[cfg(feature = "metrics")]
+        let metrics = config.metrics.clone();
+        let shared_config = Arc::new(config.clone());
+        let selected_database = Arc::new(AtomicUsize::new(config.database));
         let (msg_sender, network_task_join_handle, reconnect_sender) =
-            NetworkHandler::connect(config.into_config()?).await?;
+            NetworkHandler::connect(config, selected_database.clone(), pinned).await?;
 
         Ok(Self {
             msg_sender: Arc::new(Some(msg_sender)),
@@ -94,10 +125,205 @@ impl Client {
             reconnect_sender,
             client_state: Arc::new(RwLock::new(ClientState::new())),
             command_timeout,
+            subscribe_timeout,
             retry_on_error,
+            pub_sub_buffer_size,
+            pub_sub_overflow_policy,
+            config: Some(shared_config),
+            selected_database,
+            #[cfg(feature = "metrics")]
+            metrics,
         })
     }
 
+    /// Connects asynchronously to the Redis server, failing fast if it cannot be reached.
+    ///
+    /// With [`connect`](Client::connect), `connect_timeout` only bounds each TCP connection attempt.
+    /// Here, the whole connection establishment (TCP & TLS handshakes, authentication,
+    /// sentinel or cluster discovery) must complete within `connect_timeout`,
+    /// and errors mention the addresses which could not be reached.
+    ///
+    /// This is intended for health-check probes or command line tools
+    /// which must not hang on a wrong address.
+    ///
+    /// # Errors
+    /// * [`Error::Connection`](crate::Error::Connection) if the server cannot be reached,
+    ///   or if the connection cannot be established within `connect_timeout`
+    /// * Any other Redis driver [`Error`](crate::Error) that occurs during the connection operation
+    pub async fn try_connect(config: impl IntoConfig) -> Result<Self> {
+        let config = config.into_config()?;
+        let connect_timeout = config.connect_timeout;
+        let addresses = match &config.server {
+            ServerConfig::Standalone { host, port } => format!("{host}:{port}"),
+            ServerConfig::Sentinel(sentinel_config) => {
+                Self::join_addresses(&sentinel_config.instances)
+            }
+            ServerConfig::Cluster(cluster_config) => Self::join_addresses(&cluster_config.nodes),
+        };
+
+        match timeout(connect_timeout, Self::connect(config)).await {
+            Ok(Ok(client)) => Ok(client),
+            Ok(Err(Error::IO(e) | Error::Timeout(e))) => Err(Error::Connection(format!(
+                "Cannot connect to {addresses}: {e}"
+            ))),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(Error::Connection(format!(
+                "Cannot connect to {addresses} within {connect_timeout:?}"
+            ))),
+        }
+    }
+
+    /// Creates a client which exchanges commands and replies with a [`Transport`]
+    /// instead of a Redis server.
+    ///
+    /// This is mainly intended to unit test the encoding of commands without a running server:
+    /// see [`RecordingTransport`](crate::client::RecordingTransport).
+    ///
+    /// The client is created with the default [`Config`](crate::client::Config) options.
+    /// Pub/sub, monitor, and reconnection are not supported by this client.
+    pub fn with_transport(transport: impl Transport) -> Self {
+        Self::with_transport_and_config(transport, Config::default())
+    }
+
+    /// Creates a client which sends its commands to `transport` instead of a Redis server,
+    /// with the given [`Config`](crate::client::Config) options
+    ///
+    /// Only the options which do not relate to the connection are taken into account,
+    /// like [`command_timeout`](Config::command_timeout)
+    /// or [`retry_on_error`](Config::retry_on_error).
+    ///
+    /// See [`with_transport`](Client::with_transport)
+    pub fn with_transport_and_config(transport: impl Transport, config: Config) -> Self {
+        let (msg_sender, network_task_join_handle, reconnect_sender) =
+            TransportHandler::start(transport);
+
+        Self {
+            msg_sender: Arc::new(Some(msg_sender)),
+            network_task_join_handle: Arc::new(Some(network_task_join_handle)),
+            reconnect_sender,
+            client_state: Arc::new(RwLock::new(ClientState::new())),
+            command_timeout: config.command_timeout,
+            subscribe_timeout: config.subscribe_timeout.unwrap_or(config.command_timeout),
+            retry_on_error: config.retry_on_error,
+            pub_sub_buffer_size: config.pub_sub_buffer_size,
+            pub_sub_overflow_policy: config.pub_sub_overflow_policy,
+            config: None,
+            selected_database: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "metrics")]
+            metrics: config.metrics.clone(),
+        }
+    }
+
+    /// Hooks registered with [`Config::metrics`]
+    #[cfg(all(feature = "metrics", feature = "pool"))]
+    pub(crate) fn metrics(&self) -> Option<&dyn Metrics> {
+        self.metrics.as_deref()
+    }
+
+    /// Connects a new client to the logical database `index` of the same Redis server,
+    /// with the same configuration as this client.
+    ///
+    /// [`select`](ConnectionCommands::select) changes the database of the connection,
+    /// and thus of all the clones of a client which share this connection.
+    /// The returned client has its own connection instead, on which `index` is selected,
+    /// so that a single application can work with several databases side by side.
+    /// Its clones share this new connection, like for any other client.
+    ///
+    /// # Errors
+    /// * [`Error::Client`](crate::Error::Client) if this client has been created
+    ///   with [`with_transport`](Client::with_transport)
+    /// * Any Redis driver [`Error`](crate::Error) that occurs during the connection operation
+    pub async fn get_database(&self, index: usize) -> Result<Client> {
+        let Some(config) = &self.config else {
+            return Err(Error::Client(
+                "Cannot open a new connection from a client with a custom transport".to_owned(),
+            ));
+        };
+
+        let mut config = config.as_ref().clone();
+        config.database = index;
+        Self::connect(config).await
+    }
+
+    /// Runs `f` with a client pinned to its own connection to the Redis server,
+    /// with the same configuration as this client.
+    ///
+    /// All the clones of a client share the same connection, on which the commands of
+    /// concurrent tasks are interleaved. The client passed to `f` is not shared with
+    /// the other clones of this client, so all the commands sent by `f` hit the same socket,
+    /// one after the other, like for a sequence of [`watch`](crate::commands::TransactionCommands::watch),
+    /// reads and [`multi`](crate::commands::TransactionCommands::multi),
+    /// or for blocking commands which should not hold up the other tasks.
+    ///
+    /// A new connection is opened for each call, on the database currently selected by this client,
+    /// and closed once `f` returns.
+    /// This connection is never reestablished: if it is lost, the pending and following commands
+    /// sent by `f` fail instead of being replayed on another socket.
+    ///
+    /// # Errors
+    /// * [`Error::Client`](crate::Error::Client) if this client has been created
+    ///   with [`with_transport`](Client::with_transport) or with a cluster configuration
+    /// * Any Redis driver [`Error`](crate::Error) that occurs during the connection operation
+    /// * Any error returned by `f`
+    ///
+    /// # Example
+    /// ```
+    /// use rustis::{client::Client, commands::ConnectionCommands, Result};
+    ///
+    /// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+    /// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+    /// async fn main() -> Result<()> {
+    ///     let client = Client::connect("127.0.0.1:6379").await?;
+    ///
+    ///     let (id1, id2) = client
+    ///         .with_connection(|connection| async move {
+    ///             let id1 = connection.client_id().await?;
+    ///             let id2 = connection.client_id().await?;
+    ///             Ok((id1, id2))
+    ///         })
+    ///         .await?;
+    ///     assert_eq!(id1, id2);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn with_connection<F, Fut, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(Client) -> Fut,
+        Fut: std::future::Future<Output = Result<R>>,
+    {
+        let Some(config) = &self.config else {
+            return Err(Error::Client(
+                "Cannot open a new connection from a client with a custom transport".to_owned(),
+            ));
+        };
+
+        if let ServerConfig::Cluster(_) = config.server {
+            return Err(Error::Client(
+                "Cannot pin a single connection with a cluster configuration".to_owned(),
+            ));
+        }
+
+        let mut config = config.as_ref().clone();
+        config.retry_on_error = false;
+        config.database = self.selected_database.load(Ordering::Relaxed);
+        let connection = Self::connect_with(config, true).await?;
+
+        let result = f(connection.clone()).await;
+        if let Err(e) = connection.close().await {
+            warn!("Cannot close the pinned connection: {e}");
+        }
+        result
+    }
+
+    fn join_addresses(addresses: &[(String, u16)]) -> String {
+        addresses
+            .iter()
+            .map(|(host, port)| format!("{host}:{port}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
     /// if this client is the last client on the shared connection, the channel to send messages
     /// to the underlying network handler will be closed explicitely.
     ///
@@ -198,6 +424,20 @@ impl Client {
 
     #[inline]
     pub async fn send(&self, command: Command, retry_on_error: Option<bool>) -> Result<RespBuf> {
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            let name = command.name;
+            let start = Instant::now();
+            let result = self.send_single(command, retry_on_error).await;
+            metrics.on_command(name, start.elapsed(), result.as_ref().map(|_| ()));
+            return result;
+        }
+
+        self.send_single(command, retry_on_error).await
+    }
+
+    #[inline]
+    async fn send_single(&self, command: Command, retry_on_error: Option<bool>) -> Result<RespBuf> {
         let (result_sender, result_receiver): (ResultSender, ResultReceiver) = oneshot::channel();
         let message = Message::single(
             command,
@@ -213,6 +453,49 @@ impl Client {
         }
     }
 
+    /// Prepare an arbitrary command, to be sent to the Redis server
+    /// like any command of the typed API.
+    ///
+    /// This is an escape hatch for commands that do not have a typed API yet:
+    /// the response can be converted to any [`Response`](crate::resp::Response) type,
+    /// including the generic [`Value`](crate::resp::Value).
+    ///
+    /// # Arguments
+    /// * `command` - generic [`Command`](crate::resp::Command) built with [`cmd`](crate::resp::cmd).
+    ///
+    /// # Cluster
+    /// In cluster mode, the command is routed with the key specifications returned by
+    /// [`command`](crate::commands::ServerCommands::command): the command must be known by the server
+    /// and its keys must be passed at the positions declared by the server.
+    /// A command without key is sent to a random node.
+    ///
+    /// # Example
+    /// ```
+    /// use rustis::{
+    ///     client::Client,
+    ///     resp::{cmd, Value},
+    ///     Result,
+    /// };
+    ///
+    /// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+    /// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+    /// async fn main() -> Result<()> {
+    ///     let client = Client::connect("127.0.0.1:6379").await?;
+    ///
+    ///     client.send_command::<()>(cmd("SET").arg("key").arg("value")).await?;
+    ///     let value: Value = client.send_command(cmd("GET").arg("key")).await?;
+    ///     let value: String = value.into()?;
+    ///     assert_eq!("value", value);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn send_command<R: Response>(&self, command: Command) -> PreparedCommand<'_, &Self, R> {
+        PreparedCommand::new(self, command)
+    }
+
     /// Send command to the Redis server and forget its response.
     ///
     /// # Arguments
@@ -226,10 +509,18 @@ impl Client {
     /// Any Redis driver [`Error`](crate::Error) that occurs during the send operation
     #[inline]
     pub fn send_and_forget(&self, command: Command, retry_on_error: Option<bool>) -> Result<()> {
+        #[cfg(feature = "metrics")]
+        let name = command.name;
         let message =
             Message::single_forget(command, retry_on_error.unwrap_or(self.retry_on_error));
-        self.send_message(message)?;
-        Ok(())
+        let result = self.send_message(message);
+
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            metrics.on_send_and_forget(name, result.as_ref().map(|_| ()));
+        }
+
+        result
     }
 
     /// Send a batch of commands to the Redis server.
@@ -248,6 +539,27 @@ impl Client {
         &self,
         commands: Vec<Command>,
         retry_on_error: Option<bool>,
+    ) -> Result<Vec<RespBuf>> {
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            let names = commands.iter().map(|c| c.name).collect::<Vec<_>>();
+            let start = Instant::now();
+            let result = self.send_batch_inner(commands, retry_on_error).await;
+            let duration = start.elapsed();
+            for name in names {
+                metrics.on_command(name, duration, result.as_ref().map(|_| ()));
+            }
+            return result;
+        }
+
+        self.send_batch_inner(commands, retry_on_error).await
+    }
+
+    #[inline]
+    async fn send_batch_inner(
+        &self,
+        commands: Vec<Command>,
+        retry_on_error: Option<bool>,
     ) -> Result<Vec<RespBuf>> {
         let (results_sender, results_receiver): (ResultsSender, ResultsReceiver) =
             oneshot::channel();
@@ -290,13 +602,299 @@ impl Client {
         Pipeline::new(self)
     }
 
+    /// Create a stream over the keys of the current database,
+    /// iterated with successive [`scan_from`](GenericCommands::scan_from) calls.
+    ///
+    /// The `COUNT` of each call can be fixed or adapted to the number of returned keys:
+    /// see [`ScanStreamOptions`](crate::client::ScanStreamOptions).
+    ///
+    /// Like with [`scan`](GenericCommands::scan), a key may be returned multiple times.
+    /// In cluster mode, the keys of a single node are iterated:
+    /// use a client connected to each master to iterate the whole keyspace.
+    ///
+    /// # Example
+    /// ```
+    /// use rustis::{
+    ///     client::{Client, ScanStreamOptions},
+    ///     Result,
+    /// };
+    /// use futures_util::TryStreamExt;
+    ///
+    /// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+    /// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+    /// async fn main() -> Result<()> {
+    ///     let client = Client::connect("127.0.0.1:6379").await?;
+    ///
+    ///     let keys: Vec<String> = client
+    ///         .scan_stream(
+    ///             ScanStreamOptions::default()
+    ///                 .match_pattern("user:*")
+    ///                 .adaptive_count(1000, 100),
+    ///         )
+    ///         .try_collect()
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[inline]
+    pub fn scan_stream<K>(&self, options: ScanStreamOptions) -> ScanStream<K>
+    where
+        K: DeserializeOwned + Send + 'static,
+    {
+        ScanStream::new(self.clone(), options)
+    }
+
     /// Create a new pub sub stream with no upfront subscription
     #[inline]
     pub fn create_pub_sub(&self) -> PubSubStream {
-        let (pub_sub_sender, pub_sub_receiver): (PubSubSender, PubSubReceiver) = mpsc::unbounded();
+        let (pub_sub_sender, pub_sub_receiver) = self.create_pub_sub_channel();
         PubSubStream::new(pub_sub_sender, pub_sub_receiver, self.clone())
     }
 
+    /// Publishes a message on a channel until at least `num_receivers` clients have received it,
+    /// or until `timeout` has elapsed.
+    ///
+    /// This is useful right after a subscriber has been started,
+    /// when its subscription may not be registered by the server yet.
+    ///
+    /// Each attempt publishes the message again,
+    /// so clients already subscribed may receive it more than once.
+    ///
+    /// # Return
+    /// The number of clients that received the message on the last attempt.
+    pub async fn publish_to_at_least<C, M>(
+        &self,
+        channel: C,
+        message: M,
+        num_receivers: usize,
+        timeout: Duration,
+    ) -> Result<usize>
+    where
+        C: SingleArg + Clone + Send,
+        M: SingleArg + Clone + Send,
+    {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let count = self.publish(channel.clone(), message.clone()).await?;
+            if count >= num_receivers || Instant::now() >= deadline {
+                return Ok(count);
+            }
+
+            sleep(PUBLISH_RETRY_INTERVAL).await;
+        }
+    }
+
+    /// Subscribes to `channel`, waits for the next message published on it,
+    /// then unsubscribes.
+    ///
+    /// This is a shortcut for request/response patterns over pub/sub,
+    /// which avoids managing the lifecycle of a [`PubSubStream`].
+    ///
+    /// # Return
+    /// The first message received on the channel,
+    /// or `None` if no message has been received before `timeout` has elapsed.
+    pub async fn subscribe_once<C>(
+        &self,
+        channel: C,
+        timeout: Duration,
+    ) -> Result<Option<PubSubMessage>>
+    where
+        C: SingleArg + Send,
+    {
+        let mut pub_sub_stream = PubSubCommands::subscribe(self, channel).await?;
+        let result = crate::network::timeout(timeout, pub_sub_stream.next()).await;
+        if let Err(e) = pub_sub_stream.close().await {
+            warn!("Cannot unsubscribe after receiving a single message: {e}");
+        }
+
+        match result {
+            Ok(Some(message)) => message.map(Some),
+            Ok(None) | Err(Error::Timeout(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Removes `key` with a [`del`](GenericCommands::del) if its memory usage is smaller than
+    /// `size_threshold` bytes, or with an [`unlink`](GenericCommands::unlink) otherwise.
+    ///
+    /// The memory usage of the key is first retrieved with [`memory_usage`](ServerCommands::memory_usage),
+    /// so that large values are reclaimed in a background thread
+    /// instead of blocking the server.
+    ///
+    /// # Return
+    /// The number of keys that were removed.
+    pub async fn del_if_smaller_than<K>(&self, key: K, size_threshold: usize) -> Result<usize>
+    where
+        K: SingleArg + Clone + Send,
+    {
+        let Some(memory_usage) = self
+            .memory_usage(key.clone(), MemoryUsageOptions::default())
+            .await?
+        else {
+            return Ok(0);
+        };
+
+        if memory_usage < size_threshold {
+            self.del(key).await
+        } else {
+            self.unlink(key).await
+        }
+    }
+
+    /// Sends `command` to each of the cluster nodes selected by `target`
+    /// and collects their replies.
+    ///
+    /// Some commands, like [`config_set`](ServerCommands::config_set)
+    /// or [`script_load`](ScriptingCommands::script_load),
+    /// are only effective on the node which executes them.
+    /// On each call, a new connection is opened to each node with the configuration
+    /// of this client, and closed once the node has replied.
+    ///
+    /// # Return
+    /// The reply of each node, by node id.
+    /// A node which cannot be reached or which replies with an error,
+    /// or the master of a shard without any online master,
+    /// is reported in its entry, without failing the whole broadcast.
+    ///
+    /// # Errors
+    /// * [`Error::Client`](crate::Error::Client) if this client is not connected to a cluster
+    /// * Any Redis driver [`Error`](crate::Error) that occurs while retrieving the cluster nodes
+    pub async fn broadcast(
+        &self,
+        command: Command,
+        target: NodeSelector,
+    ) -> Result<HashMap<String, Result<Value>>> {
+        let Some(config) = self.cluster_config() else {
+            return Err(Error::Client(
+                "Broadcast is only supported in cluster mode".to_owned(),
+            ));
+        };
+
+        let nodes = self.get_cluster_nodes(target).await?;
+        let results = future::join_all(nodes.into_iter().map(|(id, node)| {
+            let command = command.clone();
+            async move {
+                let result = async {
+                    let node_client = Self::connect_to_node(config, &node?).await?;
+                    let result = node_client.send(command, None).await;
+                    Self::close_node_client(node_client).await;
+                    result?.to::<Value>()
+                }
+                .await;
+                (id, result)
+            }
+        }))
+        .await;
+
+        Ok(results.into_iter().collect())
+    }
+
+    /// Reloads the slot map of the cluster, i.e. which node serves which hash slot.
+    ///
+    /// The slot map is already reloaded after a `MOVED` redirection or a `CLUSTERDOWN` error,
+    /// and periodically with [`ClusterConfig::slots_refresh_interval`](crate::client::ClusterConfig::slots_refresh_interval).
+    /// This forces a reload after a known topology change, before any command is redirected.
+    ///
+    /// # Errors
+    /// * [`Error::Client`](crate::Error::Client) if this client is not connected to a cluster
+    /// * Any Redis driver [`Error`](crate::Error) that occurs while reloading the slot map
+    pub async fn refresh_cluster_slots(&self) -> Result<()> {
+        if self.cluster_config().is_none() {
+            return Err(Error::Client(
+                "Slot map refresh is only supported in cluster mode".to_owned(),
+            ));
+        }
+
+        let (refresh_slots_sender, refresh_slots_receiver) = oneshot::channel();
+        self.send_message(Message::refresh_cluster_slots(refresh_slots_sender))?;
+
+        refresh_slots_receiver.await?
+    }
+
+    /// Returns the config of this client if it is connected to a cluster
+    fn cluster_config(&self) -> Option<&Config> {
+        self.config
+            .as_deref()
+            .filter(|config| matches!(config.server, ServerConfig::Cluster(_)))
+    }
+
+    /// Checks if this client is connected to a cluster
+    pub(crate) fn is_cluster(&self) -> bool {
+        self.cluster_config().is_some()
+    }
+
+    /// Retrieves the online cluster nodes selected by `target`, with their node id.
+    ///
+    /// Unless only replicas are selected, a shard without any online master
+    /// is reported as an error for its master.
+    pub(crate) async fn get_cluster_nodes(
+        &self,
+        target: NodeSelector,
+    ) -> Result<Vec<(String, Result<ClusterNodeResult>)>> {
+        let shards: Vec<ClusterShardResult> = self.cluster_shards().await?;
+        let mut nodes = Vec::new();
+
+        for shard in shards {
+            let master_id = shard
+                .nodes
+                .iter()
+                .find(|node| node.role == "master")
+                .or_else(|| shard.nodes.first())
+                .map(|node| node.id.clone());
+            let mut has_master = false;
+
+            for node in shard.nodes {
+                if node.health != ClusterHealthStatus::Online {
+                    continue;
+                }
+
+                let is_master = node.role == "master";
+                has_master |= is_master;
+
+                let is_selected = match target {
+                    NodeSelector::AllMasters => is_master,
+                    NodeSelector::AllReplicas => !is_master,
+                    NodeSelector::All => true,
+                };
+
+                if is_selected {
+                    nodes.push((node.id.clone(), Ok(node)));
+                }
+            }
+
+            if !has_master && target != NodeSelector::AllReplicas {
+                let slots = format!("{:?}", shard.slots);
+                nodes.push((
+                    master_id.unwrap_or_else(|| slots.clone()),
+                    Err(Error::Client(format!(
+                        "Cluster misconfiguration: no online master for slots {slots}"
+                    ))),
+                ));
+            }
+        }
+
+        Ok(nodes)
+    }
+
+    /// Closes a client connected to a single node, logging instead of returning a failure
+    async fn close_node_client(node_client: Client) {
+        if let Err(e) = node_client.close().await {
+            warn!("Cannot close the connection to a cluster node: {e}");
+        }
+    }
+
+    /// Connects a standalone client to a single node of the cluster
+    async fn connect_to_node(config: &Config, node: &ClusterNodeResult) -> Result<Client> {
+        let mut node_config = config.clone();
+        node_config.server = ServerConfig::Standalone {
+            host: node.ip.clone(),
+            port: node.get_port()?,
+        };
+        Self::connect(node_config).await
+    }
+
     pub fn create_client_tracking_invalidation_stream(
         &self,
     ) -> Result<impl Stream<Item = Vec<String>>> {
@@ -306,6 +904,48 @@ impl Client {
         Ok(ClientTrackingInvalidationStream::new(push_receiver))
     }
 
+    fn create_pub_sub_channel(&self) -> (PubSubSender, PubSubReceiver) {
+        pub_sub_channel(self.pub_sub_buffer_size, self.pub_sub_overflow_policy)
+    }
+
+    /// Waits for the server to confirm a subscription,
+    /// within [`subscribe_timeout`](Config::subscribe_timeout)
+    ///
+    /// On timeout, the network handler is asked to forget the pending subscriptions
+    /// and to unsubscribe from `channels_or_patterns` with `unsubscribe_command`,
+    /// so that a late confirmation does not leave a subscription without any receiver.
+    async fn wait_for_subscription(
+        &self,
+        result_receiver: ResultReceiver,
+        unsubscribe_command: &'static str,
+        pub_sub_senders: Vec<(Vec<u8>, PubSubSender)>,
+    ) -> Result<()> {
+        let result = if self.subscribe_timeout != Duration::ZERO {
+            match timeout(self.subscribe_timeout, result_receiver).await {
+                Ok(result) => result?,
+                Err(e) => {
+                    let channels_or_patterns = pub_sub_senders
+                        .iter()
+                        .map(|(channel_or_pattern, _)| channel_or_pattern.clone())
+                        .collect::<Vec<_>>();
+                    let mut message = Message::single_forget(
+                        cmd(unsubscribe_command).arg(channels_or_patterns),
+                        false,
+                    );
+                    message.pub_sub_senders = Some(pub_sub_senders);
+                    if let Err(e) = self.send_message(message) {
+                        warn!("Cannot abandon timed out subscription: {e}");
+                    }
+                    return Err(e);
+                }
+            }
+        } else {
+            result_receiver.await?
+        };
+
+        result?.to::<()>()
+    }
+
     pub(crate) async fn subscribe_from_pub_sub_sender(
         &self,
         channels: &CommandArgs,
@@ -321,12 +961,13 @@ impl Client {
         let message = Message::pub_sub(
             cmd("SUBSCRIBE").arg(channels.clone()),
             result_sender,
-            pub_sub_senders,
+            pub_sub_senders.clone(),
         );
 
         self.send_message(message)?;
 
-        result_receiver.await??.to::<()>()
+        self.wait_for_subscription(result_receiver, "UNSUBSCRIBE", pub_sub_senders)
+            .await
     }
 
     pub(crate) async fn psubscribe_from_pub_sub_sender(
@@ -344,12 +985,13 @@ impl Client {
         let message = Message::pub_sub(
             cmd("PSUBSCRIBE").arg(patterns.clone()),
             result_sender,
-            pub_sub_senders,
+            pub_sub_senders.clone(),
         );
 
         self.send_message(message)?;
 
-        result_receiver.await??.to::<()>()
+        self.wait_for_subscription(result_receiver, "PUNSUBSCRIBE", pub_sub_senders)
+            .await
     }
 
     pub(crate) async fn ssubscribe_from_pub_sub_sender(
@@ -367,12 +1009,13 @@ impl Client {
         let message = Message::pub_sub(
             cmd("SSUBSCRIBE").arg(shardchannels.clone()),
             result_sender,
-            pub_sub_senders,
+            pub_sub_senders.clone(),
         );
 
         self.send_message(message)?;
 
-        result_receiver.await??.to::<()>()
+        self.wait_for_subscription(result_receiver, "SUNSUBSCRIBE", pub_sub_senders)
+            .await
     }
 }
 
@@ -391,7 +1034,11 @@ impl<'a, R: Response> ClientPreparedCommand<'a, R> for PreparedCommand<'a, &'a C
     ///
     /// # Errors
     /// Any Redis driver [`Error`](crate::Error) that occur during the send operation
-    fn forget(self) -> Result<()> {
+    fn forget(mut self) -> Result<()> {
+        if let Some(error) = self.take_error() {
+            return Err(error);
+        }
+
         self.executor
             .send_and_forget(self.command, self.retry_on_error)
     }
@@ -404,21 +1051,28 @@ where
     type Output = Result<R>;
     type IntoFuture = Future<'a, R>;
 
-    fn into_future(self) -> Self::IntoFuture {
+    fn into_future(mut self) -> Self::IntoFuture {
         Box::pin(async move {
+            if let Some(error) = self.take_error() {
+                return Err(error);
+            }
+
+            let command_name = self.command.name;
             if let Some(custom_converter) = self.custom_converter {
                 let command_for_result = self.command.clone();
                 let result = self
                     .executor
                     .send(self.command, self.retry_on_error)
                     .await?;
-                custom_converter(result, command_for_result, self.executor).await
+                custom_converter(result, command_for_result, self.executor)
+                    .await
+                    .map_err(|e| e.with_command(command_name))
             } else {
                 let result = self
                     .executor
                     .send(self.command, self.retry_on_error)
                     .await?;
-                result.to()
+                result.to().map_err(|e| e.with_command(command_name))
             }
         })
     }
@@ -480,7 +1134,7 @@ impl<'a> PubSubCommands<'a> for &'a Client {
     {
         let channels = CommandArgs::default().arg(channels).build();
 
-        let (pub_sub_sender, pub_sub_receiver): (PubSubSender, PubSubReceiver) = mpsc::unbounded();
+        let (pub_sub_sender, pub_sub_receiver) = self.create_pub_sub_channel();
 
         self.subscribe_from_pub_sub_sender(&channels, &pub_sub_sender)
             .await?;
@@ -501,7 +1155,7 @@ impl<'a> PubSubCommands<'a> for &'a Client {
     {
         let patterns = CommandArgs::default().arg(patterns).build();
 
-        let (pub_sub_sender, pub_sub_receiver): (PubSubSender, PubSubReceiver) = mpsc::unbounded();
+        let (pub_sub_sender, pub_sub_receiver) = self.create_pub_sub_channel();
 
         self.psubscribe_from_pub_sub_sender(&patterns, &pub_sub_sender)
             .await?;
@@ -522,7 +1176,7 @@ impl<'a> PubSubCommands<'a> for &'a Client {
     {
         let shardchannels = CommandArgs::default().arg(shardchannels).build();
 
-        let (pub_sub_sender, pub_sub_receiver): (PubSubSender, PubSubReceiver) = mpsc::unbounded();
+        let (pub_sub_sender, pub_sub_receiver) = self.create_pub_sub_channel();
 
         self.ssubscribe_from_pub_sub_sender(&shardchannels, &pub_sub_sender)
             .await?;
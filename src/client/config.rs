@@ -1,7 +1,15 @@
-use crate::{Error, Result};
+#[cfg(feature = "metrics")]
+use crate::client::Metrics;
+use crate::{client::Connector, Error, Result};
 #[cfg(feature = "tls")]
 use native_tls::{Certificate, Identity, Protocol, TlsConnector, TlsConnectorBuilder};
-use std::{collections::HashMap, str::FromStr, time::Duration};
+use std::{
+    collections::HashMap,
+    net::{Ipv6Addr, SocketAddr},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 use url::Url;
 
 const DEFAULT_PORT: u16 = 6379;
@@ -15,12 +23,14 @@ const DEFAULT_KEEP_ALIVE: Option<Duration> = None;
 const DEFAULT_NO_DELAY: bool = true;
 const DEFAULT_MAX_COMMAND_ATTEMPTS: usize = 3;
 const DEFAULT_RETRY_ON_ERROR: bool = false;
+const DEFAULT_PUB_SUB_BUFFER_SIZE: usize = 10_000;
+const DEFAULT_WRITE_BUFFER_SIZE: usize = 64 * 1024;
 
 type Uri<'a> = (
     &'a str,
     Option<&'a str>,
     Option<&'a str>,
-    Vec<(&'a str, u16)>,
+    Vec<(String, u16)>,
     Vec<&'a str>,
     Option<HashMap<String, String>>,
 );
@@ -47,6 +57,9 @@ pub struct Config {
     ///
     /// If `database` is not set to `0`, a [`SELECT`](https://redis.io/commands/select/)
     /// command will be automatically issued at connection or reconnection.
+    ///
+    /// A database selected afterwards with [`select`](crate::commands::ConnectionCommands::select)
+    /// replaces this one at reconnection.
     pub database: usize,
     /// An optional TLS configuration.
     #[cfg_attr(docsrs, doc(cfg(feature = "tls")))]
@@ -61,9 +74,25 @@ pub struct Config {
     ///
     /// The default is 0
     pub command_timeout: Duration,
+    /// If the server does not confirm a subscription
+    /// ([`subscribe`](crate::commands::PubSubCommands::subscribe),
+    /// [`psubscribe`](crate::commands::PubSubCommands::psubscribe)
+    /// or [`ssubscribe`](crate::commands::PubSubCommands::ssubscribe))
+    /// within this duration, a timeout error will be thrown
+    /// and the client unsubscribes from the channels or patterns of this subscription.
+    ///
+    /// The default is `None`, in which case [`command_timeout`](Config::command_timeout) applies
+    pub subscribe_timeout: Option<Duration>,
     /// When the client reconnects, channels subscribed in the previous connection will be
     /// resubscribed automatically if `auto_resubscribe` is `true`.
     ///
+    /// This is also how a subscribed connection recovers from a protocol error:
+    /// pub/sub streams yield the error once, then resume on the new connection.
+    /// If `auto_resubscribe` is `false`, the streams end after the error.
+    ///
+    /// In cluster mode, a shard channel whose slot has been migrated is also subscribed again
+    /// on its new node. If `auto_resubscribe` is `false`, its stream yields an error instead.
+    ///
     /// The default is `true`
     pub auto_resubscribe: bool,
     /// When the client reconnects, if in `monitor` mode, the
@@ -78,11 +107,17 @@ pub struct Config {
     pub connection_name: String,
     /// Enable/disable keep-alive functionality (default `None`)
     ///
+    /// When set, TCP keep-alive probes are sent after the socket has been idle for this duration,
+    /// so that dead peers are detected even without traffic.
+    ///
     /// See [`TcpKeepAlive::with_time`](https://docs.rs/socket2/latest/socket2/struct.TcpKeepalive.html#method.with_time)
     pub keep_alive: Option<Duration>,
     /// Enable/disable the use of Nagle's algorithm (default `true`)
     ///
-    /// See [`TcpStream::set_nodelay`](https://docs.rs/tokio/latest/tokio/net/struct.TcpStream.html#method.set_nodelay)    
+    /// Disabling it lets the OS batch small writes together,
+    /// which may reduce the number of packets at the cost of a higher latency.
+    ///
+    /// See [`TcpStream::set_nodelay`](https://docs.rs/tokio/latest/tokio/net/struct.TcpStream.html#method.set_nodelay)
     pub no_delay: bool,
     /// Maximum number of retry attempts to send a command to the Redis server (default `3`).
     pub max_command_attempts: usize,
@@ -99,6 +134,71 @@ pub struct Config {
     /// * [`Client::send_and_forget`](crate::client::Client::send_and_forget)
     /// * [`Client::send_batch`](crate::client::Client::send_batch)
     pub retry_on_error: bool,
+    /// Maximum number of messages buffered by a [`PubSubStream`](crate::client::PubSubStream)
+    /// which is not consumed fast enough (default `10000`).
+    ///
+    /// See [`pub_sub_overflow_policy`](Config::pub_sub_overflow_policy)
+    pub pub_sub_buffer_size: usize,
+    /// Defines what happens when the buffer of a [`PubSubStream`](crate::client::PubSubStream)
+    /// is full (default [`DropOldest`](PubSubOverflowPolicy::DropOldest)).
+    pub pub_sub_overflow_policy: PubSubOverflowPolicy,
+    /// If set, a [`PING`](https://redis.io/commands/ping/) command is sent
+    /// when a subscribed connection has been idle for this duration (default `None`).
+    ///
+    /// This prevents intermediaries (proxies, load balancers, firewalls)
+    /// from silently dropping pub/sub connections which do not receive any message.
+    pub pub_sub_keepalive: Option<Duration>,
+    /// If set, a [`PING`](https://redis.io/commands/ping/) command is sent
+    /// when a regular connection has been idle for this duration (default `None`).
+    ///
+    /// This prevents intermediaries (proxies, load balancers, firewalls)
+    /// from silently dropping idle connections, and detects a broken connection
+    /// so that the client reconnects before the next command is sent.
+    /// The `PING` is only sent when no reply is pending on the connection.
+    ///
+    /// See [`pub_sub_keepalive`](Config::pub_sub_keepalive) for subscribed connections.
+    pub keepalive_interval: Option<Duration>,
+    /// If set, commands sent concurrently on the same client are buffered
+    /// for up to this duration before being written together to the socket (default `None`).
+    ///
+    /// Coalescing many small commands in fewer writes improves the throughput
+    /// of latency-insensitive workloads, at the cost of an added latency for each command.
+    /// Buffered commands are written earlier if their number reaches an internal threshold.
+    ///
+    /// In an URI, this duration is expressed in milliseconds (`redis://127.0.0.1?auto_pipeline=1`)
+    /// or with a unit (`ms`, `s`, `m` or `h`).
+    pub auto_pipeline: Option<Duration>,
+    /// Maximum number of bytes of encoded commands buffered
+    /// before being flushed to the socket (default `65536`).
+    ///
+    /// Commands queued at the same time are encoded in a single buffer
+    /// which is written to the socket each time it reaches this size, and once at the end.
+    /// Replies are still matched to commands in the order they were sent.
+    ///
+    /// `0` disables buffering: each command is written to the socket as soon as it is encoded.
+    pub write_buffer_size: usize,
+    /// Optional custom connector used to establish connections
+    /// instead of a TCP connection to `host`:`port` (default `None`).
+    ///
+    /// When set, `tls_config` is ignored:
+    /// the connector is responsible for the encryption of the stream, if needed.
+    ///
+    /// This option cannot be set from an URI.
+    pub connector: Option<Arc<dyn Connector>>,
+    /// Optional [PROXY protocol](https://www.haproxy.org/download/2.8/doc/proxy-protocol.txt)
+    /// header sent at the beginning of each connection (default `None`).
+    ///
+    /// The header is written as soon as the connection is established,
+    /// before the TLS handshake if any, and before the `HELLO` command.
+    ///
+    /// This option cannot be set from an URI.
+    pub send_proxy_protocol: Option<ProxyProtocolHeader>,
+    /// Optional hooks called to observe the activity of the client (default `None`).
+    ///
+    /// This option cannot be set from an URI.
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    #[cfg(feature = "metrics")]
+    pub metrics: Option<Arc<dyn Metrics>>,
 }
 
 impl Default for Config {
@@ -112,6 +212,7 @@ impl Default for Config {
             tls_config: Default::default(),
             connect_timeout: Duration::from_millis(DEFAULT_CONNECT_TIMEOUT),
             command_timeout: Duration::from_millis(DEFAULT_COMMAND_TIMEOUT),
+            subscribe_timeout: None,
             auto_resubscribe: DEFAULT_AUTO_RESUBSCRTBE,
             auto_remonitor: DEFAULT_AUTO_REMONITOR,
             connection_name: String::from(""),
@@ -119,6 +220,16 @@ impl Default for Config {
             no_delay: DEFAULT_NO_DELAY,
             max_command_attempts: DEFAULT_MAX_COMMAND_ATTEMPTS,
             retry_on_error: DEFAULT_RETRY_ON_ERROR,
+            pub_sub_buffer_size: DEFAULT_PUB_SUB_BUFFER_SIZE,
+            pub_sub_overflow_policy: Default::default(),
+            pub_sub_keepalive: None,
+            keepalive_interval: None,
+            auto_pipeline: None,
+            write_buffer_size: DEFAULT_WRITE_BUFFER_SIZE,
+            connector: None,
+            send_proxy_protocol: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
         }
     }
 }
@@ -128,7 +239,7 @@ impl FromStr for Config {
 
     /// Build a config from an URI or a standard address format `host`:`port`
     fn from_str(str: &str) -> Result<Config> {
-        if let Some(config) = Self::parse_uri(str) {
+        if let Some(config) = Self::parse_uri(str)? {
             Ok(config)
         } else if let Some(addr) = Self::parse_addr(str) {
             addr.into_config()
@@ -139,31 +250,82 @@ impl FromStr for Config {
 }
 
 impl Config {
-    /// Build a config from an URI in the format `redis[s]://[[username]:password@]host[:port]/[database]`
+    /// Build a config from an URI in the format `redis[s]://[[username]:password@]host[:port]/[database][?parameters]`
+    ///
+    /// Query parameters are named after the fields of [`Config`] (e.g. `connect_timeout`).
+    /// `timeout` and `client_name` are accepted as aliases of `command_timeout` and `connection_name`,
+    /// and `protocol=3` is accepted for compatibility.
+    /// Durations are expressed in milliseconds, or with a unit: `ms`, `s`, `m` or `h` (e.g. `timeout=5s`).
+    ///
+    /// # Errors
+    /// An [`Error::Config`] is returned if the database index or the value of a parameter is invalid,
+    /// or if a parameter is unknown.
     pub fn from_uri(uri: Url) -> Result<Config> {
         Self::from_str(uri.as_str())
     }
 
     /// Parse address in the standard formart `host`:`port`
-    fn parse_addr(str: &str) -> Option<(&str, u16)> {
-        let mut iter = str.split(':');
-
-        match (iter.next(), iter.next(), iter.next()) {
-            (Some(host), Some(port), None) => {
-                if let Ok(port) = port.parse::<u16>() {
-                    Some((host, port))
-                } else {
-                    None
-                }
+    ///
+    /// An IPv6 host must be enclosed in brackets to be followed by a port (`[::1]:6379`).
+    /// A zone identifier can be appended to an IPv6 host,
+    /// percent-encoded in brackets as in URIs (`[fe80::1%25eth0]`).
+    fn parse_addr(str: &str) -> Option<(String, u16)> {
+        let (host, port) = if let Some(after_bracket) = str.strip_prefix('[') {
+            let (host, after_host) = after_bracket.split_once(']')?;
+            let host = host.replace("%25", "%");
+            if after_host.is_empty() {
+                (host, None)
+            } else {
+                (host, Some(after_host.strip_prefix(':')?))
             }
-            (Some(host), None, None) => Some((host, DEFAULT_PORT)),
-            _ => None,
+        } else if Self::is_ipv6(str) {
+            // IPv6 host without port
+            (str.to_owned(), None)
+        } else {
+            match str.split_once(':') {
+                Some((host, port)) => (host.to_owned(), Some(port)),
+                None => (str.to_owned(), None),
+            }
+        };
+
+        match port {
+            Some(port) => port.parse::<u16>().ok().map(|port| (host, port)),
+            None => Some((host, DEFAULT_PORT)),
         }
     }
 
-    fn parse_uri(uri: &str) -> Option<Config> {
-        let (scheme, username, password, hosts, path_segments, mut query) =
-            Self::break_down_uri(uri)?;
+    /// IPv6 address, with an optional zone identifier
+    fn is_ipv6(host: &str) -> bool {
+        let address = match host.split_once('%') {
+            Some((address, _zone)) => address,
+            None => host,
+        };
+        address.parse::<Ipv6Addr>().is_ok()
+    }
+
+    /// Format an address as `host`:`port`, enclosing IPv6 hosts in brackets
+    fn format_addr(host: &str, port: Option<u16>) -> String {
+        let mut s = if host.contains(':') {
+            format!("[{}]", host.replace('%', "%25"))
+        } else {
+            host.to_owned()
+        };
+
+        if let Some(port) = port {
+            s.push(':');
+            s.push_str(&port.to_string());
+        }
+
+        s
+    }
+
+    /// Parse an URI, `Ok(None)` is returned if `uri` is not a well-formed URI
+    fn parse_uri(uri: &str) -> Result<Option<Config>> {
+        let Some((scheme, username, password, hosts, path_segments, mut query)) =
+            Self::break_down_uri(uri)
+        else {
+            return Ok(None);
+        };
         let mut hosts = hosts;
         let mut path_segments = path_segments.into_iter();
 
@@ -186,7 +348,7 @@ impl Config {
                 (Some(TlsConfig::default()), ServerType::Cluster)
             }
             _ => {
-                return None;
+                return Ok(None);
             }
         };
 
@@ -196,32 +358,28 @@ impl Config {
             "redis+sentinel" | "redis-sentinel" => ServerType::Sentinel,
             "redis+cluster" | "redis-cluster" => ServerType::Cluster,
             _ => {
-                return None;
+                return Ok(None);
             }
         };
 
         let server = match server_type {
             ServerType::Standalone => {
                 if hosts.len() > 1 {
-                    return None;
+                    return Ok(None);
                 } else {
-                    let (host, port) = hosts.pop()?;
-                    ServerConfig::Standalone {
-                        host: host.to_owned(),
-                        port,
-                    }
+                    let Some((host, port)) = hosts.pop() else {
+                        return Ok(None);
+                    };
+                    ServerConfig::Standalone { host, port }
                 }
             }
             ServerType::Sentinel => {
-                let instances = hosts
-                    .iter()
-                    .map(|(host, port)| ((*host).to_owned(), *port))
-                    .collect::<Vec<_>>();
+                let instances = hosts;
 
                 let service_name = match path_segments.next() {
                     Some(service_name) => service_name.to_owned(),
                     None => {
-                        return None;
+                        return Ok(None);
                     }
                 };
 
@@ -232,10 +390,9 @@ impl Config {
                 };
 
                 if let Some(ref mut query) = query {
-                    if let Some(millis) = query.remove("wait_between_failures") {
-                        if let Ok(millis) = millis.parse::<u64>() {
-                            sentinel_config.wait_between_failures = Duration::from_millis(millis);
-                        }
+                    if let Some(value) = query.remove("wait_between_failures") {
+                        sentinel_config.wait_between_failures =
+                            Self::parse_duration("wait_between_failures", &value)?;
                     }
 
                     sentinel_config.username = query.remove("sentinel_username");
@@ -245,22 +402,28 @@ impl Config {
                 ServerConfig::Sentinel(sentinel_config)
             }
             ServerType::Cluster => {
-                let nodes = hosts
-                    .iter()
-                    .map(|(host, port)| ((*host).to_owned(), *port))
-                    .collect::<Vec<_>>();
+                let mut cluster_config = ClusterConfig {
+                    nodes: hosts,
+                    ..Default::default()
+                };
+
+                if let Some(ref mut query) = query {
+                    if let Some(value) = query.remove("slots_refresh_interval") {
+                        cluster_config.slots_refresh_interval =
+                            Some(Self::parse_duration("slots_refresh_interval", &value)?);
+                    }
+                }
 
-                ServerConfig::Cluster(ClusterConfig { nodes })
+                ServerConfig::Cluster(cluster_config)
             }
         };
 
         let database = match path_segments.next() {
-            Some(database) => match database.parse::<usize>() {
-                Ok(database) => database,
-                Err(_) => {
-                    return None;
-                }
-            },
+            Some(database) => database.parse::<usize>().map_err(|_| {
+                Error::Config(format!(
+                    "Invalid database index `{database}` in URI: it must be a non-negative integer"
+                ))
+            })?,
             None => DEFAULT_DATABASE,
         };
 
@@ -275,60 +438,132 @@ impl Config {
         };
 
         if let Some(ref mut query) = query {
-            if let Some(millis) = query.remove("connect_timeout") {
-                if let Ok(millis) = millis.parse::<u64>() {
-                    config.connect_timeout = Duration::from_millis(millis);
-                }
+            if let Some(value) = query.remove("connect_timeout") {
+                config.connect_timeout = Self::parse_duration("connect_timeout", &value)?;
             }
 
-            if let Some(millis) = query.remove("command_timeout") {
-                if let Ok(millis) = millis.parse::<u64>() {
-                    config.command_timeout = Duration::from_millis(millis);
+            // `timeout` is a common alias of `command_timeout`
+            for name in ["command_timeout", "timeout"] {
+                if let Some(value) = query.remove(name) {
+                    config.command_timeout = Self::parse_duration(name, &value)?;
                 }
             }
 
-            if let Some(auto_resubscribe) = query.remove("auto_resubscribe") {
-                if let Ok(auto_resubscribe) = auto_resubscribe.parse::<bool>() {
-                    config.auto_resubscribe = auto_resubscribe;
-                }
+            if let Some(value) = query.remove("auto_resubscribe") {
+                config.auto_resubscribe = Self::parse_query_value("auto_resubscribe", &value)?;
+            }
+
+            if let Some(value) = query.remove("auto_remonitor") {
+                config.auto_remonitor = Self::parse_query_value("auto_remonitor", &value)?;
             }
 
-            if let Some(auto_remonitor) = query.remove("auto_remonitor") {
-                if let Ok(auto_remonitor) = auto_remonitor.parse::<bool>() {
-                    config.auto_remonitor = auto_remonitor;
+            // `client_name` is a common alias of `connection_name`
+            for name in ["connection_name", "client_name"] {
+                if let Some(connection_name) = query.remove(name) {
+                    config.connection_name = connection_name;
                 }
             }
 
-            if let Some(connection_name) = query.remove("connection_name") {
-                config.connection_name = connection_name;
+            if let Some(value) = query.remove("keep_alive") {
+                config.keep_alive = Some(Self::parse_duration("keep_alive", &value)?);
             }
 
-            if let Some(keep_alive) = query.remove("keep_alive") {
-                if let Ok(keep_alive) = keep_alive.parse::<u64>() {
-                    config.keep_alive = Some(Duration::from_millis(keep_alive));
-                }
+            if let Some(value) = query.remove("no_delay") {
+                config.no_delay = Self::parse_query_value("no_delay", &value)?;
             }
 
-            if let Some(no_delay) = query.remove("no_delay") {
-                if let Ok(no_delay) = no_delay.parse::<bool>() {
-                    config.no_delay = no_delay;
-                }
+            if let Some(value) = query.remove("max_command_attempts") {
+                config.max_command_attempts =
+                    Self::parse_query_value("max_command_attempts", &value)?;
             }
 
-            if let Some(max_command_attempts) = query.remove("max_command_attempts") {
-                if let Ok(max_command_attempts) = max_command_attempts.parse::<usize>() {
-                    config.max_command_attempts = max_command_attempts;
-                }
+            if let Some(value) = query.remove("retry_on_error") {
+                config.retry_on_error = Self::parse_query_value("retry_on_error", &value)?;
             }
 
-            if let Some(retry_on_error) = query.remove("retry_on_error") {
-                if let Ok(retry_on_error) = retry_on_error.parse::<bool>() {
-                    config.retry_on_error = retry_on_error;
+            if let Some(value) = query.remove("pub_sub_buffer_size") {
+                config.pub_sub_buffer_size =
+                    Self::parse_query_value("pub_sub_buffer_size", &value)?;
+            }
+
+            if let Some(value) = query.remove("pub_sub_overflow_policy") {
+                config.pub_sub_overflow_policy = value.parse::<PubSubOverflowPolicy>()?;
+            }
+
+            if let Some(value) = query.remove("pub_sub_keepalive") {
+                config.pub_sub_keepalive = Some(Self::parse_duration("pub_sub_keepalive", &value)?);
+            }
+
+            if let Some(value) = query.remove("subscribe_timeout") {
+                config.subscribe_timeout =
+                    Some(Self::parse_duration("subscribe_timeout", &value)?);
+            }
+
+            if let Some(value) = query.remove("keepalive_interval") {
+                config.keepalive_interval =
+                    Some(Self::parse_duration("keepalive_interval", &value)?);
+            }
+
+            if let Some(value) = query.remove("auto_pipeline") {
+                config.auto_pipeline = Some(Self::parse_duration("auto_pipeline", &value)?);
+            }
+
+            if let Some(value) = query.remove("write_buffer_size") {
+                config.write_buffer_size = Self::parse_query_value("write_buffer_size", &value)?;
+            }
+
+            // rustis only speaks RESP3
+            if let Some(value) = query.remove("protocol") {
+                if value != "3" {
+                    return Err(Error::Config(format!(
+                        "Invalid value `{value}` for parameter `protocol`: only RESP3 (`3`) is supported"
+                    )));
                 }
             }
+
+            // every known parameter has been removed from the query
+            if let Some(name) = query.keys().min() {
+                return Err(Error::Config(format!("Unknown parameter `{name}` in URI")));
+            }
         }
 
-        Some(config)
+        Ok(Some(config))
+    }
+
+    /// Parse the value of a query parameter of an URI
+    fn parse_query_value<T: FromStr>(name: &str, value: &str) -> Result<T> {
+        value
+            .parse::<T>()
+            .map_err(|_| Error::Config(format!("Invalid value `{value}` for parameter `{name}`")))
+    }
+
+    /// Parse a duration in a query parameter of an URI
+    ///
+    /// The duration is expressed in milliseconds, unless suffixed by a unit:
+    /// `ms`, `s`, `m` or `h` (e.g. `500`, `500ms`, `5s`)
+    fn parse_duration(name: &str, value: &str) -> Result<Duration> {
+        let (number, unit_millis) = if let Some(number) = value.strip_suffix("ms") {
+            (number, 1)
+        } else if let Some(number) = value.strip_suffix('s') {
+            (number, 1_000)
+        } else if let Some(number) = value.strip_suffix('m') {
+            (number, 60_000)
+        } else if let Some(number) = value.strip_suffix('h') {
+            (number, 3_600_000)
+        } else {
+            (value, 1)
+        };
+
+        number
+            .parse::<u64>()
+            .ok()
+            .and_then(|number| number.checked_mul(unit_millis))
+            .map(Duration::from_millis)
+            .ok_or_else(|| {
+                Error::Config(format!(
+                    "Invalid duration `{value}` for parameter `{name}`: expected milliseconds or a number followed by `ms`, `s`, `m` or `h`"
+                ))
+            })
     }
 
     /// break down an uri in a tuple (scheme, username, password, hosts, path_segments)
@@ -468,11 +703,10 @@ impl ToString for Config {
 
         match &self.server {
             ServerConfig::Standalone { host, port } => {
-                s.push_str(host);
-                if *port != DEFAULT_PORT {
-                    s.push(':');
-                    s.push_str(&port.to_string());
-                }
+                s.push_str(&Self::format_addr(
+                    host,
+                    (*port != DEFAULT_PORT).then_some(*port),
+                ));
             }
             ServerConfig::Sentinel(SentinelConfig {
                 instances,
@@ -484,18 +718,18 @@ impl ToString for Config {
                 s.push_str(
                     &instances
                         .iter()
-                        .map(|(host, port)| format!("{host}:{port}"))
+                        .map(|(host, port)| Self::format_addr(host, Some(*port)))
                         .collect::<Vec<String>>()
                         .join(","),
                 );
                 s.push('/');
                 s.push_str(service_name);
             }
-            ServerConfig::Cluster(ClusterConfig { nodes }) => {
+            ServerConfig::Cluster(ClusterConfig { nodes, .. }) => {
                 s.push_str(
                     &nodes
                         .iter()
-                        .map(|(host, port)| format!("{host}:{port}"))
+                        .map(|(host, port)| Self::format_addr(host, Some(*port)))
                         .collect::<Vec<String>>()
                         .join(","),
                 );
@@ -606,6 +840,105 @@ impl ToString for Config {
             s.push_str(&format!("retry_on_error={}", self.retry_on_error));
         }
 
+        if self.pub_sub_buffer_size != DEFAULT_PUB_SUB_BUFFER_SIZE {
+            if !query_separator {
+                query_separator = true;
+                s.push('?');
+            } else {
+                s.push('&');
+            }
+            s.push_str(&format!("pub_sub_buffer_size={}", self.pub_sub_buffer_size));
+        }
+
+        if self.pub_sub_overflow_policy != PubSubOverflowPolicy::default() {
+            if !query_separator {
+                query_separator = true;
+                s.push('?');
+            } else {
+                s.push('&');
+            }
+            s.push_str(&format!(
+                "pub_sub_overflow_policy={}",
+                self.pub_sub_overflow_policy.as_str()
+            ));
+        }
+
+        if let Some(pub_sub_keepalive) = self.pub_sub_keepalive {
+            if !query_separator {
+                query_separator = true;
+                s.push('?');
+            } else {
+                s.push('&');
+            }
+            s.push_str(&format!(
+                "pub_sub_keepalive={}",
+                pub_sub_keepalive.as_millis()
+            ));
+        }
+
+        if let Some(subscribe_timeout) = self.subscribe_timeout {
+            if !query_separator {
+                query_separator = true;
+                s.push('?');
+            } else {
+                s.push('&');
+            }
+            s.push_str(&format!(
+                "subscribe_timeout={}",
+                subscribe_timeout.as_millis()
+            ));
+        }
+
+        if let Some(keepalive_interval) = self.keepalive_interval {
+            if !query_separator {
+                query_separator = true;
+                s.push('?');
+            } else {
+                s.push('&');
+            }
+            s.push_str(&format!(
+                "keepalive_interval={}",
+                keepalive_interval.as_millis()
+            ));
+        }
+
+        if let Some(auto_pipeline) = self.auto_pipeline {
+            if !query_separator {
+                query_separator = true;
+                s.push('?');
+            } else {
+                s.push('&');
+            }
+            s.push_str(&format!("auto_pipeline={}", auto_pipeline.as_millis()));
+        }
+
+        if self.write_buffer_size != DEFAULT_WRITE_BUFFER_SIZE {
+            if !query_separator {
+                query_separator = true;
+                s.push('?');
+            } else {
+                s.push('&');
+            }
+            s.push_str(&format!("write_buffer_size={}", self.write_buffer_size));
+        }
+
+        if let ServerConfig::Cluster(ClusterConfig {
+            slots_refresh_interval: Some(slots_refresh_interval),
+            ..
+        }) = &self.server
+        {
+            if !query_separator {
+                query_separator = true;
+                s.push('?');
+            } else {
+                s.push('&');
+            }
+            s.push_str(&format!(
+                "slots_refresh_interval={}",
+                slots_refresh_interval.as_millis()
+            ));
+        }
+
         if let ServerConfig::Sentinel(SentinelConfig {
             instances: _,
             service_name: _,
@@ -649,6 +982,108 @@ impl ToString for Config {
     }
 }
 
+/// Policy applied when the buffer of a [`PubSubStream`](crate::client::PubSubStream) is full.
+///
+/// See [`Config::pub_sub_buffer_size`](Config::pub_sub_buffer_size)
+///
+/// Errors forwarded to the stream are never dropped, whatever the policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PubSubOverflowPolicy {
+    /// Wait for the stream to be consumed before reading further from the connection.
+    ///
+    /// Because the connection is shared, a slow consumer stalls every other
+    /// command sent through the same client.
+    Block,
+    /// Drop the oldest buffered message to make room for the new one
+    #[default]
+    DropOldest,
+    /// Drop the new message and keep the buffered ones
+    DropNewest,
+}
+
+impl PubSubOverflowPolicy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PubSubOverflowPolicy::Block => "block",
+            PubSubOverflowPolicy::DropOldest => "drop_oldest",
+            PubSubOverflowPolicy::DropNewest => "drop_newest",
+        }
+    }
+}
+
+impl FromStr for PubSubOverflowPolicy {
+    type Err = Error;
+
+    fn from_str(str: &str) -> Result<PubSubOverflowPolicy> {
+        match str {
+            "block" => Ok(PubSubOverflowPolicy::Block),
+            "drop_oldest" => Ok(PubSubOverflowPolicy::DropOldest),
+            "drop_newest" => Ok(PubSubOverflowPolicy::DropNewest),
+            _ => Err(Error::Config(format!(
+                "Cannot parse pub/sub overflow policy from {str}"
+            ))),
+        }
+    }
+}
+
+/// Header of the [PROXY protocol](https://www.haproxy.org/download/2.8/doc/proxy-protocol.txt)
+///
+/// See [`Config::send_proxy_protocol`](Config::send_proxy_protocol)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyProtocolHeader {
+    /// Human-readable header (version 1) describing the original connection
+    V1 {
+        /// Address of the client of the original connection
+        source: SocketAddr,
+        /// Address of the proxy the original connection was made to
+        destination: SocketAddr,
+    },
+}
+
+impl ProxyProtocolHeader {
+    /// Encodes the header as it is sent on the connection
+    ///
+    /// # Example
+    /// ```
+    /// use rustis::client::ProxyProtocolHeader;
+    ///
+    /// let header = ProxyProtocolHeader::V1 {
+    ///     source: "192.168.0.1:56324".parse().unwrap(),
+    ///     destination: "192.168.0.11:443".parse().unwrap(),
+    /// };
+    ///
+    /// assert_eq!(
+    ///     b"PROXY TCP4 192.168.0.1 192.168.0.11 56324 443\r\n".to_vec(),
+    ///     header.to_bytes()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            ProxyProtocolHeader::V1 {
+                source,
+                destination,
+            } => {
+                let protocol = match (source, destination) {
+                    (SocketAddr::V4(_), SocketAddr::V4(_)) => "TCP4",
+                    (SocketAddr::V6(_), SocketAddr::V6(_)) => "TCP6",
+                    // addresses of different families cannot be described
+                    _ => return b"PROXY UNKNOWN\r\n".to_vec(),
+                };
+
+                format!(
+                    "PROXY {protocol} {} {} {} {}\r\n",
+                    source.ip(),
+                    destination.ip(),
+                    source.port(),
+                    destination.port()
+                )
+                .into_bytes()
+            }
+        }
+    }
+}
+
 /// Configuration for connecting to a Redis server
 #[derive(Debug, Clone)]
 pub enum ServerConfig {
@@ -710,6 +1145,14 @@ impl Default for SentinelConfig {
 pub struct ClusterConfig {
     /// An array of `(host, port)` tuples for each known cluster node.
     pub nodes: Vec<(String, u16)>,
+
+    /// If set, the slot map of the cluster is reloaded before sending a command
+    /// when it is older than this duration (default `None`).
+    ///
+    /// Independently of this option, the slot map is reloaded after a `MOVED` redirection
+    /// or a `CLUSTERDOWN` error, or on demand with
+    /// [`Client::refresh_cluster_slots`](crate::client::Client::refresh_cluster_slots).
+    pub slots_refresh_interval: Option<Duration>,
 }
 
 /// Config for TLS.
@@ -857,6 +1300,19 @@ impl<T: Into<String>> IntoConfig for (T, u16) {
     }
 }
 
+impl IntoConfig for SocketAddr {
+    fn into_config(self) -> Result<Config> {
+        let host = match self {
+            SocketAddr::V6(addr) if addr.scope_id() != 0 => {
+                format!("{}%{}", addr.ip(), addr.scope_id())
+            }
+            _ => self.ip().to_string(),
+        };
+
+        (host, self.port()).into_config()
+    }
+}
+
 impl IntoConfig for &str {
     fn into_config(self) -> Result<Config> {
         Config::from_str(self)
@@ -0,0 +1,77 @@
+use crate::Result;
+use std::{fmt, future::Future, pin::Pin};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+/// A bidirectional byte stream established by a [`Connector`]
+///
+/// This trait is implemented for any type implementing
+/// tokio's [`AsyncRead`] and [`AsyncWrite`] traits.
+/// With async-std, a stream implementing the `futures` IO traits
+/// can be adapted with `tokio_util::compat`.
+pub trait ConnectorStream: AsyncRead + AsyncWrite + Send + Unpin + 'static {}
+
+impl<T> ConnectorStream for T where T: AsyncRead + AsyncWrite + Send + Unpin + 'static {}
+
+/// Future returned by [`Connector::connect`]
+pub type ConnectFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Box<dyn ConnectorStream>>> + Send + 'a>>;
+
+/// A pluggable replacement of the TCP connection to a Redis server
+///
+/// A connector is registered with [`Config::connector`](crate::client::Config::connector)
+/// and is called each time a connection to a Redis server is (re)established,
+/// including the connections to the nodes of a cluster and to the sentinel instances.
+///
+/// This allows to resolve host names by other means than the system resolver
+/// (e.g. SRV records), to go through a proxy, or to plug a test harness.
+///
+/// When no connector is set, the client opens a TCP connection to `host`:`port`.
+///
+/// # Example
+/// ```
+/// use rustis::{
+///     client::{Client, ConnectFuture, Connector, IntoConfig},
+///     commands::StringCommands,
+///     Result,
+/// };
+/// use std::sync::Arc;
+///
+/// /// Connects to a local Redis server whatever the target
+/// struct LocalConnector;
+///
+/// impl Connector for LocalConnector {
+///     fn connect<'a>(&'a self, _host: &'a str, _port: u16) -> ConnectFuture<'a> {
+///         Box::pin(async move {
+///             let stream = tokio::net::TcpStream::connect("127.0.0.1:6379").await?;
+///             Ok(Box::new(stream) as _)
+///         })
+///     }
+/// }
+///
+/// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+/// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+/// async fn main() -> Result<()> {
+///     let mut config = "redis.internal:6379".into_config()?;
+///     config.connector = Some(Arc::new(LocalConnector));
+///
+///     let client = Client::connect(config).await?;
+///     client.set("key", "value").await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub trait Connector: Send + Sync {
+    /// Establishes a stream to the Redis server `host`:`port`
+    ///
+    /// The returned future is bounded by [`Config::connect_timeout`](crate::client::Config::connect_timeout).
+    ///
+    /// # Errors
+    /// The returned error is forwarded to the caller of the connection
+    fn connect<'a>(&'a self, host: &'a str, port: u16) -> ConnectFuture<'a>;
+}
+
+impl fmt::Debug for dyn Connector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Connector")
+    }
+}
@@ -1,6 +1,6 @@
 use smallvec::SmallVec;
 
-use crate::{resp::Command, PushSender, PubSubSender, RetryReason, network::{ResultSender, ResultsSender}};
+use crate::{resp::Command, PushSender, PubSubSender, RetryReason, network::{RefreshSlotsSender, ResultSender, ResultsSender}};
 
 #[cfg(debug_assertions)]
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -119,6 +119,7 @@ pub(crate) struct Message {
     pub commands: Commands,
     pub pub_sub_senders: Option<Vec<(Vec<u8>, PubSubSender)>>,
     pub push_sender: Option<PushSender>,
+    pub refresh_slots_sender: Option<RefreshSlotsSender>,
     pub retry_reasons: Option<SmallVec<[RetryReason; 10]>>,
     pub retry_on_error: bool,
     #[cfg(debug_assertions)]
@@ -133,6 +134,7 @@ impl Message {
             commands: Commands::Single(command, Some(result_sender)),
             pub_sub_senders: None,
             push_sender: None,
+            refresh_slots_sender: None,
             retry_reasons: None,
             retry_on_error,
             #[cfg(debug_assertions)]
@@ -146,6 +148,7 @@ impl Message {
             commands: Commands::Single(command, None),
             pub_sub_senders: None,
             push_sender: None,
+            refresh_slots_sender: None,
             retry_reasons: None,
             retry_on_error,
             #[cfg(debug_assertions)]
@@ -159,6 +162,7 @@ impl Message {
             commands: Commands::Batch(commands, results_sender),
             pub_sub_senders: None,
             push_sender: None,
+            refresh_slots_sender: None,
             retry_reasons: None,
             retry_on_error,
             #[cfg(debug_assertions)]
@@ -176,6 +180,7 @@ impl Message {
             commands: Commands::Single(command, Some(result_sender)),
             pub_sub_senders: Some(pub_sub_senders),
             push_sender: None,
+            refresh_slots_sender: None,
             retry_reasons: None,
             retry_on_error: true,
             #[cfg(debug_assertions)]
@@ -193,6 +198,7 @@ impl Message {
             commands: Commands::Single(command, Some(result_sender)),
             pub_sub_senders: None,
             push_sender: Some(push_sender),
+            refresh_slots_sender: None,
             retry_reasons: None,
             retry_on_error: true,
             #[cfg(debug_assertions)]
@@ -206,6 +212,21 @@ impl Message {
             commands: Commands::None,
             pub_sub_senders: None,
             push_sender: Some(push_sender),
+            refresh_slots_sender: None,
+            retry_reasons: None,
+            retry_on_error: false,
+            #[cfg(debug_assertions)]
+            message_seq: MESSAGE_SEQUENCE_COUNTER.fetch_add(1, Ordering::SeqCst),
+        }
+    }
+
+    #[inline(always)]
+    pub fn refresh_cluster_slots(refresh_slots_sender: RefreshSlotsSender) -> Self {
+        Message {
+            commands: Commands::None,
+            pub_sub_senders: None,
+            push_sender: None,
+            refresh_slots_sender: Some(refresh_slots_sender),
             retry_reasons: None,
             retry_on_error: false,
             #[cfg(debug_assertions)]
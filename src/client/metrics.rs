@@ -0,0 +1,174 @@
+use crate::Error;
+use std::{fmt, time::Duration};
+
+/// Hooks called by a [`Client`](crate::client::Client) to observe its activity
+///
+/// An implementation is registered with [`Config::metrics`](crate::client::Config::metrics)
+/// and is shared by all the clones of a client.
+///
+/// All the callbacks have an empty default implementation,
+/// so only the relevant ones need to be implemented.
+///
+/// Callbacks are called from the client or network tasks:
+/// they must be fast and must not block.
+///
+/// # Example
+/// ```
+/// use rustis::{
+///     client::{Client, Config, IntoConfig, Metrics},
+///     commands::StringCommands,
+///     Error, Result,
+/// };
+/// use std::{
+///     sync::{
+///         atomic::{AtomicUsize, Ordering},
+///         Arc,
+///     },
+///     time::Duration,
+/// };
+///
+/// #[derive(Default)]
+/// struct ErrorCounter(AtomicUsize);
+///
+/// impl Metrics for ErrorCounter {
+///     fn on_command(&self, _name: &str, _duration: Duration, result: std::result::Result<(), &Error>) {
+///         if result.is_err() {
+///             self.0.fetch_add(1, Ordering::Relaxed);
+///         }
+///     }
+/// }
+///
+/// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+/// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+/// async fn main() -> Result<()> {
+///     let error_counter = Arc::new(ErrorCounter::default());
+///
+///     let mut config = "127.0.0.1:6379".into_config()?;
+///     config.metrics = Some(error_counter.clone());
+///     let client = Client::connect(config).await?;
+///
+///     client.set("key", "value").await?;
+///     assert_eq!(0, error_counter.0.load(Ordering::Relaxed));
+///
+///     Ok(())
+/// }
+/// ```
+pub trait Metrics: Send + Sync {
+    /// Called when a command sent with [`Client::send`](crate::client::Client::send)
+    /// or [`Client::send_batch`](crate::client::Client::send_batch) completes
+    ///
+    /// # Arguments
+    /// * `name` - name of the command (e.g. `GET`)
+    /// * `duration` - time elapsed between the call and the reception of the reply
+    /// * `result` - the error returned to the caller, if any
+    ///
+    /// For a batch of commands, this callback is called for each command of the batch
+    /// with the duration and the result of the whole batch.
+    fn on_command(&self, _name: &str, _duration: Duration, _result: Result<(), &Error>) {}
+
+    /// Called when a command is sent with [`Client::send_and_forget`](crate::client::Client::send_and_forget)
+    ///
+    /// # Arguments
+    /// * `name` - name of the command (e.g. `PUBLISH`)
+    /// * `result` - the error returned to the caller if the command could not be queued
+    ///
+    /// No reply is awaited, so there is no duration to report.
+    fn on_send_and_forget(&self, _name: &str, _result: Result<(), &Error>) {}
+
+    /// Called when a pub/sub message is delivered to a [`PubSubStream`](crate::client::PubSubStream)
+    ///
+    /// # Arguments
+    /// * `channel` - channel on which the message has been published
+    fn on_pub_sub_message(&self, _channel: &[u8]) {}
+
+    /// Called when the connection to the Redis server has been reestablished
+    fn on_reconnect(&self) {}
+
+    /// Called when a client is checked out from a pool of clients
+    /// managed by [`PooledClientManager`](crate::client::PooledClientManager).
+    ///
+    /// Checkouts are detected when bb8 validates the client,
+    /// so they are not reported if the pool is built with `test_on_check_out(false)`.
+    fn on_pool_checkout(&self) {}
+}
+
+impl fmt::Debug for dyn Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Metrics")
+    }
+}
+
+/// [`Metrics`] implementation which reports to the [metrics](https://docs.rs/metrics) crate
+///
+/// Metrics are sent to the recorder installed in the application
+/// (e.g. a Prometheus exporter):
+/// * `rustis_commands_total` - counter labeled with `command` and `status` (`ok` or `error`)
+/// * `rustis_command_duration_seconds` - histogram labeled with `command`
+/// * `rustis_forgotten_commands_total` - counter labeled with `command` and `status`
+/// * `rustis_pub_sub_messages_total` - counter
+/// * `rustis_reconnections_total` - counter
+/// * `rustis_pool_checkouts_total` - counter
+///
+/// # Example
+/// ```
+/// use rustis::{
+///     client::{Client, IntoConfig, MetricsCrateRecorder},
+///     Result,
+/// };
+/// use std::sync::Arc;
+///
+/// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+/// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+/// async fn main() -> Result<()> {
+///     let mut config = "127.0.0.1:6379".into_config()?;
+///     config.metrics = Some(Arc::new(MetricsCrateRecorder));
+///     let _client = Client::connect(config).await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MetricsCrateRecorder;
+
+impl MetricsCrateRecorder {
+    fn status(result: Result<(), &Error>) -> &'static str {
+        match result {
+            Ok(()) => "ok",
+            Err(_) => "error",
+        }
+    }
+}
+
+impl Metrics for MetricsCrateRecorder {
+    fn on_command(&self, name: &str, duration: Duration, result: Result<(), &Error>) {
+        metrics::counter!(
+            "rustis_commands_total",
+            "command" => name.to_owned(),
+            "status" => Self::status(result)
+        )
+        .increment(1);
+        metrics::histogram!("rustis_command_duration_seconds", "command" => name.to_owned())
+            .record(duration.as_secs_f64());
+    }
+
+    fn on_send_and_forget(&self, name: &str, result: Result<(), &Error>) {
+        metrics::counter!(
+            "rustis_forgotten_commands_total",
+            "command" => name.to_owned(),
+            "status" => Self::status(result)
+        )
+        .increment(1);
+    }
+
+    fn on_pub_sub_message(&self, _channel: &[u8]) {
+        metrics::counter!("rustis_pub_sub_messages_total").increment(1);
+    }
+
+    fn on_reconnect(&self) {
+        metrics::counter!("rustis_reconnections_total").increment(1);
+    }
+
+    fn on_pool_checkout(&self) {
+        metrics::counter!("rustis_pool_checkouts_total").increment(1);
+    }
+}
@@ -121,6 +121,9 @@ The manager can be configured via [bb8](https://docs.rs/bb8/latest/bb8/) with a
 
 For you convenience, [bb8](https://docs.rs/bb8/latest/bb8/) is reexported from the **rustis** crate.
 
+With the `metrics` feature, each checkout is reported to `Metrics::on_pool_checkout`,
+as long as bb8 validates the clients on checkout (`test_on_check_out`, enabled by default).
+
 ```
 #[cfg(feature = "pool")]
 use rustis::{
@@ -413,6 +416,15 @@ Once the stream has been created, it is still possible to add additional subscri
 by calling [`subscribe`](PubSubStream::subscribe), [`psubscribe`](PubSubStream::psubscribe)
 or [`ssubscribe`](PubSubStream::ssubscribe) on the [`PubSubStream`] instance.
 
+Messages are buffered until the stream is consumed. If the stream is not consumed fast enough,
+at most [`Config::pub_sub_buffer_size`] messages are kept and [`Config::pub_sub_overflow_policy`]
+decides which messages are dropped. The number of dropped messages is exposed by
+[`PubSubStream::dropped_count`].
+
+By default, the stream only yields messages. To also observe when the server confirms
+a subscription or an unsubscription, wrap the stream with [`with_events`](PubSubStream::with_events):
+the returned [`PubSubEventStream`] yields [`PubSubEvent`]s.
+
 ### Split Stream Example
 
 To make it easy to modify subscriptions while iterating over messages, you can use the [`split`](PubSubStream::split) method to
@@ -463,7 +475,11 @@ mod client;
 mod client_state;
 mod client_tracking_invalidation_stream;
 mod config;
+mod connector;
 mod message;
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+#[cfg(feature = "metrics")]
+mod metrics;
 mod monitor_stream;
 mod pipeline;
 #[cfg_attr(docsrs, doc(cfg(feature = "pool")))]
@@ -471,13 +487,20 @@ mod pipeline;
 mod pooled_client_manager;
 mod prepared_command;
 mod pub_sub_stream;
+mod scan_stream;
+mod script_registry;
 mod transaction;
+mod transport;
 
 pub use client::*;
 pub use client_state::*;
 pub(crate) use client_tracking_invalidation_stream::*;
 pub use config::*;
+pub use connector::*;
 pub(crate) use message::*;
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+#[cfg(feature = "metrics")]
+pub use metrics::*;
 pub use monitor_stream::*;
 pub use pipeline::*;
 #[cfg_attr(docsrs, doc(cfg(feature = "pool")))]
@@ -485,4 +508,7 @@ pub use pipeline::*;
 pub use pooled_client_manager::*;
 pub use prepared_command::*;
 pub use pub_sub_stream::*;
+pub use scan_stream::*;
+pub use script_registry::*;
 pub use transaction::*;
+pub use transport::*;
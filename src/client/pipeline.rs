@@ -18,7 +18,7 @@ use crate::{
         SetCommands, SortedSetCommands, StreamCommands, StringCommands,
     },
     resp::{Command, RespBatchDeserializer, Response},
-    Result,
+    Error, Result,
 };
 use serde::de::DeserializeOwned;
 use std::iter::zip;
@@ -29,6 +29,8 @@ pub struct Pipeline<'a> {
     commands: Vec<Command>,
     forget_flags: Vec<bool>,
     retry_on_error: Option<bool>,
+    /// First error detected while preparing a queued command
+    error: Option<Error>,
 }
 
 impl<'a> Pipeline<'a> {
@@ -38,6 +40,7 @@ impl<'a> Pipeline<'a> {
             commands: Vec::new(),
             forget_flags: Vec::new(),
             retry_on_error: None,
+            error: None,
         }
     }
     /// Set a flag to override default `retry_on_error` behavior.
@@ -59,6 +62,12 @@ impl<'a> Pipeline<'a> {
         self.forget_flags.push(true);
     }
 
+    /// Record an error detected while preparing a command,
+    /// returned by [`execute`](Self::execute) instead of sending the batch
+    pub(crate) fn fail(&mut self, error: Error) {
+        self.error.get_or_insert(error);
+    }
+
     /// Execute the pipeline by the sending the queued command
     /// as a whole batch to the Redis server.
     ///
@@ -96,7 +105,14 @@ impl<'a> Pipeline<'a> {
     /// }
     /// ```    
     pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
         let num_commands = self.commands.len();
+        let command_names = zip(&self.commands, &self.forget_flags)
+            .filter_map(|(command, forget_flag)| if *forget_flag { None } else { Some(command.name) })
+            .collect::<Vec<_>>();
         let results = self
             .client
             .send_batch(self.commands, self.retry_on_error)
@@ -109,13 +125,16 @@ impl<'a> Pipeline<'a> {
 
             if filtered_results.len() == 1 {
                 let result = filtered_results.pop().unwrap();
-                result.to()
+                result.to().map_err(|e| e.with_command(command_names[0]))
             } else {
-                let deserializer = RespBatchDeserializer::new(&filtered_results);
+                let deserializer = RespBatchDeserializer::new(&filtered_results, &command_names);
                 T::deserialize(&deserializer)
             }
         } else {
-            results[0].to()
+            match command_names.first() {
+                Some(command_name) => results[0].to().map_err(|e| e.with_command(command_name)),
+                None => results[0].to(),
+            }
         }
     }
 }
@@ -134,14 +153,20 @@ pub trait BatchPreparedCommand<R = ()> {
 impl<'a, 'b, R: Response> BatchPreparedCommand for PreparedCommand<'a, &'a mut Pipeline<'b>, R> {
     /// Queue a command.
     #[inline]
-    fn queue(self) {
-        self.executor.queue(self.command)
+    fn queue(mut self) {
+        match self.take_error() {
+            Some(error) => self.executor.fail(error),
+            None => self.executor.queue(self.command),
+        }
     }
 
     /// Queue a command and forget its response.
     #[inline]
-    fn forget(self) {
-        self.executor.forget(self.command)
+    fn forget(mut self) {
+        match self.take_error() {
+            Some(error) => self.executor.fail(error),
+            None => self.executor.forget(self.command),
+        }
     }
 }
 
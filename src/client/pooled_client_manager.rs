@@ -3,7 +3,7 @@ use crate::{
     commands::ConnectionCommands,
     Error, Future, Result,
 };
-use bb8::ManageConnection;
+use bb8::{ManageConnection, Pool, PooledConnection, RunError};
 
 /// An object which manages a pool of clients, based on [bb8](https://docs.rs/bb8/latest/bb8/)
 pub struct PooledClientManager {
@@ -16,6 +16,21 @@ impl PooledClientManager {
             config: config.into_config()?,
         })
     }
+
+    /// Checks out a client from `pool`, like [`Pool::get`],
+    /// with the pool errors converted into a Redis driver [`Error`]
+    ///
+    /// # Errors
+    /// * [`Error::Timeout`] if no client is available within the connection timeout of the pool
+    /// * Any Redis driver [`Error`] that occurs while connecting a new client
+    pub async fn checkout(pool: &Pool<Self>) -> Result<PooledConnection<'_, Self>> {
+        pool.get().await.map_err(|e| match e {
+            RunError::User(e) => e,
+            RunError::TimedOut => {
+                Error::Timeout("cannot check out a client from the pool".to_owned())
+            }
+        })
+    }
 }
 
 impl ManageConnection for PooledClientManager {
@@ -39,6 +54,13 @@ impl ManageConnection for PooledClientManager {
     {
         Box::pin(async move {
             client.ping(Default::default()).await?;
+
+            // bb8 validates a client each time it is checked out
+            #[cfg(feature = "metrics")]
+            if let Some(metrics) = client.metrics() {
+                metrics.on_pool_checkout();
+            }
+
             Ok(())
         })
     }
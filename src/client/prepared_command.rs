@@ -1,7 +1,7 @@
 use crate::{
     client::Client,
-    resp::{Command, RespBuf, Response},
-    Future,
+    resp::{Command, CommandRouting, RespBuf, Response},
+    Error, Future,
 };
 use std::marker::PhantomData;
 
@@ -25,6 +25,8 @@ where
     pub custom_converter: Option<Box<CustomConverter<'a, R>>>,
     /// Flag to retry sending the command on network error.
     pub retry_on_error: Option<bool>,
+    /// Error detected while preparing the command, returned instead of sending it
+    error: Option<Error>,
 }
 
 impl<'a, E, R> PreparedCommand<'a, E, R>
@@ -40,9 +42,22 @@ where
             command,
             custom_converter: None,
             retry_on_error: None,
+            error: None,
         }
     }
 
+    /// Fail the command with `error` instead of sending it,
+    /// when its arguments are rejected on the client side.
+    pub(crate) fn error(mut self, error: Error) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    /// Take the error detected while preparing the command, if any
+    pub(crate) fn take_error(&mut self) -> Option<Error> {
+        self.error.take()
+    }
+
     /// Set the functor [`self.custom_converter`]
     pub fn custom_converter(mut self, custom_converter: Box<CustomConverter<'a, R>>) -> Self {
         self.custom_converter = Some(custom_converter);
@@ -57,6 +72,30 @@ where
         self
     }
 
+    /// In cluster mode, send the command to the master node
+    /// serving the hash slot of `key`.
+    ///
+    /// The keys of a command are inferred from the command specifications returned
+    /// by [`command`](crate::commands::ServerCommands::command).
+    /// This hint is useful for commands sent with
+    /// [`send_command`](Client::send_command) which are unknown by the server specifications,
+    /// like module commands, or whose keys cannot be inferred.
+    ///
+    /// Ignored outside of cluster mode.
+    pub fn route_by_key(mut self, key: impl Into<String>) -> Self {
+        self.command.routing = Some(CommandRouting::Key(key.into()));
+        self
+    }
+
+    /// In cluster mode, send the command to the node with the id `node_id`
+    /// (see [`cluster_myid`](crate::commands::ClusterCommands::cluster_myid)).
+    ///
+    /// Ignored outside of cluster mode.
+    pub fn route_to_node(mut self, node_id: impl Into<String>) -> Self {
+        self.command.routing = Some(CommandRouting::Node(node_id.into()));
+        self
+    }
+
     /// Get a reference to the command to send
     pub fn command(&self) -> &Command {
         &self.command
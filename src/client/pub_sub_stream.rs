@@ -1,5 +1,5 @@
 use crate::{
-    client::{Client, ClientPreparedCommand}, commands::InternalPubSubCommands, network::PubSubSender, resp::{ByteBufSeed, CommandArgs, SingleArg, SingleArgCollection}, Error, PubSubReceiver, Result
+    client::{Client, ClientPreparedCommand}, commands::{ConnectionCommands, InternalPubSubCommands, PingOptions}, network::PubSubSender, resp::{ByteBufSeed, CommandArgs, SingleArg, SingleArgCollection}, Error, PubSubReceiver, Result
 };
 use futures_util::{Stream, StreamExt};
 use serde::{
@@ -12,9 +12,22 @@ use std::{
     task::{Context, Poll},
 };
 
+/// Kind of a [`PubSubMessage`](PubSubMessage), depending on how it has been matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PubSubMessageKind {
+    /// Message published on a channel subscribed with [`subscribe`](crate::commands::PubSubCommands::subscribe)
+    Message,
+    /// Message published on a channel matching a pattern subscribed with [`psubscribe`](crate::commands::PubSubCommands::psubscribe)
+    PMessage,
+    /// Message published on a shard channel subscribed with [`ssubscribe`](crate::commands::PubSubCommands::ssubscribe)
+    SMessage,
+}
+
 /// Pub/Sub Message that can be streamed from [`PubSubStream`](PubSubStream)
 #[derive(Debug)]
 pub struct PubSubMessage {
+    /// How the message has been matched
+    pub kind: PubSubMessageKind,
     pub pattern: Vec<u8>,
     pub channel: Vec<u8>,
     pub payload: Vec<u8>,
@@ -26,13 +39,49 @@ impl<'de> Deserialize<'de> for PubSubMessage {
     where
         D: serde::Deserializer<'de>,
     {
-        struct PubSubMessageVisitor;
+        match PubSubEvent::deserialize(deserializer)? {
+            PubSubEvent::Message(message) => Ok(message),
+            PubSubEvent::Subscribed { .. } | PubSubEvent::Unsubscribed { .. } => {
+                Err(de::Error::custom("expected message, smessage or pmessage"))
+            }
+        }
+    }
+}
+
+/// Event that can be streamed from [`PubSubEventStream`](PubSubEventStream)
+#[derive(Debug)]
+pub enum PubSubEvent {
+    /// The server confirmed a subscription to a channel, a pattern or a shard channel
+    Subscribed {
+        /// Channel, pattern or shard channel subscribed to
+        channel: Vec<u8>,
+        /// Number of subscriptions of the connection after this one
+        count: usize,
+    },
+    /// The server confirmed an unsubscription from a channel, a pattern or a shard channel
+    Unsubscribed {
+        /// Channel, pattern or shard channel unsubscribed from
+        channel: Vec<u8>,
+        /// Number of subscriptions of the connection left after this one
+        count: usize,
+    },
+    /// Message received on a channel subscribed to
+    Message(PubSubMessage),
+}
+
+impl<'de> Deserialize<'de> for PubSubEvent {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct PubSubEventVisitor;
 
-        impl<'de> Visitor<'de> for PubSubMessageVisitor {
-            type Value = PubSubMessage;
+        impl<'de> Visitor<'de> for PubSubEventVisitor {
+            type Value = PubSubEvent;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("PubSubMessage")
+                formatter.write_str("PubSubEvent")
             }
 
             fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
@@ -47,36 +96,68 @@ impl<'de> Deserialize<'de> for PubSubMessage {
                     return Err(de::Error::invalid_length(1, &"more elements in sequence"));
                 };
 
+                match kind {
+                    "subscribe" | "psubscribe" | "ssubscribe" => {
+                        let Some(count) = seq.next_element::<usize>()? else {
+                            return Err(de::Error::invalid_length(2, &"more elements in sequence"));
+                        };
+
+                        return Ok(PubSubEvent::Subscribed {
+                            channel: channel_or_pattern,
+                            count,
+                        });
+                    }
+                    "unsubscribe" | "punsubscribe" | "sunsubscribe" => {
+                        let Some(count) = seq.next_element::<usize>()? else {
+                            return Err(de::Error::invalid_length(2, &"more elements in sequence"));
+                        };
+
+                        return Ok(PubSubEvent::Unsubscribed {
+                            channel: channel_or_pattern,
+                            count,
+                        });
+                    }
+                    _ => (),
+                }
+
                 let Ok(Some(channel_or_payload)) = seq.next_element_seed(ByteBufSeed) else {
                     return Err(de::Error::invalid_length(2, &"more elements in sequence"));
                 };
 
                 match kind {
-                    "message" | "smessage" => Ok(PubSubMessage {
+                    "message" => Ok(PubSubEvent::Message(PubSubMessage {
+                        kind: PubSubMessageKind::Message,
+                        pattern: vec![],
+                        channel: channel_or_pattern,
+                        payload: channel_or_payload,
+                    })),
+                    "smessage" => Ok(PubSubEvent::Message(PubSubMessage {
+                        kind: PubSubMessageKind::SMessage,
                         pattern: vec![],
                         channel: channel_or_pattern,
                         payload: channel_or_payload,
-                    }),
+                    })),
                     "pmessage" => {
                         let Ok(Some(payload)) = seq.next_element_seed(ByteBufSeed) else {
                             return Err(de::Error::invalid_length(3, &"more elements in sequence"));
                         };
 
-                        Ok(PubSubMessage {
+                        Ok(PubSubEvent::Message(PubSubMessage {
+                            kind: PubSubMessageKind::PMessage,
                             pattern: channel_or_pattern,
                             channel: channel_or_payload,
                             payload,
-                        })
+                        }))
                     }
                     _ => Err(de::Error::invalid_value(
                         de::Unexpected::Str(kind),
-                        &"message, smessage or pmessage",
+                        &"message, smessage, pmessage or a subscription confirmation",
                     )),
                 }
             }
         }
 
-        deserializer.deserialize_seq(PubSubMessageVisitor)
+        deserializer.deserialize_seq(PubSubEventVisitor)
     }
 }
 
@@ -203,6 +284,15 @@ impl PubSubSplitSink {
         Ok(())
     }
 
+    /// Sends a [`PING`](https://redis.io/commands/ping/) on the pub/sub connection and waits for the reply.
+    ///
+    /// This can be used to check that the connection is still alive while no message is received.
+    ///
+    /// See also [`Config::pub_sub_keepalive`](crate::client::Config::pub_sub_keepalive)
+    pub async fn ping(&mut self) -> Result<()> {
+        self.client.ping(PingOptions::default()).await
+    }
+
     /// Close the stream by cancelling all subscriptions
     /// Calling `close` allows to wait for all the unsubscriptions.
     /// `drop` will achieve the same process but silently in background
@@ -268,15 +358,57 @@ pub struct PubSubSplitStream {
     receiver: PubSubReceiver,
 }
 
+impl PubSubSplitStream {
+    /// Returns the next message if it has already been received, without waiting for one.
+    ///
+    /// This function never blocks:
+    /// * `Ok(Some(message))` - a message was buffered and is returned.
+    /// * `Ok(None)` - no message is ready yet.
+    ///
+    /// Unlike polling [`next`](futures_util::StreamExt::next) with
+    /// [`now_or_never`](futures_util::FutureExt::now_or_never),
+    /// a message is only taken from the buffer when it is returned.
+    pub fn try_next_now(&mut self) -> Result<Option<PubSubMessage>> {
+        loop {
+            match self.receiver.try_recv() {
+                Some(Ok(message)) => match message.to::<PubSubEvent>()? {
+                    PubSubEvent::Message(message) => return Ok(Some(message)),
+                    // subscription events are only yielded by `PubSubEventStream`
+                    PubSubEvent::Subscribed { .. } | PubSubEvent::Unsubscribed { .. } => continue,
+                },
+                Some(Err(e)) => return Err(e),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Number of messages dropped so far because this stream was not consumed fast enough.
+    ///
+    /// See [`Config::pub_sub_overflow_policy`](crate::client::Config::pub_sub_overflow_policy)
+    pub fn dropped_count(&self) -> u64 {
+        self.receiver.dropped_count()
+    }
+}
+
 impl Stream for PubSubSplitStream {
     type Item = Result<PubSubMessage>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        match self.get_mut().receiver.poll_next_unpin(cx) {
-            Poll::Ready(Some(Ok(message))) => Poll::Ready(Some(message.to())),
-            Poll::Ready(None) => Poll::Ready(None),
-            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
-            Poll::Pending => Poll::Pending,
+        let this = self.get_mut();
+        loop {
+            match this.receiver.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(message))) => match message.to::<PubSubEvent>() {
+                    Ok(PubSubEvent::Message(message)) => return Poll::Ready(Some(Ok(message))),
+                    // subscription events are only yielded by `PubSubEventStream`
+                    Ok(PubSubEvent::Subscribed { .. } | PubSubEvent::Unsubscribed { .. }) => {
+                        continue
+                    }
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                },
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Pending => return Poll::Pending,
+            }
         }
     }
 }
@@ -450,6 +582,39 @@ impl PubSubStream {
         self.split_sink.sunsubscribe(shardchannels).await
     }
 
+    /// Returns the next message if it has already been received, without waiting for one.
+    ///
+    /// This function never blocks:
+    /// * `Ok(Some(message))` - a message was buffered and is returned.
+    /// * `Ok(None)` - no message is ready yet, or the stream has been closed.
+    ///
+    /// Unlike polling [`next`](futures_util::StreamExt::next) with
+    /// [`now_or_never`](futures_util::FutureExt::now_or_never),
+    /// a message is only taken from the buffer when it is returned.
+    pub fn try_next_now(&mut self) -> Result<Option<PubSubMessage>> {
+        if self.split_sink.closed {
+            Ok(None)
+        } else {
+            self.split_stream.try_next_now()
+        }
+    }
+
+    /// Number of messages dropped so far because this stream was not consumed fast enough.
+    ///
+    /// See [`Config::pub_sub_overflow_policy`](crate::client::Config::pub_sub_overflow_policy)
+    pub fn dropped_count(&self) -> u64 {
+        self.split_stream.dropped_count()
+    }
+
+    /// Sends a [`PING`](https://redis.io/commands/ping/) on the pub/sub connection and waits for the reply.
+    ///
+    /// This can be used to check that the connection is still alive while no message is received.
+    ///
+    /// See also [`Config::pub_sub_keepalive`](crate::client::Config::pub_sub_keepalive)
+    pub async fn ping(&mut self) -> Result<()> {
+        self.split_sink.ping().await
+    }
+
     /// Splits this object into separate [`Sink`](PubSubSplitSink) and [`Stream`](PubSubSplitStream) objects.
     /// This can be useful when you want to split ownership between tasks. 
     pub fn split(self) -> (PubSubSplitSink, PubSubSplitStream) {
@@ -462,6 +627,62 @@ impl PubSubStream {
     pub async fn close(self) -> Result<()> {
         self.split_sink.close().await
     }
+
+    /// Wraps this stream to only yield the messages whose channel matches `predicate`.
+    ///
+    /// This is useful to narrow down the messages received through a broad pattern.
+    /// Messages which do not match are silently discarded, errors are always yielded.
+    pub fn filter_channel<F>(self, predicate: F) -> FilteredPubSubStream<F>
+    where
+        F: Fn(&[u8]) -> bool + Unpin,
+    {
+        FilteredPubSubStream {
+            inner: self,
+            predicate,
+        }
+    }
+
+    /// Wraps this stream to also yield the subscription state transitions,
+    /// as [`PubSubEvent`]s, alongside the messages.
+    ///
+    /// Only the (un)subscriptions confirmed after this call are reported:
+    /// to get the event of the first subscription, create the stream with
+    /// [`create_pub_sub`](Client::create_pub_sub) and subscribe through the returned [`PubSubEventStream`].
+    ///
+    /// # Example
+    /// ```
+    /// use rustis::{
+    ///     client::{Client, PubSubEvent},
+    ///     commands::PubSubCommands,
+    ///     Result,
+    /// };
+    /// use futures_util::StreamExt;
+    ///
+    /// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+    /// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+    /// async fn main() -> Result<()> {
+    ///     let pub_sub_client = Client::connect("127.0.0.1:6379").await?;
+    ///     let regular_client = Client::connect("127.0.0.1:6379").await?;
+    ///
+    ///     let mut pub_sub_stream = pub_sub_client.create_pub_sub().with_events();
+    ///     pub_sub_stream.subscribe("mychannel").await?;
+    ///
+    ///     regular_client.publish("mychannel", "mymessage").await?;
+    ///
+    ///     let event = pub_sub_stream.next().await.unwrap()?;
+    ///     assert!(matches!(event, PubSubEvent::Subscribed { count: 1, .. }));
+    ///     let event = pub_sub_stream.next().await.unwrap()?;
+    ///     assert!(matches!(event, PubSubEvent::Message(_)));
+    ///
+    ///     pub_sub_stream.close().await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_events(self) -> PubSubEventStream {
+        self.split_stream.receiver.set_events_enabled(true);
+        PubSubEventStream { inner: self }
+    }
 }
 
 impl Stream for PubSubStream {
@@ -476,3 +697,139 @@ impl Stream for PubSubStream {
         }
     }
 }
+
+/// A [`PubSubStream`] filtered by channel, returned by [`filter_channel`](PubSubStream::filter_channel)
+pub struct FilteredPubSubStream<F>
+where
+    F: Fn(&[u8]) -> bool + Unpin,
+{
+    inner: PubSubStream,
+    predicate: F,
+}
+
+impl<F> FilteredPubSubStream<F>
+where
+    F: Fn(&[u8]) -> bool + Unpin,
+{
+    /// Returns the underlying stream, without filter
+    pub fn into_inner(self) -> PubSubStream {
+        self.inner
+    }
+
+    /// Close the stream by cancelling all subscriptions
+    ///
+    /// See [`PubSubStream::close`]
+    pub async fn close(self) -> Result<()> {
+        self.inner.close().await
+    }
+}
+
+impl<F> Stream for FilteredPubSubStream<F>
+where
+    F: Fn(&[u8]) -> bool + Unpin,
+{
+    type Item = Result<PubSubMessage>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match this.inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(message))) if !(this.predicate)(&message.channel) => continue,
+                poll => return poll,
+            }
+        }
+    }
+}
+
+/// A [`PubSubStream`] yielding subscription state transitions alongside the messages,
+/// returned by [`with_events`](PubSubStream::with_events)
+pub struct PubSubEventStream {
+    inner: PubSubStream,
+}
+
+impl PubSubEventStream {
+    /// Subscribe to additional channels
+    pub async fn subscribe<C, CC>(&mut self, channels: CC) -> Result<()>
+    where
+        C: SingleArg + Send,
+        CC: SingleArgCollection<C>,
+    {
+        self.inner.subscribe(channels).await
+    }
+
+    /// Subscribe to additional patterns
+    pub async fn psubscribe<P, PP>(&mut self, patterns: PP) -> Result<()>
+    where
+        P: SingleArg + Send,
+        PP: SingleArgCollection<P>,
+    {
+        self.inner.psubscribe(patterns).await
+    }
+
+    /// Subscribe to additional shardchannels
+    pub async fn ssubscribe<C, CC>(&mut self, shardchannels: CC) -> Result<()>
+    where
+        C: SingleArg + Send,
+        CC: SingleArgCollection<C>,
+    {
+        self.inner.ssubscribe(shardchannels).await
+    }
+
+    /// Unsubscribe from the given channels
+    pub async fn unsubscribe<C, CC>(&mut self, channels: CC) -> Result<()>
+    where
+        C: SingleArg + Send,
+        CC: SingleArgCollection<C>,
+    {
+        self.inner.unsubscribe(channels).await
+    }
+
+    /// Unsubscribe from the given patterns
+    pub async fn punsubscribe<C, CC>(&mut self, patterns: CC) -> Result<()>
+    where
+        C: SingleArg + Send,
+        CC: SingleArgCollection<C>,
+    {
+        self.inner.punsubscribe(patterns).await
+    }
+
+    /// Unsubscribe from the given shardchannels
+    pub async fn sunsubscribe<C, CC>(&mut self, shardchannels: CC) -> Result<()>
+    where
+        C: SingleArg + Send,
+        CC: SingleArgCollection<C>,
+    {
+        self.inner.sunsubscribe(shardchannels).await
+    }
+
+    /// Returns the underlying stream, which only yields messages
+    pub fn into_inner(self) -> PubSubStream {
+        self.inner.split_stream.receiver.set_events_enabled(false);
+        self.inner
+    }
+
+    /// Close the stream by cancelling all subscriptions
+    ///
+    /// See [`PubSubStream::close`]
+    pub async fn close(self) -> Result<()> {
+        self.inner.close().await
+    }
+}
+
+impl Stream for PubSubEventStream {
+    type Item = Result<PubSubEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.inner.split_sink.closed {
+            return Poll::Ready(None);
+        }
+
+        match this.inner.split_stream.receiver.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(event))) => Poll::Ready(Some(event.to())),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
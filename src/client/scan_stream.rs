@@ -0,0 +1,175 @@
+use crate::{
+    client::Client,
+    commands::ScanCursor,
+    resp::{cmd, Command},
+    Future, Result,
+};
+use futures_util::{FutureExt, Stream};
+use serde::de::DeserializeOwned;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Default `COUNT` of the [`SCAN`](https://redis.io/commands/scan/) command on the server side
+const DEFAULT_SCAN_COUNT: usize = 10;
+
+/// Options for the [`scan_stream`](Client::scan_stream) function
+#[derive(Debug, Clone, Default)]
+pub struct ScanStreamOptions {
+    match_pattern: Option<String>,
+    type_: Option<String>,
+    count: Option<usize>,
+    adaptive_count: Option<(usize, usize)>,
+}
+
+impl ScanStreamOptions {
+    /// Only returns the keys matching the glob-style `match_pattern`
+    #[must_use]
+    pub fn match_pattern(mut self, match_pattern: impl Into<String>) -> Self {
+        self.match_pattern = Some(match_pattern.into());
+        self
+    }
+
+    /// Only returns the keys of the given type (`string`, `list`, `set`, `zset`, `hash`, `stream`...)
+    #[must_use]
+    pub fn type_(mut self, type_: impl Into<String>) -> Self {
+        self.type_ = Some(type_.into());
+        self
+    }
+
+    /// Amount of work done by the server for each call of the iteration (default `10`)
+    ///
+    /// With [`adaptive_count`](ScanStreamOptions::adaptive_count), this is the initial count.
+    #[must_use]
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = Some(count.max(1));
+        self
+    }
+
+    /// Adapts the `COUNT` of each call to the number of keys returned by the previous one.
+    ///
+    /// While calls return fewer than `target_batch_size` keys, the count is doubled,
+    /// up to `max_count`. When calls return more than twice `target_batch_size` keys,
+    /// the count is halved, down to the initial [`count`](ScanStreamOptions::count).
+    ///
+    /// Scanning a sparse keyspace with [`match_pattern`](ScanStreamOptions::match_pattern)
+    /// thus takes far fewer round trips, while each call stays short on a dense keyspace.
+    #[must_use]
+    pub fn adaptive_count(mut self, max_count: usize, target_batch_size: usize) -> Self {
+        self.adaptive_count = Some((max_count.max(1), target_batch_size.max(1)));
+        self
+    }
+}
+
+/// Stream over the keys of the current database,
+/// iterated with successive [`SCAN`](https://redis.io/commands/scan/) calls
+///
+/// See [`Client::scan_stream`]
+pub struct ScanStream<K> {
+    client: Client,
+    options: ScanStreamOptions,
+    cursor: ScanCursor,
+    count: usize,
+    keys: VecDeque<K>,
+    future: Option<Future<'static, (ScanCursor, Vec<K>)>>,
+    is_complete: bool,
+}
+
+impl<K> ScanStream<K>
+where
+    K: DeserializeOwned + Send + 'static,
+{
+    pub(crate) fn new(client: Client, options: ScanStreamOptions) -> Self {
+        let count = options.count.unwrap_or(DEFAULT_SCAN_COUNT);
+
+        Self {
+            client,
+            options,
+            cursor: ScanCursor::START,
+            count,
+            keys: VecDeque::new(),
+            future: None,
+            is_complete: false,
+        }
+    }
+
+    /// `COUNT` sent with the next `SCAN` call
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    fn scan_command(&self) -> Command {
+        let mut command = cmd("SCAN").arg(self.cursor);
+
+        if let Some(match_pattern) = &self.options.match_pattern {
+            command = command.arg("MATCH").arg(match_pattern.as_str());
+        }
+
+        if let Some(type_) = &self.options.type_ {
+            command = command.arg("TYPE").arg(type_.as_str());
+        }
+
+        command.arg("COUNT").arg(self.count)
+    }
+
+    fn scan(&self) -> Future<'static, (ScanCursor, Vec<K>)> {
+        let client = self.client.clone();
+        let command = self.scan_command();
+        async move { client.send(command, None).await?.to() }.boxed()
+    }
+
+    fn adapt_count(&mut self, num_keys: usize) {
+        let Some((max_count, target_batch_size)) = self.options.adaptive_count else {
+            return;
+        };
+        let min_count = self.options.count.unwrap_or(DEFAULT_SCAN_COUNT);
+
+        if num_keys < target_batch_size {
+            self.count = (self.count * 2).min(max_count.max(min_count));
+        } else if num_keys > target_batch_size * 2 {
+            self.count = (self.count / 2).max(min_count);
+        }
+    }
+}
+
+impl<K> Stream for ScanStream<K>
+where
+    K: DeserializeOwned + Send + Unpin + 'static,
+{
+    type Item = Result<K>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(key) = this.keys.pop_front() {
+                return Poll::Ready(Some(Ok(key)));
+            }
+
+            if this.is_complete {
+                return Poll::Ready(None);
+            }
+
+            let mut future = this.future.take().unwrap_or_else(|| this.scan());
+
+            match future.poll_unpin(cx) {
+                Poll::Pending => {
+                    this.future = Some(future);
+                    return Poll::Pending;
+                }
+                Poll::Ready(Ok((cursor, keys))) => {
+                    this.adapt_count(keys.len());
+                    this.is_complete = cursor.is_complete();
+                    this.cursor = cursor;
+                    this.keys.extend(keys);
+                }
+                Poll::Ready(Err(e)) => {
+                    this.is_complete = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{
+    client::Client,
+    commands::ScriptingCommands,
+    network::ReconnectReceiver,
+    resp::{cmd, Command, SingleArg, SingleArgCollection},
+    Error, RedisError, RedisErrorKind, Result,
+};
+use serde::de::DeserializeOwned;
+use std::{collections::HashMap, sync::Mutex};
+use tokio::sync::broadcast::error::TryRecvError;
+
+/// A named script of a [`ScriptRegistry`]
+#[derive(Debug, Clone)]
+struct RegisteredScript {
+    source: String,
+    sha1: String,
+}
+
+/// A registry of Lua scripts which can be called by name
+///
+/// Scripts are loaded once in the server script cache with
+/// [`script_load`](ScriptingCommands::script_load),
+/// then called with [`evalsha`](ScriptingCommands::evalsha), which avoids sending their source
+/// on each call.
+///
+/// The script cache of the server does not survive a restart or a
+/// [`script_flush`](ScriptingCommands::script_flush).
+/// Thus, all the scripts of the registry are loaded again
+/// after a reconnection of the client, or when the server replies
+/// with a `NOSCRIPT` error.
+///
+/// # Example
+/// ```
+/// use rustis::{
+///     client::{Client, ScriptRegistry},
+///     commands::GenericCommands,
+///     Result,
+/// };
+///
+/// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+/// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+/// async fn main() -> Result<()> {
+///     let client = Client::connect("127.0.0.1:6379").await?;
+///     client.del("counter").await?;
+///
+///     let registry = ScriptRegistry::new(client);
+///     registry
+///         .load("incrby", "return redis.call('INCRBY', KEYS[1], ARGV[1])")
+///         .await?;
+///
+///     let value: i64 = registry.evalsha("incrby", "counter", 5).await?;
+///     assert_eq!(5, value);
+///
+///     Ok(())
+/// }
+/// ```
+pub struct ScriptRegistry {
+    client: Client,
+    scripts: Mutex<HashMap<String, RegisteredScript>>,
+    reconnect_receiver: Mutex<ReconnectReceiver>,
+}
+
+impl ScriptRegistry {
+    /// Creates an empty registry whose scripts are loaded and called with `client`
+    pub fn new(client: Client) -> Self {
+        let reconnect_receiver = client.on_reconnect();
+        Self {
+            client,
+            scripts: Mutex::new(HashMap::new()),
+            reconnect_receiver: Mutex::new(reconnect_receiver),
+        }
+    }
+
+    /// Loads `source` into the script cache of the server and registers it as `name`
+    ///
+    /// A script previously registered with the same name is replaced.
+    ///
+    /// # Return
+    /// The SHA1 digest of the script
+    pub async fn load(&self, name: impl Into<String>, source: impl Into<String>) -> Result<String> {
+        let source = source.into();
+        let sha1: String = self.client.script_load(source.as_str()).await?;
+
+        self.scripts.lock().unwrap().insert(
+            name.into(),
+            RegisteredScript {
+                source,
+                sha1: sha1.clone(),
+            },
+        );
+
+        Ok(sha1)
+    }
+
+    /// Returns the SHA1 digest of the script registered as `name`
+    pub fn sha1(&self, name: &str) -> Option<String> {
+        self.scripts
+            .lock()
+            .unwrap()
+            .get(name)
+            .map(|script| script.sha1.clone())
+    }
+
+    /// Loads again all the registered scripts into the script cache of the server
+    pub async fn reload(&self) -> Result<()> {
+        let scripts = self
+            .scripts
+            .lock()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for script in scripts {
+            self.client
+                .script_load::<_, String>(script.source.as_str())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Calls the script registered as `name`
+    ///
+    /// # Return
+    /// The return value of the script
+    ///
+    /// # Errors
+    /// * [`Error::Client`](crate::Error::Client) if no script has been registered as `name`
+    /// * Any Redis driver [`Error`](crate::Error) that occurs during the call
+    pub async fn evalsha<K, KK, A, AA, R>(&self, name: &str, keys: KK, args: AA) -> Result<R>
+    where
+        K: SingleArg,
+        KK: SingleArgCollection<K>,
+        A: SingleArg,
+        AA: SingleArgCollection<A>,
+        R: DeserializeOwned,
+    {
+        let Some(sha1) = self.sha1(name) else {
+            return Err(Error::Client(format!("Unknown script `{name}`")));
+        };
+
+        if self.has_reconnected() {
+            self.reload().await?;
+        }
+
+        let command = cmd("EVALSHA")
+            .arg(sha1)
+            .arg(keys.num_args())
+            .arg(keys)
+            .arg(args);
+
+        match self.send(command.clone()).await {
+            Err(Error::Redis(RedisError {
+                kind: RedisErrorKind::NoScript,
+                description: _,
+            })) => {
+                self.reload().await?;
+                self.send(command).await
+            }
+            result => result,
+        }
+    }
+
+    async fn send<R: DeserializeOwned>(&self, command: Command) -> Result<R> {
+        self.client.send(command, None).await?.to()
+    }
+
+    /// Returns `true` if the client has reconnected since the last call
+    fn has_reconnected(&self) -> bool {
+        let mut reconnect_receiver = self.reconnect_receiver.lock().unwrap();
+        let mut reconnected = false;
+
+        loop {
+            match reconnect_receiver.try_recv() {
+                Ok(()) | Err(TryRecvError::Lagged(_)) => reconnected = true,
+                Err(TryRecvError::Empty) | Err(TryRecvError::Closed) => return reconnected,
+            }
+        }
+    }
+}
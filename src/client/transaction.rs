@@ -28,11 +28,18 @@ use crate::{
 use std::{fmt, marker::PhantomData};
 
 /// Represents an on-going [`transaction`](https://redis.io/docs/manual/transactions/) on a specific client instance.
+///
+/// Commands are buffered on the client side and only sent, surrounded by `MULTI` and `EXEC`,
+/// as a single batch by [`execute`](Transaction::execute).
+/// Dropping a transaction without executing it sends nothing to the server,
+/// so the connection can never be left in a `MULTI` state and no `DISCARD` is needed.
 pub struct Transaction {
     client: Client,
     commands: Vec<Command>,
     forget_flags: Vec<bool>,
     retry_on_error: Option<bool>,
+    /// First error detected while preparing a queued command
+    error: Option<Error>,
 }
 
 impl Transaction {
@@ -42,6 +49,7 @@ impl Transaction {
             commands: vec![cmd("MULTI")],
             forget_flags: Vec::new(),
             retry_on_error: None,
+            error: None,
         }
     }
 
@@ -64,6 +72,12 @@ impl Transaction {
         self.forget_flags.push(true);
     }
 
+    /// Record an error detected while preparing a command,
+    /// returned by [`execute`](Self::execute) instead of sending the batch
+    pub(crate) fn fail(&mut self, error: Error) {
+        self.error.get_or_insert(error);
+    }
+
     /// Execute the transaction by the sending the queued command
     /// as a whole batch to the Redis server.
     ///
@@ -100,6 +114,10 @@ impl Transaction {
     /// }
     /// ```
     pub async fn execute<T: DeserializeOwned>(mut self) -> Result<T> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+
         self.commands.push(cmd("EXEC"));
 
         let num_commands = self.commands.len();
@@ -264,13 +282,19 @@ where
 
 impl<'a, R: Response> BatchPreparedCommand for PreparedCommand<'a, &'a mut Transaction, R> {
     /// Queue a command into the transaction.
-    fn queue(self) {
-        self.executor.queue(self.command)
+    fn queue(mut self) {
+        match self.take_error() {
+            Some(error) => self.executor.fail(error),
+            None => self.executor.queue(self.command),
+        }
     }
 
     /// Queue a command into the transaction and forget its response.
-    fn forget(self) {
-        self.executor.forget(self.command)
+    fn forget(mut self) {
+        match self.take_error() {
+            Some(error) => self.executor.fail(error),
+            None => self.executor.forget(self.command),
+        }
     }
 }
 
@@ -0,0 +1,94 @@
+use crate::Result;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+/// A pluggable replacement for the network connection of a [`Client`](crate::client::Client)
+///
+/// A client created with [`Client::with_transport`](crate::client::Client::with_transport)
+/// hands the RESP encoding of each command to its transport, instead of writing it to a socket,
+/// and decodes the returned bytes as the reply of the command.
+///
+/// This is mainly intended to unit test the encoding of commands without a running Redis server.
+///
+/// See [`RecordingTransport`]
+pub trait Transport: Send + 'static {
+    /// Receives the RESP encoding of a single command and returns the RESP encoding of its reply
+    ///
+    /// # Errors
+    /// The returned error is forwarded to the caller of the command
+    fn send(&mut self, command: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// A [`Transport`] which records the bytes of each command and answers with canned replies
+///
+/// Clones share the same records and replies,
+/// so a clone can be kept to inspect the commands sent by the client.
+///
+/// # Example
+/// ```
+/// use rustis::{
+///     client::{Client, RecordingTransport},
+///     commands::StringCommands,
+///     Result,
+/// };
+///
+/// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+/// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+/// async fn main() -> Result<()> {
+///     let transport = RecordingTransport::new();
+///     transport.push_reply("$5\r\nvalue\r\n");
+///
+///     let client = Client::with_transport(transport.clone());
+///     let value: String = client.get("key").await?;
+///
+///     assert_eq!("value", value);
+///     assert_eq!(
+///         vec![b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n".to_vec()],
+///         transport.commands()
+///     );
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RecordingTransport {
+    state: Arc<Mutex<RecordingState>>,
+}
+
+#[derive(Debug, Default)]
+struct RecordingState {
+    commands: Vec<Vec<u8>>,
+    replies: VecDeque<Vec<u8>>,
+}
+
+impl RecordingTransport {
+    /// Creates a new transport without any record or canned reply
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues the RESP encoding of the reply to the next command
+    ///
+    /// When no reply is queued, commands are answered with `+OK\r\n`
+    pub fn push_reply(&self, reply: impl Into<Vec<u8>>) {
+        self.state.lock().unwrap().replies.push_back(reply.into());
+    }
+
+    /// Returns the RESP encoding of each command sent so far, in order
+    pub fn commands(&self) -> Vec<Vec<u8>> {
+        self.state.lock().unwrap().commands.clone()
+    }
+}
+
+impl Transport for RecordingTransport {
+    fn send(&mut self, command: &[u8]) -> Result<Vec<u8>> {
+        let mut state = self.state.lock().unwrap();
+        state.commands.push(command.to_vec());
+        Ok(state
+            .replies
+            .pop_front()
+            .unwrap_or_else(|| b"+OK\r\n".to_vec()))
+    }
+}
@@ -3,6 +3,7 @@ use crate::{
     resp::{
         cmd, CommandArgs, MultipleArgsCollection, SingleArg, SingleArgCollection, ToArgs,
     },
+    Error,
 };
 
 /// A group of Redis commands related to [`Bitmaps`](https://redis.io/docs/data-types/bitmaps/)
@@ -82,6 +83,11 @@ pub trait BitmapCommands<'a> {
     /// The size of the string stored in the destination key,
     /// that is equal to the size of the longest input string.
     ///
+    /// # Errors
+    /// An [`Error::Client`](crate::Error::Client) is returned without sending the command
+    /// if `operation` is [`BitOperation::Not`](BitOperation::Not)
+    /// and `keys` does not contain exactly one source key.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/bitop/>](https://redis.io/commands/bitop/)
     #[must_use]
@@ -97,7 +103,17 @@ pub trait BitmapCommands<'a> {
         K: SingleArg,
         KK: SingleArgCollection<K>,
     {
-        prepare_command(self, cmd("BITOP").arg(operation).arg(dest_key).arg(keys))
+        let invalid = matches!(operation, BitOperation::Not) && keys.num_args() != 1;
+        let prepared_command =
+            prepare_command(self, cmd("BITOP").arg(operation).arg(dest_key).arg(keys));
+
+        if invalid {
+            prepared_command.error(Error::Client(
+                "BITOP NOT must be called with a single source key".to_owned(),
+            ))
+        } else {
+            prepared_command
+        }
     }
 
     /// Perform a bitwise operation between multiple keys (containing string values)
@@ -121,11 +137,12 @@ pub trait BitmapCommands<'a> {
     ///
     /// # Return
     /// The bit value stored at offset.
+    /// Bits beyond the length of the string are always `false`.
     ///
     /// # See Also
     /// [<https://redis.io/commands/getbit/>](https://redis.io/commands/getbit/)
     #[must_use]
-    fn getbit<K>(self, key: K, offset: u64) -> PreparedCommand<'a, Self, u64>
+    fn getbit<K>(self, key: K, offset: u64) -> PreparedCommand<'a, Self, bool>
     where
         Self: Sized,
         K: SingleArg,
@@ -135,13 +152,16 @@ pub trait BitmapCommands<'a> {
 
     /// Sets or clears the bit at offset in the string value stored at key.
     ///
+    /// The string is grown to make sure it can hold a bit at offset,
+    /// so large offsets allocate the whole string up to that bit.
+    ///
     /// # Return
     /// The original bit value stored at offset.
     ///
     /// # See Also
     /// [<https://redis.io/commands/setbit/>](https://redis.io/commands/setbit/)
     #[must_use]
-    fn setbit<K>(self, key: K, offset: u64, value: u64) -> PreparedCommand<'a, Self, u64>
+    fn setbit<K>(self, key: K, offset: u64, value: bool) -> PreparedCommand<'a, Self, bool>
     where
         Self: Sized,
         K: SingleArg,
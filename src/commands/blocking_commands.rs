@@ -1,6 +1,6 @@
 use crate::{
     client::{prepare_command, MonitorStream, PreparedCommand},
-    commands::{LMoveWhere, ZMPopResult, ZWhere},
+    commands::{LMoveWhere, StreamEntry, ZMPopResult, ZWhere},
     resp::{cmd, deserialize_vec_of_triplets, PrimitiveResponse, SingleArg, SingleArgCollection},
     Result,
 };
@@ -278,6 +278,53 @@ pub trait BlockingCommands<'a> {
         prepare_command(self, cmd("BZPOPMIN").arg(keys).arg(timeout))
     }
 
+    /// This command is the blocking variant of [`xread`](crate::commands::StreamCommands::xread).
+    ///
+    /// It blocks the connection until an entry with an ID greater than the given one
+    /// is added to any of the given streams, or until the timeout expires.
+    ///
+    /// # Arguments
+    /// * `timeout` - maximum time to block, in milliseconds (`0` blocks indefinitely)
+    /// * `count` - optional maximum number of entries returned per stream
+    /// * `keys` - names of the streams
+    /// * `ids` - for each stream, the last ID received by the caller,
+    ///   or the special `$` ID to only receive entries added after the call.
+    ///
+    /// # Return
+    /// * `None` when no entry was added and the timeout expired
+    /// * The list of streams which received entries, with their entries
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/xread/>](https://redis.io/commands/xread/)
+    #[must_use]
+    #[allow(clippy::complexity)]
+    fn xread_block<K, KK, I, II, V>(
+        self,
+        timeout: u64,
+        count: Option<usize>,
+        keys: KK,
+        ids: II,
+    ) -> PreparedCommand<'a, Self, Option<Vec<(String, Vec<StreamEntry<V>>)>>>
+    where
+        Self: Sized,
+        K: SingleArg,
+        KK: SingleArgCollection<K>,
+        I: SingleArg,
+        II: SingleArgCollection<I>,
+        V: PrimitiveResponse + DeserializeOwned,
+    {
+        prepare_command(
+            self,
+            cmd("XREAD")
+                .arg(count.map(|c| ("COUNT", c)))
+                .arg("BLOCK")
+                .arg(timeout)
+                .arg("STREAMS")
+                .arg(keys)
+                .arg(ids),
+        )
+    }
+
     /// Debugging command that streams back every command processed by the Redis server.
     ///
     /// # See Also
@@ -4,12 +4,13 @@ use crate::{
     client::{prepare_command, PreparedCommand},
     resp::{
         cmd, deserialize_vec_of_pairs, CollectionResponse, CommandArgs, KeyValueArgsCollection,
-        PrimitiveResponse, SingleArg, SingleArgCollection, ToArgs,
+        PrimitiveResponse, Response, SingleArg, SingleArgCollection, ToArgs,
     },
+    Error,
 };
 use serde::{
     de::{self, DeserializeOwned},
-    Deserialize,
+    Deserialize, Deserializer,
 };
 
 /// A group of Redis commands related to [`Cluster Management`](https://redis.io/docs/management/scaling/)
@@ -37,6 +38,9 @@ pub trait ClusterCommands<'a> {
     /// If the command is successful, the node will map the specified hash slots to itself,
     /// and will start broadcasting the new configuration.
     ///
+    /// As other slot administration commands, it must be sent to a specific node
+    /// with a standalone client, not through a cluster client.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/cluster-addslots/>](https://redis.io/commands/cluster-addslots/)
     #[must_use]
@@ -109,7 +113,7 @@ pub trait ClusterCommands<'a> {
     /// # See Also
     /// [<https://redis.io/commands/cluster-countkeysinslot/>](https://redis.io/commands/cluster-countkeysinslot/)
     #[must_use]
-    fn cluster_countkeysinslot(self, slot: usize) -> PreparedCommand<'a, Self, usize>
+    fn cluster_countkeysinslot(self, slot: u16) -> PreparedCommand<'a, Self, usize>
     where
         Self: Sized,
     {
@@ -119,7 +123,10 @@ pub trait ClusterCommands<'a> {
     /// In Redis Cluster, each node keeps track of which master is serving a particular hash slot.
     /// This command asks a particular Redis Cluster node to forget which master
     ///  is serving the hash slots specified as arguments.
-
+    ///
+    /// As other slot administration commands, it must be sent to a specific node
+    /// with a standalone client, not through a cluster client.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/cluster-delslots/>](https://redis.io/commands/cluster-delslots/)
     #[must_use]
@@ -131,12 +138,13 @@ pub trait ClusterCommands<'a> {
         prepare_command(self, cmd("CLUSTER").arg("DELSLOTS").arg(slots))
     }
 
-    /// This command is similar to the [`cluster_delslotsrange`](ClusterCommands::cluster_delslotsrange)
+    /// This command is similar to the [`cluster_delslots`](ClusterCommands::cluster_delslots)
     ///  command in that they both remove hash slots from the node.
     ///
-    /// The difference is that [`cluster_delslotsrange`](ClusterCommands::cluster_delslotsrange)
+    /// The difference is that [`cluster_delslots`](ClusterCommands::cluster_delslots)
     ///  takes a list of hash slots to remove from the node,
     /// while this command takes a list of slot ranges (specified by a tuple containing start and end slots) to remove from the node.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/cluster-delslotsrange/>](https://redis.io/commands/cluster-delslotsrange/)
     #[must_use]
@@ -198,12 +206,17 @@ pub trait ClusterCommands<'a> {
     /// The maximum number of keys to return is specified via the count argument,
     /// so that it is possible for the user of this API to batch-processing keys.
     ///
+    /// # Return
+    /// A list of at most `count` key names
+    ///
     /// # See Also
     /// [<https://redis.io/commands/cluster-getkeysinslot/>](https://redis.io/commands/cluster-getkeysinslot/)
     #[must_use]
-    fn cluster_getkeysinslot(self, slot: u16, count: usize) -> PreparedCommand<'a, Self, ()>
+    fn cluster_getkeysinslot<K, A>(self, slot: u16, count: usize) -> PreparedCommand<'a, Self, A>
     where
         Self: Sized,
+        K: PrimitiveResponse + DeserializeOwned,
+        A: CollectionResponse<K> + DeserializeOwned,
     {
         prepare_command(
             self,
@@ -319,13 +332,15 @@ pub trait ClusterCommands<'a> {
     /// The serialized cluster configuration.
     /// The output of the command is just a space-separated CSV string, where each line represents a node in the cluster.
     ///
+    /// This string can be parsed by requesting a [`ClusterNodesResult`].
+    ///
     /// # See Also
     /// [<https://redis.io/commands/cluster-nodes/>](https://redis.io/commands/cluster-nodes/)
     #[must_use]
     fn cluster_nodes<R>(self) -> PreparedCommand<'a, Self, R>
     where
         Self: Sized,
-        R: PrimitiveResponse,
+        R: Response,
     {
         prepare_command(self, cmd("CLUSTER").arg("NODES"))
     }
@@ -342,7 +357,7 @@ pub trait ClusterCommands<'a> {
     where
         Self: Sized,
         I: SingleArg,
-        R: PrimitiveResponse,
+        R: Response,
     {
         prepare_command(self, cmd("CLUSTER").arg("REPLICAS").arg(node_id))
     }
@@ -403,6 +418,9 @@ pub trait ClusterCommands<'a> {
 
     /// This command is responsible of changing the state of a hash slot in the receiving node in different ways.
     ///
+    /// As other slot administration commands, it must be sent to a specific node
+    /// with a standalone client, not through a cluster client.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/cluster-setslot/>](https://redis.io/commands/cluster-setslot/)
     #[must_use]
@@ -770,6 +788,163 @@ pub enum ClusterHealthStatus {
     Loading,
 }
 
+/// Nodes targeted by [`Client::broadcast`](crate::client::Client::broadcast)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeSelector {
+    /// The online master of each shard
+    AllMasters,
+    /// All the online replicas
+    AllReplicas,
+    /// All the online masters and replicas
+    All,
+}
+
+/// Result for the [`cluster_nodes`](ClusterCommands::cluster_nodes)
+/// and [`cluster_replicas`](ClusterCommands::cluster_replicas) commands.
+#[derive(Debug)]
+pub struct ClusterNodesResult {
+    pub nodes: Vec<ClusterNode>,
+}
+
+impl<'de> Deserialize<'de> for ClusterNodesResult {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let lines = <&str>::deserialize(deserializer)?;
+        let nodes: crate::Result<Vec<ClusterNode>> = lines
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(ClusterNode::from_line)
+            .collect();
+
+        Ok(Self {
+            nodes: nodes.map_err(de::Error::custom)?,
+        })
+    }
+}
+
+/// A node of the serialized cluster configuration
+/// returned by the [`cluster_nodes`](ClusterCommands::cluster_nodes) command.
+///
+/// See the [serialization format](https://redis.io/commands/cluster-nodes/#serialization-format)
+#[derive(Debug, Clone)]
+pub struct ClusterNode {
+    /// The node ID, a 40 characters random string.
+    pub id: String,
+
+    /// The address where clients should contact the node to run queries (`ip:port`).
+    pub address: String,
+
+    /// The port of the cluster bus of the node.
+    pub cluster_bus_port: Option<u16>,
+
+    /// The announced hostname of the node, if any.
+    pub hostname: Option<String>,
+
+    /// A list of flags: `myself`, `master`, `slave`, `fail?`, `fail`, `handshake`, `noaddr`, `nofailover`, `noflags`.
+    pub flags: Vec<String>,
+
+    /// If the node is a replica, the node ID of its master.
+    pub master_id: Option<String>,
+
+    /// Milliseconds unix time at which the currently active ping was sent, or zero if there are no pending pings.
+    pub ping_sent: u64,
+
+    /// Milliseconds unix time the last pong was received.
+    pub pong_recv: u64,
+
+    /// The configuration epoch (or version) of the current node (or of the current master if the node is a replica).
+    pub config_epoch: u64,
+
+    /// The state of the link used for the node-to-node cluster bus: `connected` or `disconnected`.
+    pub link_state: String,
+
+    /// The hash slot ranges served by the node, bounds included.
+    ///
+    /// Slots being imported or migrated are not listed.
+    pub slots: Vec<(u16, u16)>,
+}
+
+impl ClusterNode {
+    /// Returns `true` if the node is a master
+    pub fn is_master(&self) -> bool {
+        self.flags.iter().any(|f| f == "master")
+    }
+
+    /// Returns `true` if the node is a replica
+    pub fn is_replica(&self) -> bool {
+        self.flags.iter().any(|f| f == "slave")
+    }
+
+    /// Parses a line of the output of [`cluster_nodes`](ClusterCommands::cluster_nodes)
+    pub fn from_line(line: &str) -> crate::Result<ClusterNode> {
+        let invalid_line = || Error::Client(format!("Cannot parse cluster node `{line}`"));
+
+        // <id> <ip:port@cport[,hostname]> <flags> <master> <ping-sent> <pong-recv> <config-epoch> <link-state> <slot> ...
+        let mut fields = line.split_whitespace();
+        let mut next_field = || fields.next().ok_or_else(invalid_line);
+
+        let id = next_field()?.to_owned();
+
+        let address = next_field()?;
+        let (address, hostname) = match address.split_once(',') {
+            Some((address, hostname)) => (address, Some(hostname.to_owned())),
+            None => (address, None),
+        };
+        let (address, cluster_bus_port) = match address.split_once('@') {
+            Some((address, cluster_bus_port)) => {
+                (address.to_owned(), cluster_bus_port.parse::<u16>().ok())
+            }
+            None => (address.to_owned(), None),
+        };
+
+        let flags = next_field()?
+            .split(',')
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>();
+        let master_id = match next_field()? {
+            "-" => None,
+            master_id => Some(master_id.to_owned()),
+        };
+        let ping_sent = next_field()?.parse::<u64>().map_err(|_| invalid_line())?;
+        let pong_recv = next_field()?.parse::<u64>().map_err(|_| invalid_line())?;
+        let config_epoch = next_field()?.parse::<u64>().map_err(|_| invalid_line())?;
+        let link_state = next_field()?.to_owned();
+
+        let mut slots = Vec::new();
+        for slot in fields {
+            // importing or migrating slot: [slot->-node_id] or [slot-<-node_id]
+            if slot.starts_with('[') {
+                continue;
+            }
+
+            let range = match slot.split_once('-') {
+                Some((start, end)) => (start.parse::<u16>(), end.parse::<u16>()),
+                None => (slot.parse::<u16>(), slot.parse::<u16>()),
+            };
+            match range {
+                (Ok(start), Ok(end)) => slots.push((start, end)),
+                _ => return Err(invalid_line()),
+            }
+        }
+
+        Ok(ClusterNode {
+            id,
+            address,
+            cluster_bus_port,
+            hostname,
+            flags,
+            master_id,
+            ping_sent,
+            pong_recv,
+            config_epoch,
+            link_state,
+            slots,
+        })
+    }
+}
+
 /// Result for the [`cluster_slots`](ClusterCommands::cluster_slots) command.
 #[derive(Debug)]
 pub struct LegacyClusterShardResult {
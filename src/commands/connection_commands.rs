@@ -111,6 +111,11 @@ pub trait ConnectionCommands<'a> {
 
     /// Closes a given clients connection based on a filter list
     ///
+    /// Filters are always sent in the `<filter> <value>` form, even for a single address,
+    /// so that the server replies with the number of killed clients
+    /// instead of the legacy `OK`.
+    /// When several filters are combined, only the clients matching all of them are killed.
+    ///
     /// # Return
     /// the number of clients killed.
     ///
@@ -211,11 +216,14 @@ pub trait ConnectionCommands<'a> {
         prepare_command(self, cmd("CLIENT").arg("TRACKING").arg(status).arg(options))
     }
 
-    /// This command enables the tracking feature of the Redis server,
-    /// that is used for [`server assisted client side caching`](https://redis.io/topics/client-side-caching).
+    /// The command returns information about the current client connection's use
+    /// of the [`server assisted client side caching`](https://redis.io/topics/client-side-caching) feature.
+    ///
+    /// # Return
+    /// The tracking flags, the redirection client id and the broadcasting prefixes of the connection.
     ///
     /// # See Also
-    /// [<https://redis.io/commands/client-tracking/>](https://redis.io/commands/client-tracking/)
+    /// [<https://redis.io/commands/client-trackinginfo/>](https://redis.io/commands/client-trackinginfo/)
     #[must_use]
     fn client_trackinginfo(self) -> PreparedCommand<'a, Self, ClientTrackingInfo>
     where
@@ -261,6 +269,10 @@ pub trait ConnectionCommands<'a> {
 
     /// Returns `message`.
     ///
+    /// # Return
+    /// A copy of `message`, e.g. as a `String`,
+    /// or as a [`BulkString`](crate::resp::BulkString) for a binary payload.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/echo/>](https://redis.io/commands/echo/)
     #[must_use]
@@ -289,6 +301,13 @@ pub trait ConnectionCommands<'a> {
 
     /// Returns PONG if no argument is provided, otherwise return a copy of the argument as a bulk.
     ///
+    /// # Return
+    /// * `()` - to only check that the server answers.
+    /// * A copy of the [`message`](PingOptions::message), e.g. as a `String`,
+    ///   or as a [`BulkString`](crate::resp::BulkString) for a binary payload.
+    ///
+    /// On a subscribed connection, use [`PubSubStream::ping`](crate::client::PubSubStream::ping) instead.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/ping/>](https://redis.io/commands/ping/)
     #[must_use]
@@ -327,8 +346,11 @@ pub trait ConnectionCommands<'a> {
 
     /// Select the Redis logical database having the specified zero-based numeric index.
     ///
+    /// When sent by a [`Client`](crate::client::Client), the selected database
+    /// is kept across reconnections.
+    ///
     /// # See Also
-    /// [<https://redis.io/commands/reset/>](https://redis.io/commands/reset/)
+    /// [<https://redis.io/commands/select/>](https://redis.io/commands/select/)
     #[must_use]
     fn select(self, index: usize) -> PreparedCommand<'a, Self, ()>
     where
@@ -631,6 +653,8 @@ pub struct ClientKillOptions {
 }
 
 impl ClientKillOptions {
+    /// Kill the client with the given unique ID
+    /// as returned by the [`client_id`](ConnectionCommands::client_id) command.
     #[must_use]
     pub fn id(mut self, client_id: i64) -> Self {
         Self {
@@ -638,6 +662,7 @@ impl ClientKillOptions {
         }
     }
 
+    /// Kill all the clients of the given type.
     #[must_use]
     pub fn client_type(mut self, client_type: ClientType) -> Self {
         Self {
@@ -645,6 +670,7 @@ impl ClientKillOptions {
         }
     }
 
+    /// Kill all the clients authenticated with the given username.
     #[must_use]
     pub fn user<U: SingleArg>(mut self, username: U) -> Self {
         Self {
@@ -683,6 +709,14 @@ impl ClientKillOptions {
                 .build(),
         }
     }
+
+    /// Kill all the clients connected for more than `max_age` seconds.
+    #[must_use]
+    pub fn max_age(mut self, max_age: u64) -> Self {
+        Self {
+            command_args: self.command_args.arg("MAXAGE").arg(max_age).build(),
+        }
+    }
 }
 
 impl ToArgs for ClientKillOptions {
@@ -805,7 +839,7 @@ impl ToArgs for ClientTrackingOptions {
 }
 
 /// Result for the [`client_trackinginfo`](ConnectionCommands::client_trackinginfo) command.
-#[derive(Deserialize)]
+#[derive(Debug, Deserialize)]
 pub struct ClientTrackingInfo {
     /// A list of tracking flags used by the connection.
     pub flags: Vec<String>,
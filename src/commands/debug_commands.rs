@@ -65,6 +65,41 @@ pub trait DebugCommands<'a> {
         prepare_command(self, cmd("DEBUG").arg("OOM"))
     }
 
+    /// Sets the threshold, in bytes, above which a list element
+    /// is stored in its own plain quicklist node instead of a packed listpack node.
+    #[must_use]
+    fn debug_quicklist_packed_threshold(self, threshold: usize) -> PreparedCommand<'a, Self, ()>
+    where
+        Self: Sized,
+    {
+        prepare_command(
+            self,
+            cmd("DEBUG")
+                .arg("QUICKLIST-PACKED-THRESHOLD")
+                .arg(threshold),
+        )
+    }
+
+    /// Save the RDB file, flush the database, and reload it from the RDB file.
+    #[must_use]
+    fn debug_reload(self) -> PreparedCommand<'a, Self, ()>
+    where
+        Self: Sized,
+    {
+        prepare_command(self, cmd("DEBUG").arg("RELOAD"))
+    }
+
+    /// Change the replication IDs of the server, as if it was promoted after a failover.
+    ///
+    /// Replicas will need a full resynchronization with this server.
+    #[must_use]
+    fn debug_change_repl_id(self) -> PreparedCommand<'a, Self, ()>
+    where
+        Self: Sized,
+    {
+        prepare_command(self, cmd("DEBUG").arg("CHANGE-REPL-ID"))
+    }
+
     /// Crash the server simulating a panic.
     #[must_use]
     fn debug_panic(self) -> PreparedCommand<'a, Self, ()>
@@ -1,11 +1,14 @@
 use crate::{
-    client::{prepare_command, PreparedCommand},
+    client::{prepare_command, Client, PreparedCommand},
+    commands::NodeSelector,
     resp::{
-        cmd, deserialize_byte_buf, CollectionResponse, CommandArgs, PrimitiveResponse, SingleArg,
-        SingleArgCollection, ToArgs,
+        cmd, deserialize_byte_buf, BulkString, CollectionResponse, CommandArgs, PrimitiveResponse,
+        SingleArg, SingleArgCollection, ToArgs,
     },
+    Result,
 };
-use serde::{de::DeserializeOwned, Deserialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// A group of generic Redis commands
 ///
@@ -59,6 +62,66 @@ pub trait GenericCommands<'a> {
         prepare_command(self, cmd("DEL").arg(keys))
     }
 
+    /// Removes all the keys matching `pattern`, without blocking the server.
+    ///
+    /// Keys are iterated with [`scan_from`](GenericCommands::scan_from),
+    /// never with [`keys`](GenericCommands::keys), and are removed with one
+    /// [`unlink`](GenericCommands::unlink) per batch of `batch_size` keys.
+    /// In cluster mode, the keys of every master node are iterated,
+    /// through the existing connection of the client.
+    ///
+    /// The operation is not atomic: keys matching `pattern` which are created
+    /// during the iteration may not be removed.
+    ///
+    /// Only available on a [`Client`].
+    ///
+    /// # Return
+    /// The number of keys that were removed.
+    #[allow(async_fn_in_trait)]
+    async fn del_matching(self, pattern: &str, batch_size: usize) -> Result<usize>
+    where
+        Self: Sized + Into<&'a Client>,
+    {
+        let client: &Client = self.into();
+        let batch_size = batch_size.max(1);
+
+        if !client.is_cluster() {
+            return del_matching_on(client, None, pattern, batch_size).await;
+        }
+
+        let mut deleted = 0;
+
+        for (node_id, master) in client.get_cluster_nodes(NodeSelector::AllMasters).await? {
+            master?;
+            deleted += del_matching_on(client, Some(&node_id), pattern, batch_size).await?;
+        }
+
+        Ok(deleted)
+    }
+
+    /// Removes the specified keys without blocking the server.
+    ///
+    /// This sends an [`UNLINK`](GenericCommands::unlink):
+    /// keys are removed from the keyspace immediately,
+    /// and the memory of large values is reclaimed in a background thread.
+    /// This is the safe default to delete keys of unknown or huge size,
+    /// for which a [`DEL`](GenericCommands::del) would cause a latency spike.
+    ///
+    /// # Return
+    /// The number of keys that were removed.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/unlink/>](https://redis.io/commands/unlink/)
+    #[must_use]
+    fn del_lazy<K, C>(self, keys: C) -> PreparedCommand<'a, Self, usize>
+    where
+        Self: Sized,
+        K: SingleArg,
+        C: SingleArgCollection<K>,
+    {
+        prepare_command(self, cmd("UNLINK").arg(keys))
+    }
+
     /// Serialize the value stored at key in a Redis-specific format and return it to the user.
     ///
     /// # Return
@@ -77,6 +140,10 @@ pub trait GenericCommands<'a> {
 
     /// Returns if keys exist.
     ///
+    /// If the same existing key is mentioned in the arguments multiple times,
+    /// it will be counted multiple times.
+    /// So if `key` exists, `exists(["key", "key"])` returns 2.
+    ///
     /// # Return
     /// The number of keys that exist from those specified as arguments.
     ///
@@ -92,6 +159,23 @@ pub trait GenericCommands<'a> {
         prepare_command(self, cmd("EXISTS").arg(keys))
     }
 
+    /// Returns if a single key exists.
+    ///
+    /// # Return
+    /// * `true` - if the key exists.
+    /// * `false` - if the key does not exist.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/exists/>](https://redis.io/commands/exists/)
+    #[must_use]
+    fn exists_one<K>(self, key: K) -> PreparedCommand<'a, Self, bool>
+    where
+        Self: Sized,
+        K: SingleArg,
+    {
+        prepare_command(self, cmd("EXISTS").arg(key))
+    }
+
     /// Set a timeout on key in seconds
     ///
     /// # Return
@@ -143,6 +227,61 @@ pub trait GenericCommands<'a> {
         )
     }
 
+    /// Set a timeout on key, given as a [`Duration`]
+    ///
+    /// Sends a [`PEXPIRE`](GenericCommands::pexpire) when the duration has a sub-second precision,
+    /// and an [`EXPIRE`](GenericCommands::expire) otherwise.
+    /// The duration is rounded up to the next millisecond, so that a sub-millisecond duration
+    /// does not delete the key right away.
+    ///
+    /// # Return
+    /// * `true` - if the timeout was set.
+    /// * `false` - if the timeout was not set. e.g. key doesn't exist.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/expire/>](https://redis.io/commands/expire/)
+    #[must_use]
+    fn expire_in<K>(self, key: K, duration: Duration) -> PreparedCommand<'a, Self, bool>
+    where
+        Self: Sized,
+        K: SingleArg,
+    {
+        let command = if duration.subsec_nanos() == 0 {
+            cmd("EXPIRE").arg(key).arg(duration.as_secs())
+        } else {
+            cmd("PEXPIRE").arg(key).arg(ceil_millis(duration))
+        };
+        prepare_command(self, command)
+    }
+
+    /// Set the absolute time at which key will expire, given as a [`SystemTime`]
+    ///
+    /// Sends a [`PEXPIREAT`](GenericCommands::pexpireat) when the time has a sub-second precision,
+    /// and an [`EXPIREAT`](GenericCommands::expireat) otherwise.
+    /// The time is rounded up to the next millisecond.
+    /// A time in the past will delete the key.
+    ///
+    /// # Return
+    /// * `true` - if the timeout was set.
+    /// * `false` - if the timeout was not set. e.g. key doesn't exist.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/expireat/>](https://redis.io/commands/expireat/)
+    #[must_use]
+    fn expire_at_instant<K>(self, key: K, time: SystemTime) -> PreparedCommand<'a, Self, bool>
+    where
+        Self: Sized,
+        K: SingleArg,
+    {
+        let unix_time = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let command = if unix_time.subsec_nanos() == 0 {
+            cmd("EXPIREAT").arg(key).arg(unix_time.as_secs())
+        } else {
+            cmd("PEXPIREAT").arg(key).arg(ceil_millis(unix_time))
+        };
+        prepare_command(self, command)
+    }
+
     /// Returns the absolute Unix timestamp (since January 1, 1970) in seconds at which the given key will expire.
     ///
     /// # Return
@@ -218,12 +357,13 @@ pub trait GenericCommands<'a> {
     ///
     /// # Return
     /// * `true` - if key was moved.
-    /// * `false` - f key was not moved.
+    /// * `false` - if key was not moved, because it does not exist in the source database
+    ///   or already exists in the destination database.
     ///
     /// # See Also
     /// [<https://redis.io/commands/move/>](https://redis.io/commands/move/)
     #[must_use]
-    fn move_<K>(self, key: K, db: usize) -> PreparedCommand<'a, Self, i64>
+    fn move_<K>(self, key: K, db: usize) -> PreparedCommand<'a, Self, bool>
     where
         Self: Sized,
         K: SingleArg,
@@ -406,7 +546,8 @@ pub trait GenericCommands<'a> {
     /// Return a random key from the currently selected database.
     ///
     /// # Return
-    /// The number of references.
+    /// The random key, or `None` when the database is empty
+    /// (use an `Option` as the response type to handle it).
     ///
     /// # See Also
     /// [<https://redis.io/commands/randomkey/>](https://redis.io/commands/randomkey/)
@@ -498,6 +639,31 @@ pub trait GenericCommands<'a> {
         prepare_command(self, cmd("SCAN").arg(cursor).arg(options))
     }
 
+    /// Resumes an iteration of the set of keys from a [`ScanCursor`]
+    /// returned by a previous call, possibly by another process.
+    ///
+    /// Start with [`ScanCursor::START`] and stop when the returned cursor
+    /// [`is_complete`](ScanCursor::is_complete).
+    ///
+    /// # Return
+    /// The cursor to resume from and a list of keys
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/scan/>](https://redis.io/commands/scan/)
+    #[must_use]
+    fn scan_from<K, A>(
+        self,
+        cursor: ScanCursor,
+        options: ScanOptions,
+    ) -> PreparedCommand<'a, Self, (ScanCursor, A)>
+    where
+        Self: Sized,
+        K: PrimitiveResponse + DeserializeOwned,
+        A: CollectionResponse<K> + DeserializeOwned,
+    {
+        prepare_command(self, cmd("SCAN").arg(cursor).arg(options))
+    }
+
     /// Returns the elements contained in the list, set or sorted set at key.
     ///
     /// # Return
@@ -601,6 +767,25 @@ pub trait GenericCommands<'a> {
         prepare_command(self, cmd("TTL").arg(key))
     }
 
+    /// Returns the remaining time to live of a key, with a millisecond precision.
+    ///
+    /// Unlike [`ttl`](GenericCommands::ttl) and [`pttl`](GenericCommands::pttl),
+    /// the negative values are decoded in the corresponding [`TtlStatus`].
+    ///
+    /// # Return
+    /// The [`TtlStatus`] of the key
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/pttl/>](https://redis.io/commands/pttl/)
+    #[must_use]
+    fn ttl_status<K>(self, key: K) -> PreparedCommand<'a, Self, TtlStatus>
+    where
+        Self: Sized,
+        K: SingleArg,
+    {
+        prepare_command(self, cmd("PTTL").arg(key))
+    }
+
     /// Returns the string representation of the type of the value stored at key.
     ///
     /// The different types that can be returned are: string, list, set, zset, hash and stream.
@@ -653,6 +838,47 @@ pub trait GenericCommands<'a> {
     }
 }
 
+/// Scans the keys matching `pattern`, on the master node `node_id` in cluster mode,
+/// and unlinks them by batches
+async fn del_matching_on(
+    client: &Client,
+    node_id: Option<&str>,
+    pattern: &str,
+    batch_size: usize,
+) -> Result<usize> {
+    let mut cursor = ScanCursor::START;
+    let mut batch = Vec::<BulkString>::with_capacity(batch_size);
+    let mut deleted = 0;
+
+    loop {
+        let mut scan = client.scan_from(
+            cursor,
+            ScanOptions::default()
+                .match_pattern(pattern)
+                .count(batch_size),
+        );
+        if let Some(node_id) = node_id {
+            scan = scan.route_to_node(node_id);
+        }
+        let (next_cursor, keys): (ScanCursor, Vec<BulkString>) = scan.await?;
+        batch.extend(keys);
+
+        if batch.len() >= batch_size || (next_cursor.is_complete() && !batch.is_empty()) {
+            deleted += client.unlink(std::mem::take(&mut batch)).await?;
+        }
+
+        if next_cursor.is_complete() {
+            return Ok(deleted);
+        }
+        cursor = next_cursor;
+    }
+}
+
+/// Number of milliseconds of a duration, rounded up
+fn ceil_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos().div_ceil(1_000_000)).unwrap_or(u64::MAX)
+}
+
 /// Options for the [`expire`](GenericCommands::expire) command
 #[derive(Default)]
 pub enum ExpireOption {
@@ -858,6 +1084,47 @@ impl ToArgs for SortOptions {
 #[derive(Deserialize)]
 pub struct DumpResult(#[serde(deserialize_with = "deserialize_byte_buf")] pub Vec<u8>);
 
+/// Cursor of a [`scan_from`](GenericCommands::scan_from) iteration
+///
+/// A cursor can be persisted as a `u64`, or serialized with serde,
+/// to checkpoint a long-running scan and resume it later.
+///
+/// Redis cursors stay usable only as long as the keyspace is not rehashed in a way
+/// which invalidates them: a resumed scan may return keys which have already been returned,
+/// and keys added or removed in the meantime may or may not be returned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ScanCursor(pub u64);
+
+impl ScanCursor {
+    /// Cursor starting a new iteration
+    pub const START: ScanCursor = ScanCursor(0);
+
+    /// Returns `true` if the iteration which returned this cursor is complete
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl From<u64> for ScanCursor {
+    fn from(cursor: u64) -> Self {
+        ScanCursor(cursor)
+    }
+}
+
+impl From<ScanCursor> for u64 {
+    fn from(cursor: ScanCursor) -> Self {
+        cursor.0
+    }
+}
+
+impl ToArgs for ScanCursor {
+    fn write_args(&self, args: &mut CommandArgs) {
+        args.arg(self.0);
+    }
+}
+
 /// Options for the [`scan`](GenericCommands::scan) command
 #[derive(Default)]
 pub struct ScanOptions {
@@ -893,6 +1160,29 @@ impl ToArgs for ScanOptions {
     }
 }
 
+/// Result for the [`ttl_status`](GenericCommands::ttl_status) command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(from = "i64")]
+pub enum TtlStatus {
+    /// The key expires after this remaining time to live
+    Expires(Duration),
+    /// The key exists but has no associated expire
+    NoExpiry,
+    /// The key does not exist
+    NoKey,
+}
+
+/// Converts a [`PTTL`](https://redis.io/commands/pttl/) reply, in milliseconds
+impl From<i64> for TtlStatus {
+    fn from(pttl: i64) -> Self {
+        match pttl {
+            -2 => TtlStatus::NoKey,
+            pttl if pttl < 0 => TtlStatus::NoExpiry,
+            pttl => TtlStatus::Expires(Duration::from_millis(pttl as u64)),
+        }
+    }
+}
+
 /// Result for the [`migrate`](GenericCommands::migrate) command
 #[derive(Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
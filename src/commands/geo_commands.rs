@@ -22,6 +22,11 @@ use std::{fmt, marker::PhantomData};
 pub trait GeoCommands<'a> {
     /// Adds the specified geospatial items (longitude, latitude, name) to the specified key.
     ///
+    /// # Arguments
+    /// * `condition` - `NX` or `XX`, which are mutually exclusive like for [`zadd`](crate::commands::SortedSetCommands::zadd)
+    /// * `change` - the `CH` option, to return the number of changed elements instead of added elements
+    /// * `items` - the `(longitude, latitude, member)` items to add
+    ///
     /// # Return
     /// * When used without optional arguments, the number of elements added to the sorted set (excluding score updates).
     /// * If the CH option is specified, the number of elements that were changed (added or updated).
@@ -1,11 +1,15 @@
 use crate::{
     client::{prepare_command, PreparedCommand},
+    commands::{ExpireOption, GetExOptions},
     resp::{
         cmd, deserialize_vec_of_pairs, CollectionResponse, CommandArgs, KeyValueArgsCollection,
         KeyValueCollectionResponse, PrimitiveResponse, SingleArg, SingleArgCollection, ToArgs,
     },
 };
-use serde::{de::DeserializeOwned, Deserialize};
+use serde::{
+    de::{self, DeserializeOwned, Unexpected},
+    Deserialize, Deserializer,
+};
 
 /// A group of Redis commands related to [`Hashes`](https://redis.io/docs/data-types/hashes/)
 ///
@@ -48,6 +52,107 @@ pub trait HashCommands<'a> {
         prepare_command(self, cmd("HEXISTS").arg(key).arg(field))
     }
 
+    /// Set a timeout on one or more fields of the hash stored at key, in seconds.
+    ///
+    /// Once the timeout expires, the field is deleted from the hash.
+    ///
+    /// # Return
+    /// One [`HExpireResult`](HExpireResult) per requested field, in the same order.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/hexpire/>](https://redis.io/commands/hexpire/)
+    #[must_use]
+    fn hexpire<K, F, C>(
+        self,
+        key: K,
+        seconds: u64,
+        option: ExpireOption,
+        fields: C,
+    ) -> PreparedCommand<'a, Self, Vec<HExpireResult>>
+    where
+        Self: Sized,
+        K: SingleArg,
+        F: SingleArg,
+        C: SingleArgCollection<F>,
+    {
+        prepare_command(
+            self,
+            cmd("HEXPIRE")
+                .arg(key)
+                .arg(seconds)
+                .arg(option)
+                .arg("FIELDS")
+                .arg(fields.num_args())
+                .arg(fields),
+        )
+    }
+
+    /// HEXPIREAT has the same effect and semantic as HEXPIRE,
+    /// but instead of specifying the number of seconds representing the TTL (time to live),
+    /// it takes an absolute Unix timestamp (seconds since January 1, 1970)
+    ///
+    /// A timestamp in the past will delete the fields
+    ///
+    /// # Return
+    /// One [`HExpireResult`](HExpireResult) per requested field, in the same order.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/hexpireat/>](https://redis.io/commands/hexpireat/)
+    #[must_use]
+    fn hexpireat<K, F, C>(
+        self,
+        key: K,
+        unix_time_seconds: u64,
+        option: ExpireOption,
+        fields: C,
+    ) -> PreparedCommand<'a, Self, Vec<HExpireResult>>
+    where
+        Self: Sized,
+        K: SingleArg,
+        F: SingleArg,
+        C: SingleArgCollection<F>,
+    {
+        prepare_command(
+            self,
+            cmd("HEXPIREAT")
+                .arg(key)
+                .arg(unix_time_seconds)
+                .arg(option)
+                .arg("FIELDS")
+                .arg(fields.num_args())
+                .arg(fields),
+        )
+    }
+
+    /// Returns the absolute Unix timestamp (since January 1, 1970) in seconds
+    /// at which the given fields of the hash stored at key will expire.
+    ///
+    /// # Return
+    /// For each requested field, in the same order:
+    /// * The expiration Unix timestamp in seconds.
+    /// * `-1` - if the field exists but has no associated expiration time.
+    /// * `-2` - if the field or the key does not exist.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/hexpiretime/>](https://redis.io/commands/hexpiretime/)
+    #[must_use]
+    fn hexpiretime<K, F, C>(self, key: K, fields: C) -> PreparedCommand<'a, Self, Vec<i64>>
+    where
+        Self: Sized,
+        K: SingleArg,
+        F: SingleArg,
+        C: SingleArgCollection<F>,
+    {
+        prepare_command(
+            self,
+            cmd("HEXPIRETIME")
+                .arg(key)
+                .arg("FIELDS")
+                .arg(fields.num_args())
+                .arg(fields),
+        )
+    }
+
     /// Returns the value associated with field in the hash stored at key.
     ///
     /// # Return
@@ -85,6 +190,72 @@ pub trait HashCommands<'a> {
         prepare_command(self, cmd("HGETALL").arg(key))
     }
 
+    /// Returns the values associated with the specified fields in the hash stored at key
+    /// and deletes these fields from the hash.
+    ///
+    /// When the last field is deleted, the key is deleted as well.
+    ///
+    /// # Return
+    /// The list of values associated with the given fields, in the same order as they are requested.
+    /// A nil value is returned for each field that does not exist.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/hgetdel/>](https://redis.io/commands/hgetdel/)
+    #[must_use]
+    fn hgetdel<K, F, V, C, A>(self, key: K, fields: C) -> PreparedCommand<'a, Self, A>
+    where
+        Self: Sized,
+        K: SingleArg,
+        F: SingleArg,
+        C: SingleArgCollection<F>,
+        V: PrimitiveResponse + DeserializeOwned,
+        A: CollectionResponse<V> + DeserializeOwned,
+    {
+        prepare_command(
+            self,
+            cmd("HGETDEL")
+                .arg(key)
+                .arg("FIELDS")
+                .arg(fields.num_args())
+                .arg(fields),
+        )
+    }
+
+    /// Returns the values associated with the specified fields in the hash stored at key
+    /// and optionally sets or removes their expiration.
+    ///
+    /// # Return
+    /// The list of values associated with the given fields, in the same order as they are requested.
+    /// A nil value is returned for each field that does not exist.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/hgetex/>](https://redis.io/commands/hgetex/)
+    #[must_use]
+    fn hgetex<K, F, V, C, A>(
+        self,
+        key: K,
+        options: GetExOptions,
+        fields: C,
+    ) -> PreparedCommand<'a, Self, A>
+    where
+        Self: Sized,
+        K: SingleArg,
+        F: SingleArg,
+        C: SingleArgCollection<F>,
+        V: PrimitiveResponse + DeserializeOwned,
+        A: CollectionResponse<V> + DeserializeOwned,
+    {
+        prepare_command(
+            self,
+            cmd("HGETEX")
+                .arg(key)
+                .arg(options)
+                .arg("FIELDS")
+                .arg(fields.num_args())
+                .arg(fields),
+        )
+    }
+
     /// Increments the number stored at field in the hash stored at key by increment.
     ///
     /// # Return
@@ -108,6 +279,9 @@ pub trait HashCommands<'a> {
     /// # Return
     /// The value at field after the increment operation.
     ///
+    /// # Errors
+    /// A Redis error is returned if the field contains a value that cannot be parsed as a floating point number.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/hincrbyfloat/>](https://redis.io/commands/hincrbyfloat/)
     #[must_use]
@@ -159,6 +333,9 @@ pub trait HashCommands<'a> {
     /// # Return
     /// The list of values associated with the given fields, in the same order as they are requested.
     ///
+    /// Use `Option<V>` as the value type (e.g. `Vec<Option<String>>`)
+    /// to tell which fields do not exist in the hash.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/hmget/>](https://redis.io/commands/hmget/)
     #[must_use]
@@ -174,6 +351,160 @@ pub trait HashCommands<'a> {
         prepare_command(self, cmd("HMGET").arg(key).arg(fields))
     }
 
+    /// Remove the existing timeout on the given fields of the hash stored at key.
+    ///
+    /// # Return
+    /// For each requested field, in the same order:
+    /// * `1` - if the expiration time was removed.
+    /// * `-1` - if the field exists but has no associated expiration time.
+    /// * `-2` - if the field or the key does not exist.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/hpersist/>](https://redis.io/commands/hpersist/)
+    #[must_use]
+    fn hpersist<K, F, C>(self, key: K, fields: C) -> PreparedCommand<'a, Self, Vec<i64>>
+    where
+        Self: Sized,
+        K: SingleArg,
+        F: SingleArg,
+        C: SingleArgCollection<F>,
+    {
+        prepare_command(
+            self,
+            cmd("HPERSIST")
+                .arg(key)
+                .arg("FIELDS")
+                .arg(fields.num_args())
+                .arg(fields),
+        )
+    }
+
+    /// This command works exactly like [`hexpire`](HashCommands::hexpire)
+    /// but the time to live of the fields is specified in milliseconds instead of seconds.
+    ///
+    /// # Return
+    /// One [`HExpireResult`](HExpireResult) per requested field, in the same order.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/hpexpire/>](https://redis.io/commands/hpexpire/)
+    #[must_use]
+    fn hpexpire<K, F, C>(
+        self,
+        key: K,
+        milliseconds: u64,
+        option: ExpireOption,
+        fields: C,
+    ) -> PreparedCommand<'a, Self, Vec<HExpireResult>>
+    where
+        Self: Sized,
+        K: SingleArg,
+        F: SingleArg,
+        C: SingleArgCollection<F>,
+    {
+        prepare_command(
+            self,
+            cmd("HPEXPIRE")
+                .arg(key)
+                .arg(milliseconds)
+                .arg(option)
+                .arg("FIELDS")
+                .arg(fields.num_args())
+                .arg(fields),
+        )
+    }
+
+    /// HPEXPIREAT has the same effect and semantic as [`hexpireat`](HashCommands::hexpireat),
+    /// but the Unix time at which the fields will expire is specified in milliseconds instead of seconds.
+    ///
+    /// # Return
+    /// One [`HExpireResult`](HExpireResult) per requested field, in the same order.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/hpexpireat/>](https://redis.io/commands/hpexpireat/)
+    #[must_use]
+    fn hpexpireat<K, F, C>(
+        self,
+        key: K,
+        unix_time_milliseconds: u64,
+        option: ExpireOption,
+        fields: C,
+    ) -> PreparedCommand<'a, Self, Vec<HExpireResult>>
+    where
+        Self: Sized,
+        K: SingleArg,
+        F: SingleArg,
+        C: SingleArgCollection<F>,
+    {
+        prepare_command(
+            self,
+            cmd("HPEXPIREAT")
+                .arg(key)
+                .arg(unix_time_milliseconds)
+                .arg(option)
+                .arg("FIELDS")
+                .arg(fields.num_args())
+                .arg(fields),
+        )
+    }
+
+    /// HPEXPIRETIME has the same semantic as [`hexpiretime`](HashCommands::hexpiretime),
+    /// but returns the absolute Unix expiration timestamp in milliseconds instead of seconds.
+    ///
+    /// # Return
+    /// For each requested field, in the same order:
+    /// * The expiration Unix timestamp in milliseconds.
+    /// * `-1` - if the field exists but has no associated expiration time.
+    /// * `-2` - if the field or the key does not exist.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/hpexpiretime/>](https://redis.io/commands/hpexpiretime/)
+    #[must_use]
+    fn hpexpiretime<K, F, C>(self, key: K, fields: C) -> PreparedCommand<'a, Self, Vec<i64>>
+    where
+        Self: Sized,
+        K: SingleArg,
+        F: SingleArg,
+        C: SingleArgCollection<F>,
+    {
+        prepare_command(
+            self,
+            cmd("HPEXPIRETIME")
+                .arg(key)
+                .arg("FIELDS")
+                .arg(fields.num_args())
+                .arg(fields),
+        )
+    }
+
+    /// Like [`httl`](HashCommands::httl) this command returns the remaining time to live
+    /// of the given fields, but in milliseconds instead of seconds.
+    ///
+    /// # Return
+    /// For each requested field, in the same order:
+    /// * The TTL in milliseconds.
+    /// * `-1` - if the field exists but has no associated expiration time.
+    /// * `-2` - if the field or the key does not exist.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/hpttl/>](https://redis.io/commands/hpttl/)
+    #[must_use]
+    fn hpttl<K, F, C>(self, key: K, fields: C) -> PreparedCommand<'a, Self, Vec<i64>>
+    where
+        Self: Sized,
+        K: SingleArg,
+        F: SingleArg,
+        C: SingleArgCollection<F>,
+    {
+        prepare_command(
+            self,
+            cmd("HPTTL")
+                .arg(key)
+                .arg("FIELDS")
+                .arg(fields.num_args())
+                .arg(fields),
+        )
+    }
+
     /// return random fields from the hash value stored at key.
     ///
     /// # Return
@@ -248,6 +579,8 @@ pub trait HashCommands<'a> {
     /// array of elements contain two elements, a field and a value,
     /// for every returned element of the Hash.
     ///
+    /// To iterate fields only (`NOVALUES` option), use [`hscan_novalues`](HashCommands::hscan_novalues).
+    ///
     /// # See Also
     /// [<https://redis.io/commands/hlen/>](https://redis.io/commands/hscan/)
     #[must_use]
@@ -266,6 +599,35 @@ pub trait HashCommands<'a> {
         prepare_command(self, cmd("HSCAN").arg(key).arg(cursor).arg(options))
     }
 
+    /// Iterates fields of Hash types, without their associated values.
+    ///
+    /// # Return
+    /// the cursor and the list of fields of the Hash.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/hscan/>](https://redis.io/commands/hscan/)
+    #[must_use]
+    fn hscan_novalues<K, F>(
+        self,
+        key: K,
+        cursor: u64,
+        options: HScanOptions,
+    ) -> PreparedCommand<'a, Self, (u64, Vec<F>)>
+    where
+        Self: Sized,
+        K: SingleArg,
+        F: PrimitiveResponse + DeserializeOwned,
+    {
+        prepare_command(
+            self,
+            cmd("HSCAN")
+                .arg(key)
+                .arg(cursor)
+                .arg(options)
+                .arg("NOVALUES"),
+        )
+    }
+
     /// Sets field in the hash stored at key to value.
     ///
     /// # Return
@@ -322,6 +684,34 @@ pub trait HashCommands<'a> {
         prepare_command(self, cmd("HSTRLEN").arg(key).arg(field))
     }
 
+    /// Returns the remaining time to live of the given fields of the hash stored at key, in seconds.
+    ///
+    /// # Return
+    /// For each requested field, in the same order:
+    /// * The TTL in seconds.
+    /// * `-1` - if the field exists but has no associated expiration time.
+    /// * `-2` - if the field or the key does not exist.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/httl/>](https://redis.io/commands/httl/)
+    #[must_use]
+    fn httl<K, F, C>(self, key: K, fields: C) -> PreparedCommand<'a, Self, Vec<i64>>
+    where
+        Self: Sized,
+        K: SingleArg,
+        F: SingleArg,
+        C: SingleArgCollection<F>,
+    {
+        prepare_command(
+            self,
+            cmd("HTTL")
+                .arg(key)
+                .arg("FIELDS")
+                .arg(fields.num_args())
+                .arg(fields),
+        )
+    }
+
     /// list of values in the hash, or an empty list when key does not exist.
     ///
     /// # Return
@@ -341,6 +731,39 @@ pub trait HashCommands<'a> {
     }
 }
 
+/// Per-field result of the [`hexpire`](HashCommands::hexpire) family of commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HExpireResult {
+    /// The field does not exist, or the key does not exist
+    NoSuchField,
+    /// The specified condition (`NX`, `XX`, `GT` or `LT`) was not met
+    ConditionNotMet,
+    /// The expiration time was set or updated
+    Set,
+    /// The field was deleted because the expiration time is in the past
+    /// or the time to live is `0`
+    Deleted,
+}
+
+impl<'de> Deserialize<'de> for HExpireResult {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let result = i64::deserialize(deserializer)?;
+        match result {
+            -2 => Ok(HExpireResult::NoSuchField),
+            0 => Ok(HExpireResult::ConditionNotMet),
+            1 => Ok(HExpireResult::Set),
+            2 => Ok(HExpireResult::Deleted),
+            _ => Err(de::Error::invalid_value(
+                Unexpected::Signed(result),
+                &"-2, 0, 1 or 2",
+            )),
+        }
+    }
+}
+
 /// Options for the [`hscan`](HashCommands::hscan) command
 #[derive(Default)]
 pub struct HScanOptions {
@@ -11,11 +11,12 @@ pub trait HyperLogLogCommands<'a> {
     /// Adds the specified elements to the specified HyperLogLog.
     ///
     /// # Return
-    /// * `true` if at least 1 HyperLogLog inFternal register was altered.
+    /// * `true` if at least 1 HyperLogLog internal register was altered.
     /// * `false` otherwise.
     ///
     /// # See Also
     /// [<https://redis.io/commands/pfadd/>](https://redis.io/commands/pfadd/)
+    #[must_use]
     fn pfadd<K, E, EE>(self, key: K, elements: EE) -> PreparedCommand<'a, Self, bool>
     where
         Self: Sized,
@@ -29,11 +30,16 @@ pub trait HyperLogLogCommands<'a> {
     /// Return the approximated cardinality of the set(s)
     /// observed by the HyperLogLog at key(s).
     ///
+    /// When called with multiple keys, the HyperLogLogs are merged on the fly
+    /// into a temporary one, without altering the source keys.
+    ///
     /// # Return
-    /// The approximated number of unique elements observed via PFADD.
+    /// The approximated number of unique elements observed via PFADD,
+    /// or of the union of the elements observed by all the keys.
     ///
     /// # See Also
     /// [<https://redis.io/commands/pfcount/>](https://redis.io/commands/pfcount/)
+    #[must_use]
     fn pfcount<K, KK>(self, keys: KK) -> PreparedCommand<'a, Self, usize>
     where
         Self: Sized,
@@ -43,10 +49,34 @@ pub trait HyperLogLogCommands<'a> {
         prepare_command(self, cmd("PFCOUNT").arg(keys))
     }
 
+    /// Returns the internal registers of the HyperLogLog stored at key.
+    ///
+    /// This is an internal command, meant to test the HyperLogLog implementation.
+    /// As a side effect, the HyperLogLog is converted to its dense representation.
+    ///
+    /// # Return
+    /// The value of each of the 16384 registers.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/pfdebug/>](https://redis.io/commands/pfdebug/)
+    #[cfg_attr(docsrs, doc(cfg(feature = "debug-commands")))]
+    #[cfg(feature = "debug-commands")]
+    #[must_use]
+    fn pfdebug_getreg<K>(self, key: K) -> PreparedCommand<'a, Self, Vec<u8>>
+    where
+        Self: Sized,
+        K: SingleArg,
+    {
+        prepare_command(self, cmd("PFDEBUG").arg("GETREG").arg(key))
+    }
+
     /// Merge N different HyperLogLogs into a single one.
     ///
+    /// The destination key, if it already exists, is part of the merged sets.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/pfmerge/>](https://redis.io/commands/pfmerge/)
+    #[must_use]
     fn pfmerge<D, S, SS>(self, dest_key: D, source_keys: SS) -> PreparedCommand<'a, Self, ()>
     where
         Self: Sized,
@@ -56,4 +86,20 @@ pub trait HyperLogLogCommands<'a> {
     {
         prepare_command(self, cmd("PFMERGE").arg(dest_key).arg(source_keys))
     }
+
+    /// Runs the internal consistency checks of the HyperLogLog implementation.
+    ///
+    /// This is an internal command, meant to test Redis.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/pfselftest/>](https://redis.io/commands/pfselftest/)
+    #[cfg_attr(docsrs, doc(cfg(feature = "debug-commands")))]
+    #[cfg(feature = "debug-commands")]
+    #[must_use]
+    fn pfselftest(self) -> PreparedCommand<'a, Self, ()>
+    where
+        Self: Sized,
+    {
+        prepare_command(self, cmd("PFSELFTEST"))
+    }
 }
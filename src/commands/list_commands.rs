@@ -4,6 +4,7 @@ use crate::{
         cmd, CollectionResponse, CommandArgs, PrimitiveResponse, SingleArg, SingleArgCollection,
         ToArgs,
     },
+    Error,
 };
 use serde::de::DeserializeOwned;
 
@@ -106,8 +107,11 @@ pub trait ListCommands<'a> {
 
     /// Pops one or more elements from the first non-empty list key from the list of provided key names.
     ///
+    /// If `count` is greater than the length of the list, all its elements are popped.
+    ///
     /// # Return
-    /// Tuple composed by the name of the key from which elements were popped and the list of popped element
+    /// - `None` when all the lists are empty or missing
+    /// - Tuple composed by the name of the key from which elements were popped and the list of popped element
     ///
     /// # See Also
     /// [<https://redis.io/commands/lmpop/>](https://redis.io/commands/lmpop/)
@@ -117,7 +121,7 @@ pub trait ListCommands<'a> {
         keys: C,
         where_: LMoveWhere,
         count: usize,
-    ) -> PreparedCommand<'a, Self, (String, Vec<E>)>
+    ) -> PreparedCommand<'a, Self, Option<(String, Vec<E>)>>
     where
         Self: Sized,
         K: SingleArg,
@@ -155,9 +159,18 @@ pub trait ListCommands<'a> {
 
     /// Returns the index of matching elements inside a Redis list.
     ///
+    /// # Arguments
+    /// * `rank` - skip the first `rank - 1` matches; a negative rank searches from the tail
+    ///   of the list (`-1` is the last match). `0` is not a valid rank.
+    /// * `max_len` - only compare the first `max_len` elements (`0` compares all the elements)
+    ///
     /// # Return
     /// The integer representing the matching element, or nil if there is no match.
     ///
+    /// # Errors
+    /// An [`Error::Client`](crate::Error::Client) is returned without sending the command
+    /// if `rank` is `0`.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/lpos/>](https://redis.io/commands/lpos/)
     #[must_use]
@@ -165,7 +178,7 @@ pub trait ListCommands<'a> {
         self,
         key: K,
         element: E,
-        rank: Option<usize>,
+        rank: Option<isize>,
         max_len: Option<usize>,
     ) -> PreparedCommand<'a, Self, Option<usize>>
     where
@@ -173,22 +186,38 @@ pub trait ListCommands<'a> {
         K: SingleArg,
         E: SingleArg,
     {
-        prepare_command(
+        let prepared_command = prepare_command(
             self,
             cmd("LPOS")
                 .arg(key)
                 .arg(element)
                 .arg(rank.map(|r| ("RANK", r)))
                 .arg(max_len.map(|l| ("MAXLEN", l))),
-        )
+        );
+
+        if rank == Some(0) {
+            prepared_command.error(Error::Client("LPOS rank must not be 0".to_owned()))
+        } else {
+            prepared_command
+        }
     }
 
     /// Returns the index of matching elements inside a Redis list.
     ///
+    /// # Arguments
+    /// * `num_matches` - maximum number of matches to return (`0` returns all the matches)
+    /// * `rank` - skip the first `rank - 1` matches; a negative rank searches from the tail
+    ///   of the list (`-1` is the last match). `0` is not a valid rank.
+    /// * `max_len` - only compare the first `max_len` elements (`0` compares all the elements)
+    ///
     /// # Return
     /// An array of integers representing the matching elements.
     /// (empty if there are no matches).
     ///
+    /// # Errors
+    /// An [`Error::Client`](crate::Error::Client) is returned without sending the command
+    /// if `rank` is `0`.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/lpos/>](https://redis.io/commands/lpos/)
     #[must_use]
@@ -197,7 +226,7 @@ pub trait ListCommands<'a> {
         key: K,
         element: E,
         num_matches: usize,
-        rank: Option<usize>,
+        rank: Option<isize>,
         max_len: Option<usize>,
     ) -> PreparedCommand<'a, Self, A>
     where
@@ -206,7 +235,7 @@ pub trait ListCommands<'a> {
         E: SingleArg,
         A: CollectionResponse<usize>,
     {
-        prepare_command(
+        let prepared_command = prepare_command(
             self,
             cmd("LPOS")
                 .arg(key)
@@ -215,7 +244,13 @@ pub trait ListCommands<'a> {
                 .arg("COUNT")
                 .arg(num_matches)
                 .arg(max_len.map(|l| ("MAXLEN", l))),
-        )
+        );
+
+        if rank == Some(0) {
+            prepared_command.error(Error::Client("LPOS rank must not be 0".to_owned()))
+        } else {
+            prepared_command
+        }
     }
 
     /// Insert all the specified values at the head of the list stored at key
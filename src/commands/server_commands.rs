@@ -2,7 +2,7 @@ use crate::{
     client::{prepare_command, PreparedCommand},
     resp::{
         cmd, CollectionResponse, CommandArgs, KeyValueArgsCollection, KeyValueCollectionResponse,
-        PrimitiveResponse, SingleArg, SingleArgCollection, ToArgs, Value,
+        PrimitiveResponse, SingleArg, SingleArgCollection, Timestamp, ToArgs, Value,
     },
     Error, Result,
 };
@@ -110,6 +110,7 @@ pub trait ServerCommands<'a> {
     ///
     /// # Return
     /// A collection of ACL rule definitions for the user.
+    /// Use [`AclUser`](AclUser) to get a typed result.
     ///
     /// # See Also
     /// [<https://redis.io/commands/acl-getuser/>](https://redis.io/commands/acl-getuser/)
@@ -242,6 +243,45 @@ pub trait ServerCommands<'a> {
         prepare_command(self, cmd("ACL").arg("WHOAMI"))
     }
 
+    /// Instruct Redis to start an [`Append Only File`](https://redis.io/docs/management/persistence/#append-only-file)
+    /// rewrite process.
+    ///
+    /// # Return
+    /// A status message, e.g. `Background append only file rewriting started`
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/bgrewriteaof/>](https://redis.io/commands/bgrewriteaof/)
+    #[must_use]
+    fn bgrewriteaof(self) -> PreparedCommand<'a, Self, String>
+    where
+        Self: Sized,
+    {
+        prepare_command(self, cmd("BGREWRITEAOF"))
+    }
+
+    /// Save the DB in background.
+    ///
+    /// # Arguments
+    /// * `schedule` - if `true`, when an AOF rewrite is in progress,
+    ///   the save is scheduled to run when the rewrite is done, instead of returning an error.
+    ///
+    /// # Return
+    /// A status message, e.g. `Background saving started` or `Background saving scheduled`
+    ///
+    /// # Errors
+    /// An error is returned if a background save is already in progress,
+    /// or if an AOF rewrite is in progress and `schedule` is `false`.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/bgsave/>](https://redis.io/commands/bgsave/)
+    #[must_use]
+    fn bgsave(self, schedule: bool) -> PreparedCommand<'a, Self, String>
+    where
+        Self: Sized,
+    {
+        prepare_command(self, cmd("BGSAVE").arg_if(schedule, "SCHEDULE"))
+    }
+
     /// Return an array with details about every Redis command.
     ///
     /// # Return
@@ -444,6 +484,9 @@ pub trait ServerCommands<'a> {
 
     /// Delete all the keys of the currently selected DB.
     ///
+    /// In cluster mode, the command is executed on all the master nodes,
+    /// and an error is returned if it fails on any of them.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/flushdb/>](https://redis.io/commands/flushdb/)
     #[must_use]
@@ -456,6 +499,9 @@ pub trait ServerCommands<'a> {
 
     /// Delete all the keys of all the existing databases, not just the currently selected one.
     ///
+    /// In cluster mode, the command is executed on all the master nodes,
+    /// and an error is returned if it fails on any of them.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/flushall/>](https://redis.io/commands/flushall/)
     #[must_use]
@@ -480,8 +526,27 @@ pub trait ServerCommands<'a> {
         prepare_command(self, cmd("INFO").arg(sections))
     }
 
+    /// Returns the replication section of [`info`](ServerCommands::info), parsed.
+    ///
+    /// # Return
+    /// The role of the server, its connected replicas for a master,
+    /// or the state of the link to its master for a replica.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/info/>](https://redis.io/commands/info/)
+    #[must_use]
+    fn info_replication(self) -> PreparedCommand<'a, Self, ReplicationInfo>
+    where
+        Self: Sized,
+    {
+        prepare_command(self, cmd("INFO").arg(InfoSection::Replication))
+    }
+
     /// Return the UNIX TIME of the last DB save executed with success.
     ///
+    /// This can be polled after [`bgsave`](ServerCommands::bgsave)
+    /// to check if the background save has completed.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/lastsave/>](https://redis.io/commands/lastsave/)
     #[must_use]
@@ -776,6 +841,11 @@ pub trait ServerCommands<'a> {
 
     /// Shutdown the server
     ///
+    /// A successful shutdown closes the connection without any reply:
+    /// in this case, the command completes successfully instead of failing with a disconnection error,
+    /// and it is never retried.
+    /// With the `ABORT` option, the reply of the server is returned as usual.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/shutdown/>](https://redis.io/commands/shutdown/)
     #[must_use]
@@ -839,10 +909,18 @@ pub trait ServerCommands<'a> {
     /// The TIME command returns the current server time as a two items lists:
     /// a Unix timestamp and the amount of microseconds already elapsed in the current second.
     ///
+    /// # Return
+    /// The server time, convertible into a [`SystemTime`](std::time::SystemTime)
+    ///
+    /// # Migration
+    /// This command used to return a `(u32, u32)` pair.
+    /// The same values are given by [`unix_timestamp`](Timestamp::unix_timestamp)
+    /// and [`subsec_micros`](Timestamp::subsec_micros).
+    ///
     /// # See Also
     /// [<https://redis.io/commands/time/>](https://redis.io/commands/time/)
     #[must_use]
-    fn time(self) -> PreparedCommand<'a, Self, (u32, u32)>
+    fn time(self) -> PreparedCommand<'a, Self, Timestamp>
     where
         Self: Sized,
     {
@@ -979,6 +1057,37 @@ impl ToArgs for AclLogOptions {
     }
 }
 
+/// Typed result for the [`acl_getuser`](ServerCommands::acl_getuser) command.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AclUser {
+    /// User flags (`on`/`off`, `nopass`, `skip-sanitize-payload`...)
+    pub flags: Vec<String>,
+    /// SHA-256 hashes of the user passwords
+    pub passwords: Vec<String>,
+    /// Command rules of the root permissions (e.g. `-@all +get`)
+    pub commands: String,
+    /// Key patterns of the root permissions (e.g. `~* %R~read*`)
+    pub keys: String,
+    /// Pub/Sub channel patterns of the root permissions (e.g. `&*`)
+    pub channels: String,
+    /// Additional permissions
+    #[serde(default)]
+    pub selectors: Vec<AclSelector>,
+}
+
+impl KeyValueCollectionResponse<String, Value> for AclUser {}
+
+/// Selector part of the [`AclUser`](AclUser) result.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AclSelector {
+    /// Command rules of the selector
+    pub commands: String,
+    /// Key patterns of the selector
+    pub keys: String,
+    /// Pub/Sub channel patterns of the selector
+    pub channels: String,
+}
+
 /// Command info result for the [`command`](ServerCommands::command) command.
 #[derive(Debug, Clone, Deserialize)]
 pub struct CommandInfo {
@@ -2012,6 +2121,135 @@ pub enum ReplicationState {
     Unknown,
 }
 
+/// Result for the [`info_replication`](ServerCommands::info_replication) command.
+#[derive(Debug)]
+pub struct ReplicationInfo {
+    /// `master` or `slave`
+    pub role: String,
+
+    /// number of connected replicas
+    pub connected_slaves: usize,
+
+    /// the connected replicas, for a master
+    pub slaves: Vec<ConnectedReplicaInfo>,
+
+    /// host of the master, for a replica
+    pub master_host: Option<String>,
+
+    /// port of the master, for a replica
+    pub master_port: Option<u16>,
+
+    /// status of the link to the master (`up` or `down`), for a replica
+    pub master_link_status: Option<String>,
+
+    /// the replication ID of the server
+    pub master_replid: String,
+
+    /// the server's current replication offset
+    pub master_repl_offset: u64,
+
+    /// the other fields of the replication section, which depend on the role & Redis version
+    pub additional_arguments: HashMap<String, String>,
+}
+
+impl ReplicationInfo {
+    fn from_info(info: &str) -> Result<ReplicationInfo> {
+        // Each line is a property:value field, except for the section header & empty lines.
+        let mut values: HashMap<String, String> = info
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect();
+
+        let connected_slaves = values
+            .remove("connected_slaves")
+            .map(|n| n.parse::<usize>().unwrap_or_default())
+            .unwrap_or_default();
+
+        let slaves = (0..connected_slaves)
+            .filter_map(|i| values.remove(&format!("slave{i}")))
+            .map(|line| ConnectedReplicaInfo::from_line(&line))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(ReplicationInfo {
+            role: values
+                .remove("role")
+                .ok_or_else(|| Error::Client("Cannot parse replication info".to_owned()))?,
+            connected_slaves,
+            slaves,
+            master_host: values.remove("master_host"),
+            master_port: values
+                .remove("master_port")
+                .and_then(|port| port.parse::<u16>().ok()),
+            master_link_status: values.remove("master_link_status"),
+            master_replid: values.remove("master_replid").unwrap_or_default(),
+            master_repl_offset: values
+                .remove("master_repl_offset")
+                .map(|offset| offset.parse::<u64>().unwrap_or_default())
+                .unwrap_or_default(),
+            additional_arguments: values,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ReplicationInfo {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let str = <&str>::deserialize(deserializer)?;
+        ReplicationInfo::from_info(str).map_err(de::Error::custom)
+    }
+}
+
+/// A replica connected to a master,
+/// part of the [`ReplicationInfo`] returned by the [`info_replication`](ServerCommands::info_replication) command.
+#[derive(Debug)]
+pub struct ConnectedReplicaInfo {
+    /// the replica IP
+    pub ip: String,
+
+    /// the replica port
+    pub port: u16,
+
+    /// the replication state of the replica (`wait_bgsave`, `send_bulk`, `online`...)
+    pub state: String,
+
+    /// the replication offset acknowledged by the replica
+    pub offset: u64,
+
+    /// seconds since the last acknowledgement of the replica
+    pub lag: u64,
+}
+
+impl ConnectedReplicaInfo {
+    fn from_line(line: &str) -> Result<ConnectedReplicaInfo> {
+        // ip=<ip>,port=<port>,state=<state>,offset=<offset>,lag=<lag>
+        let mut values: HashMap<&str, &str> = line
+            .split(',')
+            .filter_map(|kvp| kvp.split_once('='))
+            .collect();
+
+        Ok(ConnectedReplicaInfo {
+            ip: values.remove("ip").unwrap_or_default().to_owned(),
+            port: values
+                .remove("port")
+                .and_then(|port| port.parse::<u16>().ok())
+                .ok_or_else(|| Error::Client(format!("Cannot parse replica info `{line}`")))?,
+            state: values.remove("state").unwrap_or_default().to_owned(),
+            offset: values
+                .remove("offset")
+                .map(|offset| offset.parse::<u64>().unwrap_or_default())
+                .unwrap_or_default(),
+            lag: values
+                .remove("lag")
+                .map(|lag| lag.parse::<u64>().unwrap_or_default())
+                .unwrap_or_default(),
+        })
+    }
+}
+
 /// options for the [`shutdown`](ServerCommands::shutdown) command.
 #[derive(Default)]
 pub struct ShutdownOptions {
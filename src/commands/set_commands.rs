@@ -6,7 +6,7 @@ use crate::{
     },
 };
 use serde::de::DeserializeOwned;
-use std::hash::Hash;
+use std::{collections::HashSet, hash::Hash};
 
 /// A group of Redis commands related to [`Sets`](https://redis.io/docs/data-types/sets/)
 /// # See Also
@@ -27,6 +27,30 @@ pub trait SetCommands<'a> {
         prepare_command(self, cmd("SADD").arg(key).arg(members))
     }
 
+    /// Add the members yielded by a strongly-typed iterator to the set stored at key.
+    ///
+    /// # Return
+    /// the number of elements that were added to the set,
+    /// not including all the elements already present in the set.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/sadd/>](https://redis.io/commands/sadd/)
+    #[must_use]
+    fn sadd_typed<K, T, I>(self, key: K, members: I) -> PreparedCommand<'a, Self, usize>
+    where
+        Self: Sized,
+        K: SingleArg,
+        T: SingleArg,
+        I: IntoIterator<Item = T>,
+    {
+        prepare_command(
+            self,
+            cmd("SADD")
+                .arg(key)
+                .arg(members.into_iter().collect::<Vec<T>>()),
+        )
+    }
+
     /// Returns the set cardinality (number of elements) of the set stored at key.
     ///
     /// # Return
@@ -108,7 +132,7 @@ pub trait SetCommands<'a> {
     /// the algorithm will exit and yield limit as the cardinality. 0 means unlimited
     ///
     /// # Return
-    /// A list with members of the resulting set.
+    /// The number of elements in the resulting intersection, capped to `limit`.
     ///
     /// # See Also
     /// [<https://redis.io/commands/sintercard/>](https://redis.io/commands/sintercard/)
@@ -181,6 +205,27 @@ pub trait SetCommands<'a> {
         prepare_command(self, cmd("SMEMBERS").arg(key))
     }
 
+    /// Returns all the members of the set value stored at key, as a [`HashSet`].
+    ///
+    /// Unlike [`smembers`](SetCommands::smembers), only the member type has to be specified:
+    /// `client.smembers_typed::<_, i64>("key")`.
+    ///
+    /// # Errors
+    /// A client error is returned if a member cannot be converted to `T`
+    /// (e.g. a non-integer member when `T` is `i64`).
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/smembers/>](https://redis.io/commands/smembers/)
+    #[must_use]
+    fn smembers_typed<K, T>(self, key: K) -> PreparedCommand<'a, Self, HashSet<T>>
+    where
+        Self: Sized,
+        K: SingleArg,
+        T: PrimitiveResponse + Eq + Hash + DeserializeOwned,
+    {
+        prepare_command(self, cmd("SMEMBERS").arg(key))
+    }
+
     /// Returns whether each member is a member of the set stored at key.
     ///
     /// # Return
@@ -218,15 +263,34 @@ pub trait SetCommands<'a> {
         prepare_command(self, cmd("SMOVE").arg(source).arg(destination).arg(member))
     }
 
-    /// Removes and returns one or more random members from the set value store at key.
+    /// Removes and returns a random member from the set value store at key.
     ///
     /// # Return
-    /// the list of popped elements
+    /// the removed member, or `None` when key does not exist.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/spop/>](https://redis.io/commands/spop/)
+    #[must_use]
+    fn spop<K, M>(self, key: K) -> PreparedCommand<'a, Self, Option<M>>
+    where
+        Self: Sized,
+        K: SingleArg,
+        M: PrimitiveResponse + DeserializeOwned,
+    {
+        prepare_command(self, cmd("SPOP").arg(key))
+    }
+
+    /// Removes and returns up to `count` random members from the set value store at key.
+    ///
+    /// # Return
+    /// the list of popped elements, or empty collection when key does not exist.
+    ///
+    /// If `count` is greater than the number of members, the whole set is returned and key is deleted.
     ///
     /// # See Also
     /// [<https://redis.io/commands/spop/>](https://redis.io/commands/spop/)
     #[must_use]
-    fn spop<K, M, A>(self, key: K, count: usize) -> PreparedCommand<'a, Self, A>
+    fn spop_count<K, M, A>(self, key: K, count: usize) -> PreparedCommand<'a, Self, A>
     where
         Self: Sized,
         K: SingleArg,
@@ -262,9 +262,12 @@ pub trait SortedSetCommands<'a> {
     /// This command is similar to [zinter](SortedSetCommands::zinter),
     /// but instead of returning the result set, it returns just the cardinality of the result.
     ///
-    //// limit: if the intersection cardinality reaches limit partway through the computation,
+    /// limit: if the intersection cardinality reaches limit partway through the computation,
     /// the algorithm will exit and yield limit as the cardinality. 0 means unlimited
     ///
+    /// # Return
+    /// The number of elements in the resulting intersection, capped to `limit`.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/zintercard/>](https://redis.io/commands/zintercard/)
     #[must_use]
@@ -341,6 +344,8 @@ pub trait SortedSetCommands<'a> {
     /// Pops one or more elements, that are member-score pairs,
     /// from the first non-empty sorted set in the provided list of key names.
     ///
+    /// If `count` is greater than the cardinality of the sorted set, all its members are popped.
+    ///
     /// # Return
     /// * None if no element could be popped
     /// * A tuple made up of
@@ -846,9 +851,9 @@ pub enum ZAddCondition {
     /// No condition
     #[default]
     None,
-    /// Only update elements that already exist. Don't add new elements.
-    NX,
     /// Only add new elements. Don't update already existing elements.
+    NX,
+    /// Only update elements that already exist. Don't add new elements.
     XX,
 }
 
@@ -949,13 +954,13 @@ impl ToArgs for ZAggregate {
         match self {
             ZAggregate::None => {}
             ZAggregate::Sum => {
-                args.arg("SUM");
+                args.arg("AGGREGATE").arg("SUM");
             }
             ZAggregate::Min => {
-                args.arg("MIN");
+                args.arg("AGGREGATE").arg("MIN");
             }
             ZAggregate::Max => {
-                args.arg("MAX");
+                args.arg("AGGREGATE").arg("MAX");
             }
         }
     }
@@ -4,9 +4,13 @@ use crate::{
         cmd, CommandArgs, KeyValueArgsCollection, KeyValueCollectionResponse, PrimitiveResponse,
         SingleArg, SingleArgCollection, ToArgs,
     },
+    Error, Result,
 };
-use serde::{de::DeserializeOwned, Deserialize};
-use std::collections::HashMap;
+use serde::{
+    de::{self, DeserializeOwned},
+    Deserialize, Deserializer,
+};
+use std::{collections::HashMap, fmt, str::FromStr};
 
 /// A group of Redis commands related to [`Streams`](https://redis.io/docs/data-types/streams/)
 /// # See Also
@@ -340,11 +344,62 @@ pub trait StreamCommands<'a> {
     /// This command returns information about the stream stored at `key`.
     ///
     /// # Return
-    /// A collection of XGroupInfo.
+    /// An instance of XStreamInfo.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/xinfo-stream/>](https://redis.io/commands/xinfo-stream/)
+    fn xinfo_stream<K>(self, key: K) -> PreparedCommand<'a, Self, XStreamInfo>
+    where
+        Self: Sized,
+        K: SingleArg,
+    {
+        prepare_command(self, cmd("XINFO").arg("STREAM").arg(key))
+    }
+
+    /// This command returns the verbose information about the stream stored at `key`,
+    /// including its entries, its consumer groups and their pending entries lists.
+    ///
+    /// # Arguments
+    /// * `key` - The stream key.
+    /// * `count` - Limit the number of stream and PEL entries that are returned.
+    ///   When `None`, the server default (10) is applied. `Some(0)` returns all the entries.
+    ///
+    /// # Return
+    /// An instance of XStreamFullInfo.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/xinfo-stream/>](https://redis.io/commands/xinfo-stream/)
+    #[cfg_attr(docsrs, doc(cfg(feature = "xinfo-full")))]
+    #[cfg(feature = "xinfo-full")]
+    fn xinfo_stream_full<K>(
+        self,
+        key: K,
+        count: Option<usize>,
+    ) -> PreparedCommand<'a, Self, XStreamFullInfo>
+    where
+        Self: Sized,
+        K: SingleArg,
+    {
+        prepare_command(
+            self,
+            cmd("XINFO")
+                .arg("STREAM")
+                .arg(key)
+                .arg("FULL")
+                .arg(count.map(|c| ("COUNT", c))),
+        )
+    }
+
+    /// This command returns information about the stream stored at `key`.
+    ///
+    /// # Return
+    /// An instance of XStreamInfo.
     ///
     /// # See Also
     /// [<https://redis.io/commands/xinfo-stream/>](https://redis.io/commands/xinfo-stream/)
-    fn xinfo_stream<K>(
+    #[deprecated(note = "use `xinfo_stream`, or `xinfo_stream_full` with its `count` argument")]
+    #[allow(deprecated)]
+    fn xinfo_stream_with_options<K>(
         self,
         key: K,
         options: XInfoStreamOptions,
@@ -373,6 +428,11 @@ pub trait StreamCommands<'a> {
 
     /// The XPENDING command is the interface to inspect the list of pending messages.
     ///
+    /// This form of the command returns a summary of the pending messages of the consumer group.
+    ///
+    /// # Return
+    /// An instance of XPendingResult
+    ///
     /// # See Also
     /// [<https://redis.io/commands/xpending/>](https://redis.io/commands/xpending/)
     fn xpending<K, G>(self, key: K, group: G) -> PreparedCommand<'a, Self, XPendingResult>
@@ -386,20 +446,72 @@ pub trait StreamCommands<'a> {
 
     /// The XPENDING command is the interface to inspect the list of pending messages.
     ///
+    /// This extended form of the command returns the details of each pending message
+    /// with an ID between `start` and `end`, up to `count` messages.
+    ///
+    /// # Return
+    /// A collection of XPendingMessageResult
+    ///
     /// # See Also
     /// [<https://redis.io/commands/xpending/>](https://redis.io/commands/xpending/)
-    fn xpending_with_options<K, G>(
+    fn xpending_detailed<K, G, S, E>(
         self,
         key: K,
         group: G,
+        start: S,
+        end: E,
+        count: usize,
         options: XPendingOptions,
     ) -> PreparedCommand<'a, Self, Vec<XPendingMessageResult>>
+    where
+        Self: Sized,
+        K: SingleArg,
+        G: SingleArg,
+        S: SingleArg,
+        E: SingleArg,
+    {
+        prepare_command(
+            self,
+            cmd("XPENDING")
+                .arg(key)
+                .arg(group)
+                .arg(options.idle_args)
+                .arg(start)
+                .arg(end)
+                .arg(count)
+                .arg(options.consumer_args),
+        )
+    }
+
+    /// The XPENDING command is the interface to inspect the list of pending messages.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/xpending/>](https://redis.io/commands/xpending/)
+    #[deprecated(note = "use `xpending_detailed`")]
+    #[allow(deprecated)]
+    fn xpending_with_options<K, G>(
+        self,
+        key: K,
+        group: G,
+        mut options: XPendingOptions,
+    ) -> PreparedCommand<'a, Self, Vec<XPendingMessageResult>>
     where
         Self: Sized,
         K: SingleArg,
         G: SingleArg,
     {
-        prepare_command(self, cmd("XPENDING").arg(key).arg(group).arg(options))
+        match (
+            options.start.take(),
+            options.end.take(),
+            options.count.take(),
+        ) {
+            (Some(start), Some(end), Some(count)) => {
+                self.xpending_detailed(key, group, start, end, count, options)
+            }
+            _ => prepare_command(self, cmd("XPENDING").arg(key).arg(group)).error(Error::Client(
+                "XPENDING options require a start, an end and a count".to_owned(),
+            )),
+        }
     }
 
     /// The command returns the stream entries matching a given range of IDs.
@@ -411,6 +523,9 @@ pub trait StreamCommands<'a> {
     /// The returned entries are complete, that means that the ID and all the fields they are composed are returned.
     /// Moreover, the entries are returned with their fields and values in the exact same order as XADD added them.
     ///
+    /// `start` and `end` can be ids, `-` and `+` for the smallest and greatest ids,
+    /// or a [`StreamRangeBound`](StreamRangeBound) for exclusive bounds.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/xrange/>](https://redis.io/commands/xrange/)
     fn xrange<K, S, E, V>(
@@ -539,6 +654,28 @@ pub trait StreamCommands<'a> {
         )
     }
 
+    /// Sets the last delivered ID of a stream, that is the ID
+    /// after which [`xadd`](StreamCommands::xadd) generates new IDs.
+    ///
+    /// `last_id` must not be smaller than the ID of the last entry of the stream.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/xsetid/>](https://redis.io/commands/xsetid/)
+    #[must_use]
+    fn xsetid<K, I>(
+        self,
+        key: K,
+        last_id: I,
+        options: XSetIdOptions,
+    ) -> PreparedCommand<'a, Self, ()>
+    where
+        Self: Sized,
+        K: SingleArg,
+        I: SingleArg,
+    {
+        prepare_command(self, cmd("XSETID").arg(key).arg(last_id).arg(options))
+    }
+
     /// XTRIM trims the stream by evicting older entries (entries with lower IDs) if needed.
     ///
     /// # Return
@@ -562,6 +699,10 @@ pub struct XAddOptions {
 }
 
 impl XAddOptions {
+    /// Don't create the stream if it doesn't exist.
+    ///
+    /// In this case, [`xadd`](StreamCommands::xadd) returns a Null reply,
+    /// which can be retrieved as an `Option<String>`.
     #[must_use]
     pub fn no_mk_stream(mut self) -> Self {
         Self {
@@ -569,6 +710,8 @@ impl XAddOptions {
         }
     }
 
+    /// Trim the stream after the entry has been added,
+    /// with the same semantics as the [`xtrim`](StreamCommands::xtrim) command.
     #[must_use]
     pub fn trim_options(mut self, trim_options: XTrimOptions) -> Self {
         Self {
@@ -617,6 +760,7 @@ pub struct XTrimOptions {
 }
 
 impl XTrimOptions {
+    /// Evicts entries as long as the stream's length exceeds the specified `threshold`.
     #[must_use]
     pub fn max_len(operator: XTrimOperator, threshold: i64) -> Self {
         Self {
@@ -628,6 +772,7 @@ impl XTrimOptions {
         }
     }
 
+    /// Evicts entries with IDs lower than `threshold_id`.
     #[must_use]
     pub fn min_id<I: SingleArg>(operator: XTrimOperator, threshold_id: I) -> Self {
         Self {
@@ -639,6 +784,9 @@ impl XTrimOptions {
         }
     }
 
+    /// Maximum number of entries that will be evicted.
+    ///
+    /// Only valid with the [`Approximately`](XTrimOperator::Approximately) operator.
     #[must_use]
     pub fn limit(mut self, count: usize) -> Self {
         Self {
@@ -653,6 +801,45 @@ impl ToArgs for XTrimOptions {
     }
 }
 
+/// Options for the [`xsetid`](StreamCommands::xsetid) command
+#[derive(Default)]
+pub struct XSetIdOptions {
+    command_args: CommandArgs,
+}
+
+impl XSetIdOptions {
+    /// Sets the number of entries ever added to the stream,
+    /// which is used to compute the lag of consumer groups.
+    #[must_use]
+    pub fn entries_added(mut self, entries_added: usize) -> Self {
+        Self {
+            command_args: self
+                .command_args
+                .arg("ENTRIESADDED")
+                .arg(entries_added)
+                .build(),
+        }
+    }
+
+    /// Sets the greatest ID ever deleted from the stream.
+    #[must_use]
+    pub fn max_deleted_id<I: SingleArg>(mut self, max_deleted_id: I) -> Self {
+        Self {
+            command_args: self
+                .command_args
+                .arg("MAXDELETEDID")
+                .arg(max_deleted_id)
+                .build(),
+        }
+    }
+}
+
+impl ToArgs for XSetIdOptions {
+    fn write_args(&self, args: &mut CommandArgs) {
+        args.arg(&self.command_args);
+    }
+}
+
 /// Options for the [`xautoclaim`](StreamCommands::xautoclaim) command
 #[derive(Default)]
 pub struct XAutoClaimOptions {
@@ -681,6 +868,159 @@ impl ToArgs for XAutoClaimOptions {
     }
 }
 
+/// Id of a stream entry, made of a milliseconds time and a sequence number: `<ms>-<seq>`
+///
+/// Ids are ordered like in the stream, and can be passed as arguments
+/// or received as the result of stream commands.
+///
+/// # Example
+/// ```
+/// use rustis::commands::StreamEntryId;
+///
+/// let id: StreamEntryId = "1526919030474-55".parse().unwrap();
+/// assert_eq!(StreamEntryId::new(1526919030474, 55), id);
+/// assert!(id < StreamEntryId::new(1526919030475, 0));
+/// assert_eq!("1526919030474-56", id.next().to_string());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StreamEntryId {
+    /// milliseconds time part of the id
+    pub ms: u64,
+    /// sequence number part of the id
+    pub seq: u64,
+}
+
+impl StreamEntryId {
+    /// The smallest id possible (`0-0`)
+    pub const MIN: StreamEntryId = StreamEntryId::new(0, 0);
+    /// The greatest id possible
+    pub const MAX: StreamEntryId = StreamEntryId::new(u64::MAX, u64::MAX);
+
+    /// Creates an id from its milliseconds time part and its sequence number part (`<ms>-<seq>`)
+    #[must_use]
+    pub const fn new(ms: u64, seq: u64) -> Self {
+        Self { ms, seq }
+    }
+
+    /// Returns the id immediately following this one,
+    /// e.g. to build an exclusive `start` bound for [`xrange`](StreamCommands::xrange)
+    ///
+    /// [`StreamEntryId::MAX`] is returned unchanged
+    #[must_use]
+    pub fn next(&self) -> Self {
+        match (self.ms, self.seq) {
+            (u64::MAX, u64::MAX) => *self,
+            (ms, u64::MAX) => Self::new(ms + 1, 0),
+            (ms, seq) => Self::new(ms, seq + 1),
+        }
+    }
+
+    /// Returns the id immediately preceding this one,
+    /// e.g. to build an exclusive `end` bound for [`xrange`](StreamCommands::xrange)
+    ///
+    /// [`StreamEntryId::MIN`] is returned unchanged
+    #[must_use]
+    pub fn previous(&self) -> Self {
+        match (self.ms, self.seq) {
+            (0, 0) => *self,
+            (ms, 0) => Self::new(ms - 1, u64::MAX),
+            (ms, seq) => Self::new(ms, seq - 1),
+        }
+    }
+}
+
+impl fmt::Display for StreamEntryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.ms, self.seq)
+    }
+}
+
+/// Parses `<ms>-<seq>` or `<ms>`, in which case the sequence number is 0
+impl FromStr for StreamEntryId {
+    type Err = Error;
+
+    fn from_str(str: &str) -> Result<Self> {
+        let parse = |part: &str| {
+            part.parse::<u64>()
+                .map_err(|_| Error::Client(format!("Cannot parse stream entry id `{str}`")))
+        };
+
+        match str.split_once('-') {
+            Some((ms, seq)) => Ok(Self::new(parse(ms)?, parse(seq)?)),
+            None => Ok(Self::new(parse(str)?, 0)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for StreamEntryId {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let id = String::deserialize(deserializer)?;
+        id.parse().map_err(de::Error::custom)
+    }
+}
+
+impl PrimitiveResponse for StreamEntryId {}
+
+impl SingleArg for StreamEntryId {}
+
+impl ToArgs for StreamEntryId {
+    fn write_args(&self, args: &mut CommandArgs) {
+        args.arg(self.to_string());
+    }
+}
+
+/// Bound of a range of stream entry ids for the [`xrange`](StreamCommands::xrange)
+/// and [`xrevrange`](StreamCommands::xrevrange) commands
+///
+/// # Example
+/// ```
+/// use rustis::{
+///     commands::{StreamCommands, StreamEntry, StreamEntryId, StreamRangeBound},
+///     client::Client,
+///     Result,
+/// };
+///
+/// # async fn entries_after(client: &Client, last_id: StreamEntryId) -> Result<()> {
+/// // entries strictly after `last_id`
+/// let entries: Vec<StreamEntry<String>> = client
+///     .xrange(
+///         "mystream",
+///         StreamRangeBound::Exclusive(last_id),
+///         StreamRangeBound::Max,
+///         Some(10),
+///     )
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamRangeBound {
+    /// The smallest id possible (`-`)
+    Min,
+    /// The greatest id possible (`+`)
+    Max,
+    /// The range includes this id
+    Inclusive(StreamEntryId),
+    /// The range excludes this id (`(<ms>-<seq>`)
+    Exclusive(StreamEntryId),
+}
+
+impl SingleArg for StreamRangeBound {}
+
+impl ToArgs for StreamRangeBound {
+    fn write_args(&self, args: &mut CommandArgs) {
+        match self {
+            StreamRangeBound::Min => args.arg("-"),
+            StreamRangeBound::Max => args.arg("+"),
+            StreamRangeBound::Inclusive(id) => args.arg(id.to_string()),
+            StreamRangeBound::Exclusive(id) => args.arg(format!("({id}")),
+        };
+    }
+}
+
 /// Result for the [`xrange`](StreamCommands::xrange) and other associated commands.
 #[derive(Deserialize)]
 pub struct StreamEntry<V>
@@ -688,7 +1028,7 @@ where
     V: PrimitiveResponse,
 {
     /// The stream Id
-    pub stream_id: String,
+    pub stream_id: StreamEntryId,
     /// entries with their fields and values in the exact same
     /// order as [`xadd`](StreamCommands::xadd) added them.
     pub items: HashMap<String, V>,
@@ -702,13 +1042,13 @@ where
 {
     /// A stream ID to be used as the `start` argument for
     /// the next call to [`xautoclaim`](StreamCommands::xautoclaim).
-    pub start_stream_id: String,
+    pub start_stream_id: StreamEntryId,
     /// An array containing all the successfully claimed messages in
     /// the same format as [`xrange`](StreamCommands::xrange).
     pub entries: Vec<StreamEntry<V>>,
     /// An array containing message IDs that no longer exist in the stream,
     /// and were deleted from the PEL in which they were found.
-    pub deleted_ids: Vec<String>,
+    pub deleted_ids: Vec<StreamEntryId>,
 }
 
 /// Options for the [`xclaim`](StreamCommands::xclaim) command
@@ -826,6 +1166,13 @@ pub struct XConsumerInfo {
     /// since the consumer last interacted with the server
     #[serde(rename = "idle")]
     pub idle_millis: u64,
+
+    /// the number of milliseconds that have passed
+    /// since the consumer last successful interaction (Redis 7.2+).
+    ///
+    /// `-1` if the consumer never had a successful interaction
+    #[serde(rename = "inactive")]
+    pub inactive_millis: Option<i64>,
 }
 
 /// Result entry for the [`xinfo_groups`](StreamCommands::xinfo_groups) command.
@@ -843,7 +1190,7 @@ pub struct XGroupInfo {
     pub pending: usize,
 
     /// the ID of the last entry delivered the group's consumers
-    pub last_delivered_id: String,
+    pub last_delivered_id: StreamEntryId,
 
     /// the logical "read counter" of the last entry delivered to group's consumers
     pub entries_read: Option<usize>,
@@ -853,12 +1200,14 @@ pub struct XGroupInfo {
     pub lag: Option<usize>,
 }
 
-/// Options for the [`xinfo_stream`](StreamCommands::xinfo_stream) command
+/// Options for the [`xinfo_stream_with_options`](StreamCommands::xinfo_stream_with_options) command
+#[deprecated(note = "use `xinfo_stream`, or `xinfo_stream_full` with its `count` argument")]
 #[derive(Default)]
 pub struct XInfoStreamOptions {
     command_args: CommandArgs,
 }
 
+#[allow(deprecated)]
 impl XInfoStreamOptions {
     /// The optional FULL modifier provides a more verbose reply.
     #[must_use]
@@ -878,6 +1227,7 @@ impl XInfoStreamOptions {
     }
 }
 
+#[allow(deprecated)]
 impl ToArgs for XInfoStreamOptions {
     fn write_args(&self, args: &mut CommandArgs) {
         args.arg(&self.command_args);
@@ -901,21 +1251,146 @@ pub struct XStreamInfo {
     pub groups: usize,
 
     /// the ID of the least-recently entry that was added to the stream
-    pub last_generated_id: String,
+    pub last_generated_id: StreamEntryId,
+
+    /// the maximal entry ID that was deleted from the stream
+    pub max_deleted_entry_id: StreamEntryId,
+
+    /// the count of all entries added to the stream during its lifetime
+    pub entries_added: usize,
+
+    /// the ID and field-value tuples of the first entry in the stream,
+    /// or `None` if the stream is empty
+    pub first_entry: Option<StreamEntry<String>>,
+
+    /// the ID and field-value tuples of the last entry in the stream,
+    /// or `None` if the stream is empty
+    pub last_entry: Option<StreamEntry<String>>,
+
+    /// the ID of the first entry that is still recorded in the stream
+    pub recorded_first_entry_id: StreamEntryId,
+}
+
+/// Stream info returned by the [`xinfo_stream_full`](StreamCommands::xinfo_stream_full) command.
+#[cfg_attr(docsrs, doc(cfg(feature = "xinfo-full")))]
+#[cfg(feature = "xinfo-full")]
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct XStreamFullInfo {
+    /// the number of entries in the stream (see [`xlen`](StreamCommands::xlen))
+    pub length: usize,
+
+    /// the number of keys in the underlying radix data structure
+    pub radix_tree_keys: usize,
+
+    /// the number of nodes in the underlying radix data structure
+    pub radix_tree_nodes: usize,
+
+    /// the ID of the least-recently entry that was added to the stream
+    pub last_generated_id: StreamEntryId,
 
     /// the maximal entry ID that was deleted from the stream
-    pub max_deleted_entry_id: String,
+    pub max_deleted_entry_id: StreamEntryId,
 
     /// the count of all entries added to the stream during its lifetime
     pub entries_added: usize,
 
-    /// the ID and field-value tuples of the first entry in the stream
-    pub first_entry: StreamEntry<String>,
+    /// the ID of the first entry that is still recorded in the stream
+    pub recorded_first_entry_id: StreamEntryId,
+
+    /// the entries of the stream, in ascending order
+    pub entries: Vec<StreamEntry<String>>,
+
+    /// the consumer groups defined for the stream
+    pub groups: Vec<XStreamGroupFullInfo>,
+}
+
+/// Consumer group info, part of the [`xinfo_stream_full`](StreamCommands::xinfo_stream_full) result.
+#[cfg_attr(docsrs, doc(cfg(feature = "xinfo-full")))]
+#[cfg(feature = "xinfo-full")]
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct XStreamGroupFullInfo {
+    /// the consumer group's name
+    pub name: String,
+
+    /// the ID of the last entry delivered the group's consumers
+    pub last_delivered_id: StreamEntryId,
+
+    /// the logical "read counter" of the last entry delivered to group's consumers
+    pub entries_read: Option<usize>,
+
+    /// the number of entries in the stream that are still waiting to be delivered to the group's consumers,
+    /// or a NULL when that number can't be determined.
+    pub lag: Option<usize>,
+
+    /// the length of the group's pending entries list (PEL)
+    pub pel_count: usize,
+
+    /// the entries of the group's pending entries list (PEL)
+    pub pending: Vec<XStreamPendingEntry>,
+
+    /// the consumers of the group
+    pub consumers: Vec<XStreamConsumerFullInfo>,
+}
+
+/// Pending entry of a consumer group,
+/// part of the [`xinfo_stream_full`](StreamCommands::xinfo_stream_full) result.
+#[cfg_attr(docsrs, doc(cfg(feature = "xinfo-full")))]
+#[cfg(feature = "xinfo-full")]
+#[derive(Deserialize)]
+pub struct XStreamPendingEntry {
+    /// the ID of the pending entry
+    pub id: StreamEntryId,
+
+    /// the name of the consumer the entry was delivered to
+    pub consumer: String,
+
+    /// the Unix timestamp (in milliseconds) of the last delivery of the entry
+    pub delivery_time_millis: u64,
+
+    /// the number of times the entry was delivered
+    pub delivery_count: usize,
+}
+
+/// Consumer info, part of the [`xinfo_stream_full`](StreamCommands::xinfo_stream_full) result.
+#[cfg_attr(docsrs, doc(cfg(feature = "xinfo-full")))]
+#[cfg(feature = "xinfo-full")]
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct XStreamConsumerFullInfo {
+    /// the consumer's name
+    pub name: String,
+
+    /// the Unix timestamp (in milliseconds) of the last attempted interaction
+    #[serde(rename = "seen-time")]
+    pub seen_time_millis: u64,
+
+    /// the Unix timestamp (in milliseconds) of the last successful interaction (Redis 7.2+)
+    #[serde(rename = "active-time")]
+    pub active_time_millis: Option<i64>,
 
-    /// the ID and field-value tuples of the last entry in the stream
-    pub last_entry: StreamEntry<String>,
+    /// the length of the consumer's pending entries list (PEL)
+    pub pel_count: usize,
 
-    pub recorded_first_entry_id: String,
+    /// the entries of the consumer's pending entries list (PEL)
+    pub pending: Vec<XStreamConsumerPendingEntry>,
+}
+
+/// Pending entry of a consumer,
+/// part of the [`xinfo_stream_full`](StreamCommands::xinfo_stream_full) result.
+#[cfg_attr(docsrs, doc(cfg(feature = "xinfo-full")))]
+#[cfg(feature = "xinfo-full")]
+#[derive(Deserialize)]
+pub struct XStreamConsumerPendingEntry {
+    /// the ID of the pending entry
+    pub id: StreamEntryId,
+
+    /// the Unix timestamp (in milliseconds) of the last delivery of the entry
+    pub delivery_time_millis: u64,
+
+    /// the number of times the entry was delivered
+    pub delivery_count: usize,
 }
 
 /// Options for the [`xread`](StreamCommands::xread) command
@@ -981,76 +1456,113 @@ impl ToArgs for XReadGroupOptions {
     }
 }
 
-/// Options for the [`xpending_with_options`](StreamCommands::xpending_with_options) command
+/// Options for the [`xpending_detailed`](StreamCommands::xpending_detailed) command
 #[derive(Default)]
 pub struct XPendingOptions {
-    command_args: CommandArgs,
+    idle_args: CommandArgs,
+    consumer_args: CommandArgs,
+    start: Option<Vec<u8>>,
+    end: Option<Vec<u8>>,
+    count: Option<usize>,
 }
 
 impl XPendingOptions {
+    /// Filter the pending messages by their idle time (in milliseconds).
+    ///
+    /// Calling this method again replaces the previous idle time.
     #[must_use]
-    pub fn idle(mut self, min_idle_time: u64) -> Self {
+    pub fn idle(self, min_idle_time: u64) -> Self {
         Self {
-            command_args: self.command_args.arg("IDLE").arg(min_idle_time).build(),
+            idle_args: CommandArgs::default()
+                .arg("IDLE")
+                .arg(min_idle_time)
+                .build(),
+            ..self
         }
     }
 
+    /// Only return the pending messages owned by `consumer`.
+    ///
+    /// Calling this method again replaces the previous consumer.
     #[must_use]
-    pub fn start<S: SingleArg>(mut self, start: S) -> Self {
+    pub fn consumer<C: SingleArg>(self, consumer: C) -> Self {
         Self {
-            command_args: self.command_args.arg(start).build(),
+            consumer_args: CommandArgs::default().arg(consumer).build(),
+            ..self
         }
     }
 
+    /// Start of the range of ids of the pending messages
+    #[deprecated(note = "pass `start` to `xpending_detailed`")]
     #[must_use]
-    pub fn end<E: SingleArg>(mut self, end: E) -> Self {
+    pub fn start<S: SingleArg>(self, start: S) -> Self {
         Self {
-            command_args: self.command_args.arg(end).build(),
+            start: Some(single_arg_bytes(start)),
+            ..self
         }
     }
 
+    /// End of the range of ids of the pending messages
+    #[deprecated(note = "pass `end` to `xpending_detailed`")]
     #[must_use]
-    pub fn count(mut self, count: usize) -> Self {
+    pub fn end<E: SingleArg>(self, end: E) -> Self {
         Self {
-            command_args: self.command_args.arg(count).build(),
+            end: Some(single_arg_bytes(end)),
+            ..self
         }
     }
 
+    /// Maximum number of pending messages to return
+    #[deprecated(note = "pass `count` to `xpending_detailed`")]
     #[must_use]
-    pub fn consumer<C: SingleArg>(mut self, consumer: C) -> Self {
+    pub fn count(self, count: usize) -> Self {
         Self {
-            command_args: self.command_args.arg(consumer).build(),
+            count: Some(count),
+            ..self
         }
     }
 }
 
-impl ToArgs for XPendingOptions {
-    fn write_args(&self, args: &mut CommandArgs) {
-        args.arg(&self.command_args);
-    }
+fn single_arg_bytes<A: SingleArg>(arg: A) -> Vec<u8> {
+    let args = CommandArgs::default().arg(arg).build();
+    args.into_iter()
+        .next()
+        .map(<[u8]>::to_vec)
+        .unwrap_or_default()
 }
 
 /// Result for the [`xpending`](StreamCommands::xpending) command
 #[derive(Deserialize)]
 pub struct XPendingResult {
+    /// the total number of pending messages for this consumer group
     pub num_pending_messages: usize,
-    pub smallest_id: String,
-    pub greatest_id: String,
+    /// the smallest ID among the pending messages, `None` if there is no pending message
+    pub smallest_id: Option<StreamEntryId>,
+    /// the greatest ID among the pending messages, `None` if there is no pending message
+    pub greatest_id: Option<StreamEntryId>,
+    /// every consumer in the consumer group with at least one pending message,
+    /// and the number of pending messages it has
     pub consumers: Vec<XPendingConsumer>,
 }
 
 /// Customer info result for the [`xpending`](StreamCommands::xpending) command
 #[derive(Deserialize)]
 pub struct XPendingConsumer {
+    /// the consumer name
     pub consumer: String,
+    /// the number of pending messages of this consumer
     pub num_messages: usize,
 }
 
-/// Message result for the [`xpending_with_options`](StreamCommands::xpending_with_options) command
+/// Message result for the [`xpending_detailed`](StreamCommands::xpending_detailed) command
 #[derive(Deserialize)]
 pub struct XPendingMessageResult {
-    pub message_id: String,
+    /// the ID of the message
+    pub message_id: StreamEntryId,
+    /// the name of the consumer that fetched the message and has still to acknowledge it
     pub consumer: String,
+    /// the number of milliseconds that elapsed since the last time this message was delivered to this consumer
     pub elapsed_millis: u64,
+    /// the number of times this message was delivered
     pub times_delivered: usize,
 }
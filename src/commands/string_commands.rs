@@ -1,9 +1,11 @@
 use crate::{
-    client::{prepare_command, PreparedCommand},
+    client::{prepare_command, BatchPreparedCommand, Client, PreparedCommand},
+    commands::{GenericCommands, TtlStatus},
     resp::{
         cmd, CollectionResponse, CommandArgs, KeyValueArgsCollection, PrimitiveResponse, SingleArg,
         SingleArgCollection, ToArgs,
     },
+    Result,
 };
 use serde::{
     de::{self, SeqAccess, Visitor},
@@ -132,6 +134,30 @@ pub trait StringCommands<'a> {
         prepare_command(self, cmd("GET").arg(key))
     }
 
+    /// Gets the value of `key` together with its remaining time to live,
+    /// with a [`get`](StringCommands::get) and a [`ttl_status`](GenericCommands::ttl_status)
+    /// sent in a single [`transaction`](Client::create_transaction).
+    ///
+    /// Only available on a [`Client`].
+    ///
+    /// # Return
+    /// The value of the key and its [`TtlStatus`](crate::commands::TtlStatus),
+    /// or `None` if the key does not exist.
+    #[allow(async_fn_in_trait)]
+    async fn get_with_ttl<K, V>(self, key: K) -> Result<Option<(V, TtlStatus)>>
+    where
+        Self: Sized + Into<&'a Client>,
+        K: SingleArg + Clone,
+        V: PrimitiveResponse + serde::de::DeserializeOwned,
+    {
+        let mut transaction = self.into().create_transaction();
+        transaction.get::<_, ()>(key.clone()).queue();
+        transaction.ttl_status(key).queue();
+        let (value, ttl_status): (Option<V>, TtlStatus) = transaction.execute().await?;
+
+        Ok(value.map(|value| (value, ttl_status)))
+    }
+
     /// Get the value of key and delete the key.
     ///
     /// This command is similar to GET, except for the fact that it also deletes the key on success
@@ -207,11 +233,14 @@ pub trait StringCommands<'a> {
     /// So -1 means the last character, -2 the penultimate and so forth.
     ///
     /// The function handles out of range requests by limiting the resulting range to the actual length of the string.
-
+    ///
+    /// # Return
+    /// The substring, or an empty string if the key does not exist.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/getrange/>](https://redis.io/commands/getrange/)
     #[must_use]
-    fn getrange<K, V>(self, key: K, start: usize, end: isize) -> PreparedCommand<'a, Self, V>
+    fn getrange<K, V>(self, key: K, start: isize, end: isize) -> PreparedCommand<'a, Self, V>
     where
         Self: Sized,
         K: SingleArg,
@@ -313,6 +342,10 @@ pub trait StringCommands<'a> {
     /// # Return
     /// the value of key after the increment
     ///
+    /// The reply is parsed as an `f64`, which cannot represent most decimal values exactly
+    /// (e.g. `0.1`): use [`incrbyfloat_str`](StringCommands::incrbyfloat_str)
+    /// when the exact decimal value matters, for example for monetary counters.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/incrbyfloat/>](https://redis.io/commands/incrbyfloat/)
     #[must_use]
@@ -324,6 +357,26 @@ pub trait StringCommands<'a> {
         prepare_command(self, cmd("INCRBYFLOAT").arg(key).arg(increment))
     }
 
+    /// Same as [`incrbyfloat`](StringCommands::incrbyfloat),
+    /// but the increment and the result are exchanged as decimal strings, without going through an `f64`.
+    ///
+    /// The result can then be parsed into an exact decimal type (e.g. `rust_decimal::Decimal`).
+    ///
+    /// # Return
+    /// the value of key after the increment, as formatted by Redis (trailing zeroes are removed)
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/incrbyfloat/>](https://redis.io/commands/incrbyfloat/)
+    #[must_use]
+    fn incrbyfloat_str<K, I>(self, key: K, increment: I) -> PreparedCommand<'a, Self, String>
+    where
+        Self: Sized,
+        K: SingleArg,
+        I: SingleArg,
+    {
+        prepare_command(self, cmd("INCRBYFLOAT").arg(key).arg(increment))
+    }
+
     /// The LCS command implements the longest common subsequence algorithm
     ///
     /// # Return
@@ -411,6 +464,40 @@ pub trait StringCommands<'a> {
         prepare_command(self, cmd("MGET").arg(keys))
     }
 
+    /// Gets the values of `keys` with one [`mget`](StringCommands::mget) per chunk of `chunk_size` keys,
+    /// all chunks being sent in a single pipeline.
+    ///
+    /// This avoids sending one huge command which would block the server for a long time,
+    /// or exceed its `proto-max-bulk-len`. In cluster mode, each chunk is then split by hash slot.
+    ///
+    /// Only available on a [`Client`].
+    ///
+    /// # Return
+    /// The values of the keys, in the same order as `keys`
+    #[allow(async_fn_in_trait)]
+    async fn mget_chunked<K, V>(self, keys: &[K], chunk_size: usize) -> Result<Vec<V>>
+    where
+        Self: Sized + Into<&'a Client>,
+        K: SingleArg,
+        V: PrimitiveResponse + serde::de::DeserializeOwned,
+    {
+        let commands = keys
+            .chunks(chunk_size.max(1))
+            .map(|chunk| cmd("MGET").arg(chunk))
+            .collect::<Vec<_>>();
+        if commands.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let results = self.into().send_batch(commands, None).await?;
+
+        let mut values = Vec::with_capacity(keys.len());
+        for result in results {
+            values.extend(result.to::<Vec<V>>()?);
+        }
+        Ok(values)
+    }
+
     /// Sets the given keys to their respective values.
     ///
     /// # Return
@@ -429,6 +516,35 @@ pub trait StringCommands<'a> {
         prepare_command(self, cmd("MSET").arg(items))
     }
 
+    /// Sets the given keys to their respective values with one [`mset`](StringCommands::mset)
+    /// per chunk of `chunk_size` items, all chunks being sent in a single pipeline.
+    ///
+    /// Unlike [`mset`](StringCommands::mset), the whole operation is not atomic.
+    ///
+    /// Only available on a [`Client`].
+    #[allow(async_fn_in_trait)]
+    async fn mset_chunked<K, V>(self, items: &[(K, V)], chunk_size: usize) -> Result<()>
+    where
+        Self: Sized + Into<&'a Client>,
+        K: SingleArg,
+        V: SingleArg,
+    {
+        let commands = items
+            .chunks(chunk_size.max(1))
+            .map(|chunk| cmd("MSET").arg(chunk))
+            .collect::<Vec<_>>();
+        if commands.is_empty() {
+            return Ok(());
+        }
+
+        let results = self.into().send_batch(commands, None).await?;
+
+        for result in results {
+            result.to::<()>()?;
+        }
+        Ok(())
+    }
+
     /// Sets the given keys to their respective values.
     /// MSETNX will not perform any operation at all even if just a single key already exists.
     ///
@@ -599,6 +715,10 @@ pub trait StringCommands<'a> {
     /// starting at the specified offset,
     /// for the entire length of value.
     ///
+    /// If the offset is larger than the current length of the string,
+    /// the string is padded with zero-bytes to make offset fit.
+    /// Non-existing keys are considered as empty strings.
+    ///
     /// # Return
     /// the length of the string after it was modified by the command.
     ///
@@ -50,6 +50,11 @@ pub enum Error {
     Tls(String),
     /// The I/O operation’s timeout expired
     Timeout(String),
+    /// Raised by [`Client::try_connect`](crate::client::Client::try_connect)
+    /// if the Redis server cannot be reached within `connect_timeout`
+    Connection(String),
+    /// A reply of the Redis server cannot be converted into the requested Rust type
+    Conversion(ConversionError),
     /// Internal error to trigger retry sending the command
     #[doc(hidden)]
     Retry(SmallVec<[RetryReason; 1]>),
@@ -71,11 +76,74 @@ impl std::fmt::Display for Error {
             Error::Tls(e) => f.write_fmt(format_args!("Tls error: {}", e)),
             Error::Retry(r) => f.write_fmt(format_args!("Retry: {:?}", r)),
             Error::Timeout(e) => f.write_fmt(format_args!("Timeout error: {}", e)),
+            Error::Connection(e) => f.write_fmt(format_args!("Connection error: {}", e)),
+            Error::Conversion(e) => f.write_fmt(format_args!("Conversion error: {}", e)),
             Error::EOF => f.write_str("EOF error"),
         }
     }
 }
 
+/// Failure to convert a reply of the Redis server into the requested Rust type
+///
+/// Describes the requested type, the kind of the reply with a short preview of its content,
+/// and, when the reply of a command is converted, the name of this command, e.g.
+/// ``Cannot parse bulk string `hello` to i64 in reply to GET``
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversionError {
+    /// Name of the requested Rust type (e.g. `i64`)
+    pub target: String,
+    /// Kind of the reply, with a short preview of its content (e.g. ``bulk string `hello` ``)
+    pub value: String,
+    /// Name of the command whose reply has been converted, if known
+    pub command: Option<&'static str>,
+}
+
+impl ConversionError {
+    /// Max number of chars of the content preview
+    const PREVIEW_LEN: usize = 50;
+
+    pub(crate) fn new(target: &str, kind: &str, content: Option<&str>) -> Self {
+        let value = match content {
+            Some(content) if content.chars().count() > Self::PREVIEW_LEN => {
+                let preview = content.chars().take(Self::PREVIEW_LEN).collect::<String>();
+                format!("{kind} `{preview}...`")
+            }
+            Some(content) => format!("{kind} `{content}`"),
+            None => kind.to_owned(),
+        };
+
+        Self {
+            target: target.to_owned(),
+            value,
+            command: None,
+        }
+    }
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cannot parse {} to {}", self.value, self.target)?;
+        if let Some(command) = self.command {
+            write!(f, " in reply to {command}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error {
+    /// Adds the name of the command whose reply has been converted to a conversion error,
+    /// unless this error already reports the reply of another command
+    pub(crate) fn with_command(self, command: &'static str) -> Self {
+        match self {
+            Error::Conversion(mut e) => {
+                e.command.get_or_insert(command);
+                Error::Conversion(e)
+            }
+            e => e,
+        }
+    }
+}
+
 impl serde::de::Error for Error {
     fn custom<T>(msg: T) -> Self
     where
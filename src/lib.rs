@@ -39,12 +39,17 @@ rustis is a Redis client for Rust.
 | `tokio-tls` | Tokio TLS support (optional) |
 | `async-std-tls` | async-std TLS support (optional) |
 | `pool` | Pooled client manager (optional) |
+| `metrics` | [`Metrics`](client::Metrics) hooks to observe commands, pub/sub messages, reconnections & pool checkouts, with a [metrics](https://docs.rs/metrics) crate recorder (optional) |
+| `debug-commands` | Internal commands meant to test Redis itself, like `PFDEBUG` & `PFSELFTEST` (optional) |
+| `xinfo-full` | `xinfo_stream_full`, with a typed result of `XINFO STREAM FULL` (optional) |
 | `redis-json` | [RedisJSON v2.4](https://redis.io/docs/stack/json/) support (optional) |
 | `redis-search` | [RedisSearch v2.6](https://redis.io/docs/stack/search/) support (optional) |
 | `redis-graph` | [RedisGraph v2.10](https://redis.io/docs/stack/graph/) support (optional) |
 | `redis-bloom` | [RedisBloom v2.4](https://redis.io/docs/stack/bloom/) support (optional) |
 | `redis-time-series` | [RedisTimeSeries v1.8](https://redis.io/docs/stack/timeseries/) support (optional) |
 | `redis-stack` | activate `redis-json`, `redis-search`, `redis-graph`, `redis-bloom` & `redis-time-series` at the same time (optional) |
+| `chrono` | Conversion of [`Timestamp`](resp::Timestamp) into [chrono](https://docs.rs/chrono) `DateTime<Utc>` (optional) |
+| `indexmap` | [indexmap](https://docs.rs/indexmap) `IndexMap` & `IndexSet` as command responses, preserving the order of the reply (optional) |
 
 # Basic Usage
 
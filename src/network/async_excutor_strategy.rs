@@ -23,19 +23,30 @@ pub(crate) type TcpTlsStreamWriter =
 
 #[cfg(feature = "async-std-runtime")]
 pub(crate) type TcpStreamReader =
-    tokio_util::compat::Compat<futures::io::ReadHalf<async_std::net::TcpStream>>;
+    tokio_util::compat::Compat<futures_util::io::ReadHalf<async_std::net::TcpStream>>;
 #[cfg(feature = "async-std-runtime")]
 pub(crate) type TcpStreamWriter =
-    tokio_util::compat::Compat<futures::io::WriteHalf<async_std::net::TcpStream>>;
+    tokio_util::compat::Compat<futures_util::io::WriteHalf<async_std::net::TcpStream>>;
 #[cfg(feature = "async-std-tls")]
 pub(crate) type TcpTlsStreamReader = tokio_util::compat::Compat<
-    futures::io::ReadHalf<async_native_tls::TlsStream<async_std::net::TcpStream>>,
+    futures_util::io::ReadHalf<async_native_tls::TlsStream<async_std::net::TcpStream>>,
 >;
 #[cfg(feature = "async-std-tls")]
 pub(crate) type TcpTlsStreamWriter = tokio_util::compat::Compat<
-    futures::io::WriteHalf<async_native_tls::TlsStream<async_std::net::TcpStream>>,
+    futures_util::io::WriteHalf<async_native_tls::TlsStream<async_std::net::TcpStream>>,
 >;
 
+/// Applies the TCP options of the config to a newly connected socket
+fn set_socket_options(socket: SockRef, config: &Config) -> Result<()> {
+    if let Some(keep_alive) = config.keep_alive {
+        socket.set_tcp_keepalive(&TcpKeepalive::new().with_time(keep_alive))?;
+    }
+
+    socket.set_nodelay(config.no_delay)?;
+
+    Ok(())
+}
+
 pub(crate) async fn tcp_connect(
     host: &str,
     port: u16,
@@ -51,39 +62,35 @@ pub(crate) async fn tcp_connect(
 
     #[cfg(feature = "tokio-runtime")]
     {
-        let stream = timeout(
+        let mut stream = timeout(
             config.connect_timeout,
             tokio::net::TcpStream::connect((host, port)),
         )
         .await??;
 
-        if let Some(keep_alive) = config.keep_alive {
-            SockRef::from(&stream).set_tcp_keepalive(&TcpKeepalive::new().with_time(keep_alive))?;
-        }
+        set_socket_options(SockRef::from(&stream), config)?;
 
-        if config.no_delay {
-            stream.set_nodelay(true)?;
+        if let Some(header) = &config.send_proxy_protocol {
+            tokio::io::AsyncWriteExt::write_all(&mut stream, &header.to_bytes()).await?;
         }
 
         (reader, writer) = tokio::io::split(stream);
     }
     #[cfg(feature = "async-std-runtime")]
     {
-        use futures::AsyncReadExt;
+        use futures_util::AsyncReadExt;
         use tokio_util::compat::{FuturesAsyncReadCompatExt, FuturesAsyncWriteCompatExt};
 
-        let stream = timeout(
+        let mut stream = timeout(
             config.connect_timeout,
             async_std::net::TcpStream::connect((host, port)),
         )
         .await??;
 
-        if let Some(keep_alive) = config.keep_alive {
-            SockRef::from(&stream).set_tcp_keepalive(&TcpKeepalive::new().with_time(keep_alive))?;
-        }
+        set_socket_options(SockRef::from(&stream), config)?;
 
-        if config.no_delay {
-            stream.set_nodelay(true)?;
+        if let Some(header) = &config.send_proxy_protocol {
+            futures_util::AsyncWriteExt::write_all(&mut stream, &header.to_bytes()).await?;
         }
 
         let (r, w) = stream.split();
@@ -101,9 +108,12 @@ pub(crate) async fn tcp_tls_connect(
     host: &str,
     port: u16,
     tls_config: &TlsConfig,
-    connect_timeout: Duration,
+    config: &Config,
 ) -> Result<(TcpTlsStreamReader, TcpTlsStreamWriter)> {
-    debug!("Connecting to {host}:{port} with timeout {connect_timeout:?}...");
+    debug!(
+        "Connecting to {host}:{port} with timeout {:?}...",
+        config.connect_timeout
+    );
 
     let reader: TcpTlsStreamReader;
     let writer: TcpTlsStreamWriter;
@@ -112,11 +122,15 @@ pub(crate) async fn tcp_tls_connect(
     #[cfg(feature = "tokio-runtime")]
     #[cfg(feature = "tokio-tls")]
     {
-        let stream = timeout(
-            connect_timeout,
+        let mut stream = timeout(
+            config.connect_timeout,
             tokio::net::TcpStream::connect((host, port)),
         )
         .await??;
+        set_socket_options(SockRef::from(&stream), config)?;
+        if let Some(header) = &config.send_proxy_protocol {
+            tokio::io::AsyncWriteExt::write_all(&mut stream, &header.to_bytes()).await?;
+        }
         let tls_connector: native_tls::TlsConnector = builder.build()?;
         let tls_connector = tokio_native_tls::TlsConnector::from(tls_connector);
         let tls_stream = tls_connector.connect(host, stream).await?;
@@ -125,14 +139,18 @@ pub(crate) async fn tcp_tls_connect(
     #[cfg(feature = "async-std-runtime")]
     #[cfg(feature = "async-std-tls")]
     {
-        use futures::AsyncReadExt;
+        use futures_util::AsyncReadExt;
         use tokio_util::compat::{FuturesAsyncReadCompatExt, FuturesAsyncWriteCompatExt};
 
-        let stream = timeout(
-            connect_timeout,
+        let mut stream = timeout(
+            config.connect_timeout,
             async_std::net::TcpStream::connect((host, port)),
         )
         .await??;
+        set_socket_options(SockRef::from(&stream), config)?;
+        if let Some(header) = &config.send_proxy_protocol {
+            futures_util::AsyncWriteExt::write_all(&mut stream, &header.to_bytes()).await?;
+        }
         let tls_connector: async_native_tls::TlsConnector = builder.into();
         let tls_stream = tls_connector.connect(host, stream).await?;
         let (r, w) = tls_stream.split();
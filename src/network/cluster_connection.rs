@@ -5,7 +5,7 @@ use crate::{
         LegacyClusterShardResult, RequestPolicy, ResponsePolicy,
     },
     network::{CommandInfoManager, Version},
-    resp::{Command, RespBuf, RespDeserializer, RespSerializer},
+    resp::{Command, CommandRouting, RespBuf, RespDeserializer, RespSerializer},
     Error, RedisError, RedisErrorKind, Result, RetryReason, StandaloneConnection,
 };
 use futures_util::{future, FutureExt};
@@ -22,6 +22,7 @@ use std::{
     fmt::{self, Debug, Formatter},
     iter::zip,
     sync::Arc,
+    time::Instant,
 };
 
 #[derive(Clone, PartialEq, Eq, Debug, PartialOrd, Ord)]
@@ -102,6 +103,10 @@ pub struct ClusterConnection {
     slot_ranges: Vec<SlotRange>,
     command_info_manager: CommandInfoManager,
     pending_requests: VecDeque<RequestInfo>,
+    /// when `slot_ranges` was last loaded
+    slot_ranges_loaded_at: Instant,
+    /// set on `CLUSTERDOWN` so that `slot_ranges` is reloaded before the next write
+    slot_ranges_stale: bool,
     tag: String,
 }
 
@@ -126,6 +131,8 @@ impl ClusterConnection {
             slot_ranges,
             command_info_manager,
             pending_requests: VecDeque::new(),
+            slot_ranges_loaded_at: Instant::now(),
+            slot_ranges_stale: false,
             tag,
         })
     }
@@ -141,13 +148,17 @@ impl ClusterConnection {
     ) -> Result<()> {
         debug!("[{}] Analyzing command {command:?}", self.tag);
 
+        if let Some(routing) = &command.routing {
+            return self.explicit_routing(command, routing, ask_reasons).await;
+        }
+
         let command_info = self.command_info_manager.get_command_info(command);
 
         let command_info = if let Some(command_info) = command_info {
             command_info
         } else {
             return Err(Error::Client(format!(
-                "[{}] Unknown command {}",
+                "[{}] Unknown command {}, use an explicit routing (see PreparedCommand::route_by_key)",
                 self.tag, command.name
             )));
         };
@@ -159,17 +170,22 @@ impl ClusterConnection {
             .command_info_manager
             .extract_keys(command, &mut self.nodes[node_idx].connection)
             .await?;
+        let keys = Self::shard_channels(command, &command_name).unwrap_or(keys);
         let slots = Self::hash_slots(&keys);
 
         debug!("[{}] keys: {keys:?}, slots: {slots:?}", self.tag);
 
-        let request_policy = command_info.command_tips.iter().find_map(|tip| {
-            if let CommandTip::RequestPolicy(request_policy) = tip {
-                Some(request_policy)
-            } else {
-                None
-            }
-        });
+        let request_policy = command_info
+            .command_tips
+            .iter()
+            .find_map(|tip| {
+                if let CommandTip::RequestPolicy(request_policy) = tip {
+                    Some(request_policy.clone())
+                } else {
+                    None
+                }
+            })
+            .or_else(|| Self::default_request_policy(&command_name));
 
         if let Some(request_policy) = request_policy {
             match request_policy {
@@ -208,15 +224,7 @@ impl ClusterConnection {
         commands: SmallVec<[&mut Command; 10]>,
         retry_reasons: &[RetryReason],
     ) -> Result<()> {
-        if retry_reasons.iter().any(|r| {
-            matches!(
-                r,
-                RetryReason::Moved {
-                    hash_slot: _,
-                    address: _
-                }
-            )
-        }) {
+        if self.must_refresh_slot_ranges(retry_reasons) {
             self.refresh_nodes_and_slot_ranges().await?;
         }
 
@@ -269,6 +277,74 @@ impl ClusterConnection {
         Ok(())
     }
 
+    /// The slot map is reloaded:
+    /// * after a `CLUSTERDOWN` error,
+    /// * when it is older than `slots_refresh_interval`,
+    /// * after a `MOVED` redirection, unless the slot map is already up to date:
+    ///   concurrent commands redirected for the same migration only trigger a single reload.
+    fn must_refresh_slot_ranges(&self, retry_reasons: &[RetryReason]) -> bool {
+        if self.slot_ranges_stale {
+            return true;
+        }
+
+        if let Some(slots_refresh_interval) = self.cluster_config.slots_refresh_interval {
+            if self.slot_ranges_loaded_at.elapsed() >= slots_refresh_interval {
+                return true;
+            }
+        }
+
+        retry_reasons.iter().any(|r| match r {
+            RetryReason::Moved { hash_slot, address } => {
+                !self.is_slot_served_by(*hash_slot, address)
+            }
+            RetryReason::Ask { .. } => false,
+        })
+    }
+
+    /// Checks if the master of `slot` in the current slot map is at `address`
+    fn is_slot_served_by(&self, slot: u16, address: &(String, u16)) -> bool {
+        self.get_slot_range_by_slot(slot)
+            .and_then(|slot_range| slot_range.node_ids.first())
+            .and_then(|node_id| self.get_node_index_by_id(node_id))
+            .is_some_and(|node_idx| self.nodes[node_idx].address == *address)
+    }
+
+    /// Request policy of the commands which must reach all the masters,
+    /// when the server does not advertise a command tip for them:
+    /// `FLUSHALL` and `FLUSHDB` before Redis 7, or `PUBSUB SHARDCHANNELS`,
+    /// which only lists the shard channels of the node it is sent to
+    fn default_request_policy(command_name: &str) -> Option<RequestPolicy> {
+        match command_name {
+            "FLUSHALL" | "FLUSHDB" | "PUBSUB|SHARDCHANNELS" => Some(RequestPolicy::AllShards),
+            _ => None,
+        }
+    }
+
+    /// Shard channels are hashed to slots like keys, but are not declared as keys
+    /// by the command key specs: `PUBSUB SHARDNUMSUB` is routed with its channels
+    fn shard_channels(command: &Command, command_name: &str) -> Option<SmallVec<[String; 10]>> {
+        if command_name != "PUBSUB|SHARDNUMSUB" {
+            return None;
+        }
+
+        Some(
+            command
+                .args
+                .into_iter()
+                .skip(1)
+                .map(|arg| String::from_utf8_lossy(arg).into_owned())
+                .collect(),
+        )
+    }
+
+    /// Response policy matching [`default_request_policy`](Self::default_request_policy)
+    fn default_response_policy(command_name: &str) -> Option<ResponsePolicy> {
+        match command_name {
+            "FLUSHALL" | "FLUSHDB" => Some(ResponsePolicy::AllSucceeded),
+            _ => None,
+        }
+    }
+
     /// The client should execute the command on all master shards (e.g., the DBSIZE command).
     /// This tip is in-use by commands that don't accept key name arguments.
     /// The command operates atomically per shard.
@@ -473,15 +549,66 @@ impl ClusterConnection {
 
             self.pending_requests.push_back(request_info);
         } else {
+            // only multi-shard commands (MGET, MSET, DEL, ...) can be split by slot,
+            // the results of other commands (SUNIONSTORE, ...) cannot be merged
             return Err(Error::Client(format!(
-                "[{}] Cannot send command {} with mismatched key slots",
-                self.tag, command_name
+                "[{}] Cannot send command {} with mismatched key slots: keys {:?} must map to the same slot (see hash tags)",
+                self.tag, command_name, keys
             )));
         }
 
         Ok(())
     }
 
+    /// Sends a command to the node selected by its explicit routing,
+    /// whether the command is known by the server specifications or not
+    async fn explicit_routing(
+        &mut self,
+        command: &Command,
+        routing: &CommandRouting,
+        ask_reasons: &[(u16, (String, u16))],
+    ) -> Result<()> {
+        let command_name = self
+            .command_info_manager
+            .get_command_info(command)
+            .map(|command_info| command_info.name.clone())
+            .unwrap_or_else(|| command.name.to_owned());
+
+        match routing {
+            CommandRouting::Key(key) => {
+                let keys: SmallVec<[String; 10]> = smallvec![key.clone()];
+                let slots = Self::hash_slots(&keys);
+                self.no_request_policy(command, command_name, keys, slots, ask_reasons)
+                    .await
+            }
+            CommandRouting::Node(node_id) => {
+                let Some(node) = self.nodes.iter_mut().find(|n| n.id.as_ref() == node_id) else {
+                    return Err(Error::Client(format!(
+                        "[{}] Unknown cluster node {node_id}",
+                        self.tag
+                    )));
+                };
+
+                node.connection.write(command).await?;
+
+                let request_info = RequestInfo {
+                    command_name,
+                    sub_requests: smallvec![SubRequest {
+                        node_id: node.id.clone(),
+                        keys: SmallVec::new(),
+                        result: None,
+                    }],
+                    keys: SmallVec::new(),
+                    #[cfg(debug_assertions)]
+                    command_seq: command.command_seq,
+                };
+
+                self.pending_requests.push_back(request_info);
+                Ok(())
+            }
+        }
+    }
+
     fn request_policy_special(
         &mut self,
         _command: &Command,
@@ -574,6 +701,14 @@ impl ClusterConnection {
                             hash_slot,
                             address: address.clone(),
                         }),
+                        Err(Error::Redis(RedisError {
+                            kind: RedisErrorKind::ClusterDown,
+                            description: _,
+                        })) => {
+                            // the topology may be changing: reload the slot map before the next write
+                            self.slot_ranges_stale = true;
+                            sub_results.push(result);
+                        }
                         _ => sub_results.push(result),
                     },
                     _ => sub_results.push(result),
@@ -591,27 +726,21 @@ impl ClusterConnection {
             return Some(Err(Error::Retry(retry_reasons)));
         }
 
+        // commands sent with an explicit routing may be unknown by the server specifications
         let command_name = &request_info.command_name;
-        let command_info = self
+        let response_policy = self
             .command_info_manager
-            .get_command_info_by_name(command_name);
-
-        let command_info = if let Some(command_info) = command_info {
-            command_info
-        } else {
-            return Some(Err(Error::Client(format!(
-                "[{}] Unknown command {}",
-                self.tag, command_name
-            ))));
-        };
-
-        let response_policy = command_info.command_tips.iter().find_map(|tip| {
-            if let CommandTip::ResponsePolicy(response_policy) = tip {
-                Some(response_policy)
-            } else {
-                None
-            }
-        });
+            .get_command_info_by_name(command_name)
+            .and_then(|command_info| {
+                command_info.command_tips.iter().find_map(|tip| {
+                    if let CommandTip::ResponsePolicy(response_policy) = tip {
+                        Some(response_policy.clone())
+                    } else {
+                        None
+                    }
+                })
+            })
+            .or_else(|| Self::default_response_policy(command_name));
 
         // The response_policy tip is set for commands that reply with scalar data types,
         // or when it's expected that clients implement a non-default aggregate.
@@ -874,6 +1003,8 @@ impl ClusterConnection {
 
         self.nodes = nodes;
         self.slot_ranges = slot_ranges;
+        self.slot_ranges_loaded_at = Instant::now();
+        self.slot_ranges_stale = false;
 
         Ok(())
 
@@ -1024,6 +1155,11 @@ impl ClusterConnection {
         Ok(())
     }
 
+    /// Reloads the slot map on demand
+    pub(crate) async fn refresh_slots(&mut self) -> Result<()> {
+        self.refresh_nodes_and_slot_ranges().await
+    }
+
     /// Keep existing connection, connect new nodes, remove obsolte ones
     /// Rebuild slot_ranges from scratch
     async fn refresh_nodes_and_slot_ranges(&mut self) -> Result<()> {
@@ -1103,6 +1239,8 @@ impl ClusterConnection {
 
         self.slot_ranges.sort_by_key(|s| s.slot_range.0);
         self.nodes.sort_by(|n1, n2| n1.id.cmp(&n2.id));
+        self.slot_ranges_loaded_at = Instant::now();
+        self.slot_ranges_stale = false;
 
         debug!(
             "[{}] Cluster new setup: nodes={:?}, slot_ranges={:?}",
@@ -77,6 +77,16 @@ impl Connection {
         }
     }
 
+    /// Reloads the slot map of a cluster connection
+    pub async fn refresh_cluster_slots(&mut self) -> Result<()> {
+        match self {
+            Connection::Cluster(connection) => connection.refresh_slots().await,
+            _ => Err(Error::Client(
+                "Slot map refresh is only supported in cluster mode".to_owned(),
+            )),
+        }
+    }
+
     #[inline]
     pub async fn send(&mut self, command: &Command) -> Result<RespBuf> {
         self.write(command).await?;
@@ -92,6 +102,17 @@ impl Connection {
             Connection::Cluster(connection) => connection.tag(),
         }
     }
+
+    /// Database selected at reconnection.
+    ///
+    /// A cluster only supports database 0.
+    pub(crate) fn set_database(&mut self, database: usize) {
+        match self {
+            Connection::Standalone(connection) => connection.set_database(database),
+            Connection::Sentinel(connection) => connection.set_database(database),
+            Connection::Cluster(_) => (),
+        }
+    }
 }
 
 impl<'a, R> IntoFuture for PreparedCommand<'a, &'a mut Connection, R>
@@ -3,8 +3,10 @@ mod cluster_connection;
 mod command_info_manager;
 mod connection;
 mod network_handler;
+mod pub_sub_channel;
 mod sentinel_connection;
 mod standalone_connection;
+mod transport_handler;
 mod util;
 mod version;
 
@@ -13,6 +15,8 @@ pub(crate) use cluster_connection::*;
 pub(crate) use command_info_manager::*;
 pub(crate) use connection::*;
 pub(crate) use network_handler::*;
+pub(crate) use pub_sub_channel::*;
 pub(crate) use sentinel_connection::*;
 pub(crate) use standalone_connection::*;
+pub(crate) use transport_handler::*;
 pub(crate) use version::*;
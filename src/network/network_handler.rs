@@ -1,15 +1,25 @@
-use super::util::RefPubSubMessage;
+use super::{util::RefPubSubMessage, PubSubSender};
+#[cfg(feature = "metrics")]
+use crate::client::Metrics;
 use crate::{
     client::{Commands, Config, Message},
     commands::InternalPubSubCommands,
-    resp::{cmd, Command, RespBuf},
-    spawn, Connection, Error, JoinHandle, Result, RetryReason,
+    resp::{cmd, Command, RespBuf, Value},
+    sleep, spawn, Connection, Error, JoinHandle, Result, RetryReason,
 };
 use futures_channel::{mpsc, oneshot};
-use futures_util::{select, FutureExt, SinkExt, StreamExt};
+use futures_util::{future::pending, select, FutureExt, SinkExt, StreamExt};
 use log::{debug, error, info, log_enabled, trace, warn, Level};
 use smallvec::SmallVec;
-use std::collections::{HashMap, VecDeque};
+use std::{
+    collections::{HashMap, VecDeque},
+    iter::zip,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 use tokio::sync::broadcast;
 
 pub(crate) type MsgSender = mpsc::UnboundedSender<Message>;
@@ -17,9 +27,8 @@ pub(crate) type MsgReceiver = mpsc::UnboundedReceiver<Message>;
 pub(crate) type ResultSender = oneshot::Sender<Result<RespBuf>>;
 pub(crate) type ResultReceiver = oneshot::Receiver<Result<RespBuf>>;
 pub(crate) type ResultsSender = oneshot::Sender<Result<Vec<RespBuf>>>;
+pub(crate) type RefreshSlotsSender = oneshot::Sender<Result<()>>;
 pub(crate) type ResultsReceiver = oneshot::Receiver<Result<Vec<RespBuf>>>;
-pub(crate) type PubSubSender = mpsc::UnboundedSender<Result<RespBuf>>;
-pub(crate) type PubSubReceiver = mpsc::UnboundedReceiver<Result<RespBuf>>;
 pub(crate) type PushSender = mpsc::UnboundedSender<Result<RespBuf>>;
 pub(crate) type PushReceiver = mpsc::UnboundedReceiver<Result<RespBuf>>;
 pub(crate) type ReconnectSender = broadcast::Sender<()>;
@@ -79,6 +88,8 @@ struct PendingSubscription {
     pub sender: PubSubSender,
     /// indicates if more subscriptions will arrive in the same batch
     pub more_to_come: bool,
+    /// the caller stopped waiting for the confirmation, which must not register the subscription
+    pub abandoned: bool,
 }
 
 pub(crate) struct NetworkHandler {
@@ -99,15 +110,38 @@ pub(crate) struct NetworkHandler {
     auto_resubscribe: bool,
     auto_remonitor: bool,
     max_command_attempts: usize,
+    pub_sub_keepalive: Option<Duration>,
+    keepalive_interval: Option<Duration>,
+    auto_pipeline: Option<Duration>,
+    /// when buffered commands must be written, with `auto_pipeline`
+    auto_pipeline_deadline: Option<Instant>,
+    /// database currently selected, shared with the clients of this connection
+    selected_database: Arc<AtomicUsize>,
+    /// a pinned connection is never reestablished: the network loop ends on disconnection
+    pinned: bool,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<dyn Metrics>>,
     tag: String,
 }
 
+/// Number of buffered commands which triggers a write before the `auto_pipeline` deadline
+const AUTO_PIPELINE_MAX_COMMANDS: usize = 1000;
+
 impl NetworkHandler {
-    pub async fn connect(config: Config) -> Result<(MsgSender, JoinHandle<()>, ReconnectSender)> {
+    pub async fn connect(
+        config: Config,
+        selected_database: Arc<AtomicUsize>,
+        pinned: bool,
+    ) -> Result<(MsgSender, JoinHandle<()>, ReconnectSender)> {
         // options
         let auto_resubscribe = config.auto_resubscribe;
         let auto_remonitor = config.auto_remonitor;
         let max_command_attempts = config.max_command_attempts;
+        let pub_sub_keepalive = config.pub_sub_keepalive;
+        let keepalive_interval = config.keepalive_interval;
+        let auto_pipeline = config.auto_pipeline;
+        #[cfg(feature = "metrics")]
+        let metrics = config.metrics.clone();
 
         let connection = Connection::connect(config).await?;
         let (msg_sender, msg_receiver): (MsgSender, MsgReceiver) = mpsc::unbounded();
@@ -131,6 +165,14 @@ impl NetworkHandler {
             auto_resubscribe,
             auto_remonitor,
             max_command_attempts,
+            pub_sub_keepalive,
+            keepalive_interval,
+            auto_pipeline,
+            auto_pipeline_deadline: None,
+            selected_database,
+            pinned,
+            #[cfg(feature = "metrics")]
+            metrics,
             tag,
         };
 
@@ -145,20 +187,101 @@ impl NetworkHandler {
 
     async fn network_loop(&mut self) -> Result<()> {
         loop {
+            let idle = Self::wait_for_idle(self.keepalive_duration());
+            let auto_pipeline_deadline = Self::wait_for(self.auto_pipeline_deadline);
+
             select! {
                 msg = self.msg_receiver.next().fuse() => {
                     if !self.handle_message(msg).await { break; }
                 } ,
                 value = self.connection.read().fuse() => {
                     self.handle_result(value).await;
+                },
+                _ = idle.fuse() => {
+                    self.send_keepalive();
+                },
+                _ = auto_pipeline_deadline.fuse() => {
+                    if let Status::Disconnected = self.status {
+                        self.auto_pipeline_deadline = None;
+                    } else {
+                        self.send_messages().await;
+                    }
                 }
             }
+
+            if self.pinned && matches!(self.status, Status::Disconnected) {
+                break;
+            }
         }
 
         debug!("[{}] end of network loop", self.tag);
         Ok(())
     }
 
+    /// Idle duration after which a `PING` must be sent:
+    /// `pub_sub_keepalive` for a subscribed connection,
+    /// `keepalive_interval` for a regular connection without any pending reply
+    fn keepalive_duration(&self) -> Option<Duration> {
+        match self.status {
+            Status::Subscribed => self.pub_sub_keepalive,
+            Status::Connected
+                if self.is_reply_on
+                    && self.messages_to_send.is_empty()
+                    && self.messages_to_receive.is_empty() =>
+            {
+                self.keepalive_interval
+            }
+            _ => None,
+        }
+    }
+
+    /// Resolves when the connection has been idle for the given duration,
+    /// never without duration
+    async fn wait_for_idle(duration: Option<Duration>) {
+        match duration {
+            Some(duration) => sleep(duration).await,
+            None => pending().await,
+        }
+    }
+
+    /// Resolves at the given deadline, never without deadline
+    async fn wait_for(deadline: Option<Instant>) {
+        match deadline {
+            Some(deadline) => sleep(deadline.saturating_duration_since(Instant::now())).await,
+            None => pending().await,
+        }
+    }
+
+    /// With `auto_pipeline`, buffered commands are written at the deadline,
+    /// unless there are enough of them to be written right away
+    fn delay_send_messages(&mut self) -> bool {
+        let (Some(auto_pipeline), Status::Connected) = (self.auto_pipeline, self.status) else {
+            return false;
+        };
+
+        let num_commands = self
+            .messages_to_send
+            .iter()
+            .fold(0, |sum, msg| sum + msg.message.commands.len());
+        if num_commands >= AUTO_PIPELINE_MAX_COMMANDS {
+            return false;
+        }
+
+        if self.auto_pipeline_deadline.is_none() {
+            self.auto_pipeline_deadline = Some(Instant::now() + auto_pipeline);
+        }
+
+        true
+    }
+
+    fn send_keepalive(&mut self) {
+        debug!("[{}] Sending keepalive", self.tag);
+        let message = Message::single_forget(cmd("PING"), false);
+        if let Err(e) = self.msg_sender.unbounded_send(message) {
+            error!("[{}] Cannot send keepalive: {e}", self.tag);
+        }
+    }
+
     async fn handle_message(&mut self, mut msg: Option<Message>) -> bool {
         let is_channel_closed: bool;
 
@@ -169,25 +292,31 @@ impl NetworkHandler {
                 if let Some(pub_sub_senders) = pub_sub_senders {
                     let subscription_type = match &msg.commands {
                         Commands::Single(command, _) => match command.name {
-                            "SUBSCRIBE" => SubscriptionType::Channel,
-                            "PSUBSCRIBE" => SubscriptionType::Pattern,
-                            "SSUBSCRIBE" => SubscriptionType::ShardChannel,
+                            "SUBSCRIBE" => Some(SubscriptionType::Channel),
+                            "PSUBSCRIBE" => Some(SubscriptionType::Pattern),
+                            "SSUBSCRIBE" => Some(SubscriptionType::ShardChannel),
+                            "UNSUBSCRIBE" | "PUNSUBSCRIBE" | "SUNSUBSCRIBE" => None,
                             _ => unreachable!(),
                         },
                         _ => unreachable!(),
                     };
 
-                    let num_pending_subscriptions = pub_sub_senders.len();
-                    let pending_subscriptions = pub_sub_senders.into_iter().enumerate().map(
-                        |(index, (channel_or_pattern, sender))| PendingSubscription {
-                            channel_or_pattern,
-                            subscription_type,
-                            sender,
-                            more_to_come: index < num_pending_subscriptions - 1,
-                        },
-                    );
+                    if let Some(subscription_type) = subscription_type {
+                        let num_pending_subscriptions = pub_sub_senders.len();
+                        let pending_subscriptions = pub_sub_senders.into_iter().enumerate().map(
+                            |(index, (channel_or_pattern, sender))| PendingSubscription {
+                                channel_or_pattern,
+                                subscription_type,
+                                sender,
+                                more_to_come: index < num_pending_subscriptions - 1,
+                                abandoned: false,
+                            },
+                        );
 
-                    self.pending_subscriptions.extend(pending_subscriptions);
+                        self.pending_subscriptions.extend(pending_subscriptions);
+                    } else {
+                        self.abandon_subscriptions(&mut msg, pub_sub_senders);
+                    }
                 }
 
                 let push_sender = msg.push_sender.take();
@@ -196,6 +325,13 @@ impl NetworkHandler {
                     self.push_sender = Some(push_sender);
                 }
 
+                let refresh_slots_sender = msg.refresh_slots_sender.take();
+                if let Some(refresh_slots_sender) = refresh_slots_sender {
+                    debug!("[{}] Refreshing cluster slots on demand", self.tag);
+                    let result = self.connection.refresh_cluster_slots().await;
+                    let _result = refresh_slots_sender.send(result);
+                }
+
                 match &self.status {
                     Status::Connected => {
                         for command in &msg.commands {
@@ -211,10 +347,7 @@ impl NetworkHandler {
                         }
                         self.messages_to_send.push_back(MessageToSend::new(msg));
                     }
-                    Status::Subscribing => {
-                        self.messages_to_send.push_back(MessageToSend::new(msg));
-                    }
-                    Status::Subscribed => {
+                    Status::Subscribing | Status::Subscribed => {
                         for command in &msg.commands {
                             if let "UNSUBSCRIBE" | "PUNSUBSCRIBE" | "SUNSUBSCRIBE" = command.name {
                                 let subscription_type = match command.name {
@@ -272,7 +405,7 @@ impl NetworkHandler {
         }
 
         if let Status::Disconnected = self.status {
-        } else {
+        } else if is_channel_closed || !self.delay_send_messages() {
             self.send_messages().await
         }
 
@@ -280,6 +413,8 @@ impl NetworkHandler {
     }
 
     async fn send_messages(&mut self) {
+        self.auto_pipeline_deadline = None;
+
         if log_enabled!(Level::Debug) {
             let num_commands = self
                 .messages_to_send
@@ -395,7 +530,12 @@ impl NetworkHandler {
                     }
                 },
                 Status::Subscribing => {
-                    if result.is_ok() {
+                    let is_rejected = self.is_subscription_rejected(&result);
+                    if is_rejected {
+                        self.discard_pending_subscriptions();
+                    }
+
+                    if result.is_ok() && !is_rejected {
                         self.status = Status::Subscribed;
                     } else {
                         self.status = Status::Connected;
@@ -405,11 +545,24 @@ impl NetworkHandler {
                         self.receive_result(resp_buf);
                     }
                 }
-                Status::Subscribed => {
-                    if let Some(resp_buf) = self.try_match_pubsub_message(result).await {
-                        self.receive_result(resp_buf);
+                Status::Subscribed => match result {
+                    // protocol error which does not answer any pending command
+                    Err(e)
+                        if self.messages_to_receive.is_empty()
+                            && !matches!(e, Error::Redis(_) | Error::Retry(_)) =>
+                    {
+                        self.recover_pub_sub(e).await;
                     }
-                }
+                    result => {
+                        if self.is_subscription_rejected(&result) {
+                            self.discard_pending_subscriptions();
+                        }
+
+                        if let Some(resp_buf) = self.try_match_pubsub_message(result).await {
+                            self.receive_result(resp_buf);
+                        }
+                    }
+                },
                 Status::EnteringMonitor => {
                     self.receive_result(result);
                     self.status = Status::Monitor;
@@ -439,7 +592,10 @@ impl NetworkHandler {
                 },
             },
             // disconnection
-            None => self.reconnect().await,
+            None => {
+                self.complete_shutdown_message();
+                self.reconnect().await;
+            }
         }
     }
 
@@ -479,25 +635,25 @@ impl NetworkHandler {
                                 self.tag,
                                 message_to_receive.message
                             );
+                            if let (Commands::Single(command, _), Ok(resp_buf)) =
+                                (&message_to_receive.message.commands, &result)
+                            {
+                                self.track_selected_database(command, resp_buf);
+                            }
                             match message_to_receive.message.commands {
                                 Commands::Single(_, Some(result_sender)) => {
                                     if let Err(e) = result_sender.send(result) {
                                         warn!("[{}] Cannot send value to caller because receiver is not there anymore: {e:?}", self.tag);
                                     }
                                 }
-                                Commands::Batch(_, results_sender) => match result {
+                                Commands::Batch(commands, results_sender) => match result {
                                     Ok(resp_buf) => {
-                                        let pending_replies = self.pending_replies.take();
-
-                                        if let Some(mut pending_replies) = pending_replies {
-                                            pending_replies.push(resp_buf);
-                                            if let Err(e) = results_sender.send(Ok(pending_replies))
-                                            {
-                                                warn!("[{}] Cannot send value to caller because receiver is not there anymore: {e:?}", self.tag);
-                                            }
-                                        } else if let Err(e) =
-                                            results_sender.send(Ok(vec![resp_buf]))
-                                        {
+                                        let mut replies =
+                                            self.pending_replies.take().unwrap_or_default();
+                                        replies.push(resp_buf);
+                                        self.track_batch_selected_database(&commands, &replies);
+
+                                        if let Err(e) = results_sender.send(Ok(replies)) {
                                             warn!("[{}] Cannot send value to caller because receiver is not there anymore: {e:?}", self.tag);
                                         }
                                     }
@@ -551,6 +707,18 @@ impl NetworkHandler {
         }
     }
 
+    /// Forwards a subscription confirmation to a stream which asked for
+    /// [`PubSubEvent`](crate::client::PubSubEvent)s
+    async fn send_subscription_event(
+        tag: &str,
+        pub_sub_sender: &PubSubSender,
+        value: &Result<RespBuf>,
+    ) {
+        if let Err(e) = pub_sub_sender.send(value.clone()).await {
+            warn!("[{tag}] Cannot send pub/sub event to caller: {e}");
+        }
+    }
+
     async fn try_match_pubsub_message(
         &mut self,
         value: Result<RespBuf>,
@@ -562,11 +730,22 @@ impl NetworkHandler {
                     | RefPubSubMessage::SMessage(channel_or_pattern, _) => {
                         match self.subscriptions.get_mut(channel_or_pattern) {
                             Some((_subscription_type, pub_sub_sender)) => {
+                                // the message is moved to the stream: copy its channel
+                                // only when it is reported to the metrics
+                                #[cfg(feature = "metrics")]
+                                let channel =
+                                    self.metrics.is_some().then(|| channel_or_pattern.to_vec());
                                 if let Err(e) = pub_sub_sender.send(value).await {
                                     warn!(
                                         "[{}] Cannot send pub/sub message to caller: {e}",
                                         self.tag
                                     );
+                                } else {
+                                    #[cfg(feature = "metrics")]
+                                    if let (Some(metrics), Some(channel)) = (&self.metrics, channel)
+                                    {
+                                        metrics.on_pub_sub_message(&channel);
+                                    }
                                 }
                             }
                             None => {
@@ -584,6 +763,26 @@ impl NetworkHandler {
                     | RefPubSubMessage::SSubscribe(channel_or_pattern) => {
                         if let Some(pending_sub) = self.pending_subscriptions.pop_front() {
                             if pending_sub.channel_or_pattern == channel_or_pattern {
+                                if pending_sub.abandoned {
+                                    debug!(
+                                        "[{}] Ignoring confirmation of abandoned subscription '{:?}'",
+                                        self.tag,
+                                        String::from_utf8_lossy(channel_or_pattern)
+                                    );
+                                    if pending_sub.more_to_come {
+                                        return None;
+                                    }
+                                    return Some(Ok(RespBuf::ok()));
+                                }
+
+                                if pending_sub.sender.events_enabled() {
+                                    Self::send_subscription_event(
+                                        &self.tag,
+                                        &pending_sub.sender,
+                                        &value,
+                                    )
+                                    .await;
+                                }
                                 self.subscriptions
                                     .insert(channel_or_pattern.to_vec(), (pending_sub.subscription_type, pending_sub.sender));
 
@@ -596,6 +795,16 @@ impl NetworkHandler {
                                     self.tag,
                                     String::from_utf8_lossy(channel_or_pattern)
                                 );
+
+                                // the whole subscription is reported as failed to the caller
+                                if pending_sub.more_to_come {
+                                    self.discard_pending_subscriptions();
+                                }
+                                return Some(Err(Error::Client(format!(
+                                    "Unexpected subscription confirmation on channel '{}' instead of '{}'",
+                                    String::from_utf8_lossy(channel_or_pattern),
+                                    String::from_utf8_lossy(&pending_sub.channel_or_pattern)
+                                ))));
                             }
                         } else {
                             error!(
@@ -603,13 +812,31 @@ impl NetworkHandler {
                                 self.tag,
                                 String::from_utf8_lossy(channel_or_pattern)
                             );
+                            // no caller is waiting for this confirmation
+                            return None;
                         }
                         Some(Ok(RespBuf::ok()))
                     }
+                    RefPubSubMessage::SUnsubscribe(shardchannel)
+                        if !self.is_pending_unsubscription(shardchannel) =>
+                    {
+                        // unsolicited sunsubscribe: the slot of the shard channel
+                        // has been migrated to another node
+                        self.resubscribe_moved_shardchannel(shardchannel.to_vec())
+                            .await;
+                        None
+                    }
                     RefPubSubMessage::Unsubscribe(channel_or_pattern)
                     | RefPubSubMessage::PUnsubscribe(channel_or_pattern)
                     | RefPubSubMessage::SUnsubscribe(channel_or_pattern) => {
-                        self.subscriptions.remove(channel_or_pattern);
+                        if let Some((_subscription_type, pub_sub_sender)) =
+                            self.subscriptions.remove(channel_or_pattern)
+                        {
+                            if pub_sub_sender.events_enabled() {
+                                Self::send_subscription_event(&self.tag, &pub_sub_sender, &value)
+                                    .await;
+                            }
+                        }
                         if let Some(remaining) = self.pending_unsubscriptions.front_mut() {
                             if remaining.len() > 1 {
                                 if remaining.remove(channel_or_pattern).is_none() {
@@ -647,11 +874,21 @@ impl NetworkHandler {
                     RefPubSubMessage::PMessage(pattern, channel, _) => {
                         match self.subscriptions.get_mut(pattern) {
                             Some((_subscription_type, pub_sub_sender)) => {
+                                // the message is moved to the stream: copy its channel
+                                // only when it is reported to the metrics
+                                #[cfg(feature = "metrics")]
+                                let channel = self.metrics.is_some().then(|| channel.to_vec());
                                 if let Err(e) = pub_sub_sender.send(value).await {
                                     warn!(
                                         "[{}] Cannot send pub/sub message to caller: {e}",
                                         self.tag
                                     );
+                                } else {
+                                    #[cfg(feature = "metrics")]
+                                    if let (Some(metrics), Some(channel)) = (&self.metrics, channel)
+                                    {
+                                        metrics.on_pub_sub_message(&channel);
+                                    }
                                 }
                             }
                             None => {
@@ -690,7 +927,8 @@ impl NetworkHandler {
         }
 
         while let Some(message_to_receive) = self.messages_to_receive.front() {
-            if !message_to_receive.message.retry_on_error
+            if self.pinned
+                || !message_to_receive.message.retry_on_error
                 || message_to_receive.attempts >= self.max_command_attempts
             {
                 debug!(
@@ -738,7 +976,8 @@ impl NetworkHandler {
         }
 
         while let Some(message_to_send) = self.messages_to_send.front() {
-            if !message_to_send.message.retry_on_error
+            if self.pinned
+                || !message_to_send.message.retry_on_error
                 || message_to_send.attempts >= self.max_command_attempts
             {
                 debug!(
@@ -775,6 +1014,11 @@ impl NetworkHandler {
             }
         }
 
+        if self.pinned {
+            debug!("[{}] pinned connection lost, not reconnecting", self.tag);
+            return;
+        }
+
         if let Err(e) = self.connection.reconnect().await {
             error!("[{}] Failed to reconnect: {e:?}", self.tag);
             return;
@@ -794,6 +1038,11 @@ impl NetworkHandler {
             }
         }
 
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            metrics.on_reconnect();
+        }
+
         if let Err(e) = self.reconnect_sender.send(()) {
             debug!(
                 "[{}] Cannot send reconnect notification to clients: {e}",
@@ -823,6 +1072,262 @@ impl NetworkHandler {
         info!("[{}] reconnected!", self.tag);
     }
 
+    /// A successful `SHUTDOWN` closes the connection without any reply:
+    /// when the server closes the connection while the next expected reply is the one
+    /// of a sent `SHUTDOWN`, this command is answered with `OK` instead of being failed or retried.
+    ///
+    /// `SHUTDOWN ABORT` is excluded because it is always answered by the server.
+    fn complete_shutdown_message(&mut self) {
+        let is_shutdown = matches!(
+            self.messages_to_receive.front().map(|m| &m.message.commands),
+            Some(Commands::Single(command, _)) if Self::is_shutdown(command)
+        );
+        if !is_shutdown {
+            return;
+        }
+
+        if let Some(message_to_receive) = self.messages_to_receive.pop_front() {
+            if let Commands::Single(command, result_sender) = message_to_receive.message.commands {
+                debug!("[{}] {command:?} completed by disconnection", self.tag);
+                if let Some(result_sender) = result_sender {
+                    if let Err(e) = result_sender.send(Ok(RespBuf::ok())) {
+                        warn!("[{}] Cannot send value to caller because receiver is not there anymore: {e:?}", self.tag);
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_shutdown(command: &Command) -> bool {
+        command.name == "SHUTDOWN" && !command.args.into_iter().any(|arg| arg == b"ABORT")
+    }
+
+    /// Keep the database selected with a successful `SELECT`, or reset to 0 by `RESET`,
+    /// so that it is selected again after a reconnection
+    fn track_selected_database(&mut self, command: &Command, reply: &RespBuf) {
+        if !reply.is_error() {
+            self.track_successful_command(command);
+        }
+    }
+
+    fn track_successful_command(&mut self, command: &Command) {
+        let database = match command.name {
+            "SELECT" => command
+                .args
+                .first()
+                .and_then(|arg| std::str::from_utf8(arg).ok())
+                .and_then(|arg| arg.parse::<usize>().ok()),
+            "RESET" => Some(0),
+            _ => None,
+        };
+
+        if let Some(database) = database {
+            debug!("[{}] database {database} selected", self.tag);
+            self.connection.set_database(database);
+            self.selected_database.store(database, Ordering::Relaxed);
+        }
+    }
+
+    /// Track the database selected by the commands of a pipeline.
+    /// The commands queued in a transaction are tracked with their reply to `EXEC`.
+    fn track_batch_selected_database(&mut self, commands: &[Command], replies: &[RespBuf]) {
+        let mut queued_commands: Option<Vec<&Command>> = None;
+
+        for (command, reply) in zip(commands, replies) {
+            match (command.name, &mut queued_commands) {
+                ("MULTI", _) => queued_commands = Some(Vec::new()),
+                ("DISCARD", _) => queued_commands = None,
+                ("EXEC", Some(_)) => {
+                    let queued_commands = queued_commands.take().unwrap_or_default();
+                    if !queued_commands
+                        .iter()
+                        .any(|command| matches!(command.name, "SELECT" | "RESET"))
+                    {
+                        continue;
+                    }
+                    if let Ok(Some(exec_replies)) = reply.to::<Option<Vec<Value>>>() {
+                        for (command, reply) in zip(queued_commands, &exec_replies) {
+                            if !matches!(reply, Value::Error(_)) {
+                                self.track_successful_command(command);
+                            }
+                        }
+                    }
+                }
+                (_, Some(queued_commands)) => queued_commands.push(command),
+                (_, None) => self.track_selected_database(command, reply),
+            }
+        }
+    }
+
+    /// Recover a subscribed connection after a protocol error.
+    ///
+    /// The error is forwarded once to each pub/sub stream, then the connection is reestablished
+    /// and, with `auto_resubscribe`, subscriptions are restored so that the streams resume.
+    /// A `RESET` would not help here: once the reply stream cannot be decoded anymore,
+    /// the connection cannot be resynchronized.
+    /// If subscriptions cannot be restored, they are dropped, which ends the streams.
+    async fn recover_pub_sub(&mut self, error: Error) {
+        warn!(
+            "[{}] Error on subscribed connection, recovering: {error}",
+            self.tag
+        );
+
+        let mut notified_senders: Vec<&PubSubSender> = Vec::new();
+        for (_subscription_type, pub_sub_sender) in self.subscriptions.values() {
+            if notified_senders
+                .iter()
+                .any(|sender| sender.same_channel(pub_sub_sender))
+            {
+                continue;
+            }
+
+            if let Err(e) = pub_sub_sender.send(Err(error.clone())).await {
+                warn!("[{}] Cannot send pub/sub error to caller: {e}", self.tag);
+            }
+            notified_senders.push(pub_sub_sender);
+        }
+
+        self.reconnect().await;
+
+        if !self.auto_resubscribe || matches!(self.status, Status::Disconnected) {
+            self.subscriptions.clear();
+            self.pending_subscriptions.clear();
+            self.pending_unsubscriptions.clear();
+        }
+    }
+
+    /// Indicates if `result` is an error reply from the server
+    /// to the subscribe command currently waiting for its confirmations
+    fn is_subscription_rejected(&self, result: &Result<RespBuf>) -> bool {
+        let is_error = match result {
+            Ok(resp_buf) => resp_buf.is_error(),
+            Err(Error::Redis(_)) => true,
+            Err(_) => false,
+        };
+
+        is_error
+            && self
+                .messages_to_receive
+                .front()
+                .is_some_and(|message_to_receive| {
+                    message_to_receive.message.retry_reasons.is_none()
+                        && matches!(
+                            &message_to_receive.message.commands,
+                            Commands::Single(command, _)
+                                if matches!(command.name, "SUBSCRIBE" | "PSUBSCRIBE" | "SSUBSCRIBE")
+                        )
+                })
+    }
+
+    /// Removes the pending subscriptions of the subscribe command currently waiting for its confirmations,
+    /// so that they are not matched with the confirmations of the next subscribe commands
+    fn discard_pending_subscriptions(&mut self) {
+        while let Some(pending_sub) = self.pending_subscriptions.pop_front() {
+            if !pending_sub.more_to_come {
+                break;
+            }
+        }
+    }
+
+    /// Handles the unsubscribe command sent by a caller which stopped waiting for the confirmation
+    /// of its subscriptions:
+    /// pending subscriptions are marked so that a late confirmation does not register them,
+    /// and the command only unsubscribes from the channels or patterns of this caller
+    /// which are still pending or already confirmed
+    fn abandon_subscriptions(
+        &mut self,
+        msg: &mut Message,
+        pub_sub_senders: Vec<(Vec<u8>, PubSubSender)>,
+    ) {
+        let Commands::Single(command, _) = &msg.commands else {
+            unreachable!();
+        };
+
+        let mut channels_or_patterns = Vec::new();
+        for (channel_or_pattern, sender) in pub_sub_senders {
+            let pending_sub = self.pending_subscriptions.iter_mut().find(|pending_sub| {
+                !pending_sub.abandoned
+                    && pending_sub.channel_or_pattern == channel_or_pattern
+                    && pending_sub.sender.same_channel(&sender)
+            });
+
+            if let Some(pending_sub) = pending_sub {
+                pending_sub.abandoned = true;
+                channels_or_patterns.push(channel_or_pattern);
+            } else if self
+                .subscriptions
+                .get(&channel_or_pattern)
+                .is_some_and(|(_, subscribed_sender)| subscribed_sender.same_channel(&sender))
+            {
+                // confirmed after the caller stopped waiting
+                channels_or_patterns.push(channel_or_pattern);
+            }
+        }
+
+        debug!(
+            "[{}] Abandoning subscriptions: {:?}",
+            self.tag,
+            channels_or_patterns
+                .iter()
+                .map(|c| String::from_utf8_lossy(c))
+                .collect::<Vec<_>>()
+        );
+
+        msg.commands = if channels_or_patterns.is_empty() {
+            Commands::None
+        } else {
+            Commands::Single(cmd(command.name).arg(channels_or_patterns), None)
+        };
+    }
+
+    fn is_pending_unsubscription(&self, channel_or_pattern: &[u8]) -> bool {
+        self.pending_unsubscriptions
+            .iter()
+            .any(|unsubscriptions| unsubscriptions.contains_key(channel_or_pattern))
+    }
+
+    /// Subscribe again to a shard channel which has been unsubscribed by the server
+    /// after a slot migration, on the node now owning the slot.
+    ///
+    /// The subscription is routed with the current slot map:
+    /// if it is stale, the `MOVED` error triggers a refresh and a retry on the right node.
+    /// Without `auto_resubscribe`, the pub/sub stream receives an error instead.
+    async fn resubscribe_moved_shardchannel(&mut self, shardchannel: Vec<u8>) {
+        let Some((_subscription_type, pub_sub_sender)) = self.subscriptions.remove(&shardchannel)
+        else {
+            return;
+        };
+
+        if !self.auto_resubscribe {
+            let shardchannel = String::from_utf8_lossy(&shardchannel);
+            info!(
+                "[{}] Shard channel '{shardchannel}' has been unsubscribed by the server",
+                self.tag
+            );
+            let error = Error::Client(format!(
+                "Shard channel '{shardchannel}' has been unsubscribed by the server after a slot migration"
+            ));
+            if let Err(e) = pub_sub_sender.send(Err(error)).await {
+                warn!("[{}] Cannot send pub/sub error to caller: {e}", self.tag);
+            }
+            return;
+        }
+
+        info!(
+            "[{}] Shard channel '{}' has moved, resubscribing",
+            self.tag,
+            String::from_utf8_lossy(&shardchannel)
+        );
+
+        let mut message =
+            Message::single_forget(cmd("SSUBSCRIBE").arg(shardchannel.clone()), true);
+        message.pub_sub_senders = Some(vec![(shardchannel, pub_sub_sender)]);
+
+        if let Err(e) = self.msg_sender.unbounded_send(message) {
+            error!("[{}] Cannot resubscribe to shard channel: {e}", self.tag);
+        }
+    }
+
     async fn auto_resubscribe(&mut self) -> Result<()> {
         if !self.subscriptions.is_empty() {
             for (channel_or_pattern, (subscription_type, _)) in &self.subscriptions {
@@ -849,6 +1354,10 @@ impl NetworkHandler {
         if !self.pending_subscriptions.is_empty() {
             for pending_sub in self.pending_subscriptions.drain(..)
             {
+                if pending_sub.abandoned {
+                    continue;
+                }
+
                 match pending_sub.subscription_type {
                     SubscriptionType::Channel => {
                         self.connection
@@ -0,0 +1,226 @@
+use crate::{client::PubSubOverflowPolicy, resp::RespBuf, Error, Result};
+use futures_util::{future::poll_fn, Stream};
+use std::{
+    collections::VecDeque,
+    fmt,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+};
+
+/// Creates a bounded channel used to forward pub/sub messages
+/// from the network handler to a [`PubSubStream`](crate::client::PubSubStream).
+///
+/// When the buffer is full, `overflow_policy` decides if the sender waits,
+/// or if the oldest or newest message is dropped.
+pub(crate) fn pub_sub_channel(
+    buffer_size: usize,
+    overflow_policy: PubSubOverflowPolicy,
+) -> (PubSubSender, PubSubReceiver) {
+    let shared = Arc::new(Shared {
+        buffer_size: buffer_size.max(1),
+        overflow_policy,
+        dropped_count: AtomicU64::new(0),
+        events: AtomicBool::new(false),
+        state: Mutex::new(State {
+            buffer: VecDeque::new(),
+            receiver_waker: None,
+            sender_wakers: Vec::new(),
+            num_senders: 1,
+            receiver_alive: true,
+        }),
+    });
+
+    (
+        PubSubSender {
+            shared: shared.clone(),
+        },
+        PubSubReceiver { shared },
+    )
+}
+
+struct Shared {
+    buffer_size: usize,
+    overflow_policy: PubSubOverflowPolicy,
+    dropped_count: AtomicU64,
+    events: AtomicBool,
+    state: Mutex<State>,
+}
+
+struct State {
+    buffer: VecDeque<Result<RespBuf>>,
+    receiver_waker: Option<Waker>,
+    sender_wakers: Vec<Waker>,
+    num_senders: usize,
+    receiver_alive: bool,
+}
+
+pub(crate) struct PubSubSender {
+    shared: Arc<Shared>,
+}
+
+impl PubSubSender {
+    /// Pushes a message in the buffer, applying the overflow policy if the buffer is full
+    pub async fn send(&self, value: Result<RespBuf>) -> Result<()> {
+        let mut value = Some(value);
+        poll_fn(|cx| self.poll_send(cx, &mut value)).await
+    }
+
+    fn poll_send(
+        &self,
+        cx: &mut Context<'_>,
+        value: &mut Option<Result<RespBuf>>,
+    ) -> Poll<Result<()>> {
+        let mut state = self.shared.state.lock().unwrap();
+
+        if !state.receiver_alive {
+            return Poll::Ready(Err(Error::Client(
+                "pub/sub stream has been dropped".to_owned(),
+            )));
+        }
+
+        if state.buffer.len() >= self.shared.buffer_size {
+            match self.shared.overflow_policy {
+                PubSubOverflowPolicy::Block => {
+                    state.sender_wakers.push(cx.waker().clone());
+                    return Poll::Pending;
+                }
+                // errors are never dropped: they may be the last item the stream receives
+                PubSubOverflowPolicy::DropOldest => {
+                    match state.buffer.iter().position(|item| item.is_ok()) {
+                        Some(index) => {
+                            state.buffer.remove(index);
+                            self.shared.dropped_count.fetch_add(1, Ordering::Relaxed);
+                        }
+                        None if matches!(value, Some(Ok(_))) => {
+                            value.take();
+                            self.shared.dropped_count.fetch_add(1, Ordering::Relaxed);
+                            return Poll::Ready(Ok(()));
+                        }
+                        None => (),
+                    }
+                }
+                PubSubOverflowPolicy::DropNewest if matches!(value, Some(Ok(_))) => {
+                    value.take();
+                    self.shared.dropped_count.fetch_add(1, Ordering::Relaxed);
+                    return Poll::Ready(Ok(()));
+                }
+                PubSubOverflowPolicy::DropNewest => (),
+            }
+        }
+
+        if let Some(value) = value.take() {
+            state.buffer.push_back(value);
+        }
+
+        if let Some(waker) = state.receiver_waker.take() {
+            waker.wake();
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    /// Returns `true` if the receiver also wants subscription confirmations
+    pub fn events_enabled(&self) -> bool {
+        self.shared.events.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if both senders feed the same receiver
+    pub fn same_channel(&self, other: &PubSubSender) -> bool {
+        Arc::ptr_eq(&self.shared, &other.shared)
+    }
+}
+
+impl fmt::Debug for PubSubSender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PubSubSender")
+            .field("buffer_size", &self.shared.buffer_size)
+            .field("overflow_policy", &self.shared.overflow_policy)
+            .finish()
+    }
+}
+
+impl Clone for PubSubSender {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().unwrap().num_senders += 1;
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl Drop for PubSubSender {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.num_senders -= 1;
+        if state.num_senders == 0 {
+            if let Some(waker) = state.receiver_waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+pub(crate) struct PubSubReceiver {
+    shared: Arc<Shared>,
+}
+
+impl PubSubReceiver {
+    /// Number of messages dropped because the buffer was full
+    pub fn dropped_count(&self) -> u64 {
+        self.shared.dropped_count.load(Ordering::Relaxed)
+    }
+
+    /// Asks the network handler to also forward subscription confirmations
+    pub fn set_events_enabled(&self, enabled: bool) {
+        self.shared.events.store(enabled, Ordering::Relaxed);
+    }
+}
+
+impl PubSubReceiver {
+    /// Pops the next buffered message, if any, without waiting
+    pub fn try_recv(&self) -> Option<Result<RespBuf>> {
+        let mut state = self.shared.state.lock().unwrap();
+        let value = state.buffer.pop_front();
+        if value.is_some() {
+            for waker in state.sender_wakers.drain(..) {
+                waker.wake();
+            }
+        }
+        value
+    }
+}
+
+impl Stream for PubSubReceiver {
+    type Item = Result<RespBuf>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.shared.state.lock().unwrap();
+
+        if let Some(value) = state.buffer.pop_front() {
+            for waker in state.sender_wakers.drain(..) {
+                waker.wake();
+            }
+            Poll::Ready(Some(value))
+        } else if state.num_senders == 0 {
+            Poll::Ready(None)
+        } else {
+            state.receiver_waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for PubSubReceiver {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.receiver_alive = false;
+        state.buffer.clear();
+        for waker in state.sender_wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
@@ -149,4 +149,10 @@ impl SentinelConnection {
     pub(crate) fn tag(&self) -> &str {
         self.inner_connection.tag()
     }
+
+    /// Database selected at reconnection
+    pub(crate) fn set_database(&mut self, database: usize) {
+        self.config.database = database;
+        self.inner_connection.set_database(database);
+    }
 }
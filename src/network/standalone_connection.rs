@@ -1,20 +1,20 @@
 use crate::{
-    client::{Config, PreparedCommand},
+    client::{Config, Connector, ConnectorStream, PreparedCommand},
     commands::{
         ClusterCommands, ConnectionCommands, HelloOptions, SentinelCommands, ServerCommands,
     },
     resp::{BufferDecoder, Command, CommandEncoder, RespBuf},
-    tcp_connect, Error, Future, Result, RetryReason, TcpStreamReader, TcpStreamWriter,
+    tcp_connect, timeout, Error, Future, Result, RetryReason, TcpStreamReader, TcpStreamWriter,
 };
 #[cfg(feature = "tls")]
 use crate::{tcp_tls_connect, TcpTlsStreamReader, TcpTlsStreamWriter};
 use bytes::BytesMut;
 use futures_util::{SinkExt, StreamExt};
-use log::{debug, log_enabled, Level};
+use log::{debug, info, log_enabled, Level};
 use serde::de::DeserializeOwned;
 use smallvec::SmallVec;
 use std::future::IntoFuture;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncWriteExt, ReadHalf, WriteHalf};
 use tokio_util::codec::{Encoder, FramedRead, FramedWrite};
 
 pub(crate) enum Streams {
@@ -27,16 +27,24 @@ pub(crate) enum Streams {
         FramedRead<TcpTlsStreamReader, BufferDecoder>,
         FramedWrite<TcpTlsStreamWriter, CommandEncoder>,
     ),
+    Custom(
+        FramedRead<ReadHalf<Box<dyn ConnectorStream>>, BufferDecoder>,
+        FramedWrite<WriteHalf<Box<dyn ConnectorStream>>, CommandEncoder>,
+    ),
 }
 
 impl Streams {
     pub async fn connect(host: &str, port: u16, config: &Config) -> Result<Self> {
+        if let Some(connector) = &config.connector {
+            return Self::connect_custom(host, port, config, connector.as_ref()).await;
+        }
+
         #[cfg(feature = "tls")]
         if let Some(tls_config) = &config.tls_config {
-            let (reader, writer) =
-                tcp_tls_connect(host, port, tls_config, config.connect_timeout).await?;
+            let (reader, writer) = tcp_tls_connect(host, port, tls_config, config).await?;
             let framed_read = FramedRead::new(reader, BufferDecoder);
-            let framed_write = FramedWrite::new(writer, CommandEncoder);
+            let mut framed_write = FramedWrite::new(writer, CommandEncoder);
+            framed_write.set_backpressure_boundary(config.write_buffer_size);
             Ok(Streams::TcpTls(framed_read, framed_write))
         } else {
             Self::connect_non_secure(host, port, config).await
@@ -49,9 +57,33 @@ impl Streams {
     pub async fn connect_non_secure(host: &str, port: u16, config: &Config) -> Result<Self> {
         let (reader, writer) = tcp_connect(host, port, config).await?;
         let framed_read = FramedRead::new(reader, BufferDecoder);
-        let framed_write = FramedWrite::new(writer, CommandEncoder);
+        let mut framed_write = FramedWrite::new(writer, CommandEncoder);
+        framed_write.set_backpressure_boundary(config.write_buffer_size);
         Ok(Streams::Tcp(framed_read, framed_write))
     }
+
+    async fn connect_custom(
+        host: &str,
+        port: u16,
+        config: &Config,
+        connector: &dyn Connector,
+    ) -> Result<Self> {
+        debug!(
+            "Connecting to {host}:{port} with a custom connector and timeout {:?}...",
+            config.connect_timeout
+        );
+        let mut stream = timeout(config.connect_timeout, connector.connect(host, port)).await??;
+        if let Some(header) = &config.send_proxy_protocol {
+            stream.write_all(&header.to_bytes()).await?;
+        }
+        info!("Connected to {host}:{port}");
+
+        let (reader, writer) = tokio::io::split(stream);
+        let framed_read = FramedRead::new(reader, BufferDecoder);
+        let mut framed_write = FramedWrite::new(writer, CommandEncoder);
+        framed_write.set_backpressure_boundary(config.write_buffer_size);
+        Ok(Streams::Custom(framed_read, framed_write))
+    }
 }
 
 pub struct StandaloneConnection {
@@ -95,6 +127,7 @@ impl StandaloneConnection {
             Streams::Tcp(_, framed_write) => framed_write.send(command).await,
             #[cfg(feature = "tls")]
             Streams::TcpTls(_, framed_write) => framed_write.send(command).await,
+            Streams::Custom(_, framed_write) => framed_write.send(command).await,
         }
     }
 
@@ -105,12 +138,6 @@ impl StandaloneConnection {
     ) -> Result<()> {
         self.buffer.clear();
 
-        let command_encoder = match &mut self.streams {
-            Streams::Tcp(_, framed_write) => framed_write.encoder_mut(),
-            #[cfg(feature = "tls")]
-            Streams::TcpTls(_, framed_write) => framed_write.encoder_mut(),
-        };
-
         #[cfg(debug_assertions)]
         let mut kill_connection = false;
 
@@ -125,7 +152,16 @@ impl StandaloneConnection {
                 command.kill_connection_on_write -= 1;
             }
 
-            command_encoder.encode(command, &mut self.buffer)?;
+            CommandEncoder.encode(command, &mut self.buffer)?;
+
+            #[cfg(debug_assertions)]
+            let can_flush = !kill_connection;
+            #[cfg(not(debug_assertions))]
+            let can_flush = true;
+
+            if can_flush && self.buffer.len() >= self.config.write_buffer_size {
+                self.flush_buffer().await?;
+            }
         }
 
         #[cfg(debug_assertions)]
@@ -140,14 +176,27 @@ impl StandaloneConnection {
                 .await?;
         }
 
+        self.flush_buffer().await
+    }
+
+    /// Writes the encoded commands buffered so far to the socket
+    async fn flush_buffer(&mut self) -> Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
         match &mut self.streams {
             Streams::Tcp(_, framed_write) => framed_write.get_mut().write_all(&self.buffer).await?,
             #[cfg(feature = "tls")]
             Streams::TcpTls(_, framed_write) => {
                 framed_write.get_mut().write_all(&self.buffer).await?
             }
+            Streams::Custom(_, framed_write) => {
+                framed_write.get_mut().write_all(&self.buffer).await?
+            }
         }
 
+        self.buffer.clear();
         Ok(())
     }
 
@@ -156,6 +205,7 @@ impl StandaloneConnection {
             Streams::Tcp(framed_read, _) => framed_read.next().await,
             #[cfg(feature = "tls")]
             Streams::TcpTls(framed_read, _) => framed_read.next().await,
+            Streams::Custom(framed_read, _) => framed_read.next().await,
         } {
             if log_enabled!(Level::Debug) {
                 match &result {
@@ -217,6 +267,11 @@ impl StandaloneConnection {
     pub(crate) fn tag(&self) -> &str {
         &self.tag
     }
+
+    /// Database selected at reconnection
+    pub(crate) fn set_database(&mut self, database: usize) {
+        self.config.database = database;
+    }
 }
 
 impl<'a, R> IntoFuture for PreparedCommand<'a, &'a mut StandaloneConnection, R>
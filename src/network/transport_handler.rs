@@ -0,0 +1,77 @@
+use crate::{
+    client::{Commands, Transport},
+    resp::{BufferDecoder, Command, CommandEncoder, RespBuf},
+    spawn, Error, JoinHandle, MsgReceiver, MsgSender, ReconnectReceiver, ReconnectSender, Result,
+};
+use bytes::BytesMut;
+use futures_channel::mpsc;
+use futures_util::StreamExt;
+use log::warn;
+use tokio::sync::broadcast;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Replacement of the `NetworkHandler`
+/// which exchanges commands and replies with a [`Transport`] instead of a Redis server
+pub(crate) struct TransportHandler<T: Transport> {
+    transport: T,
+    msg_receiver: MsgReceiver,
+    buffer: BytesMut,
+}
+
+impl<T: Transport> TransportHandler<T> {
+    pub fn start(transport: T) -> (MsgSender, JoinHandle<()>, ReconnectSender) {
+        let (msg_sender, msg_receiver): (MsgSender, MsgReceiver) = mpsc::unbounded();
+        let (reconnect_sender, _): (ReconnectSender, ReconnectReceiver) = broadcast::channel(32);
+
+        let mut transport_handler = TransportHandler {
+            transport,
+            msg_receiver,
+            buffer: BytesMut::new(),
+        };
+
+        let join_handle = spawn(async move {
+            transport_handler.transport_loop().await;
+        });
+
+        (msg_sender, join_handle, reconnect_sender)
+    }
+
+    async fn transport_loop(&mut self) {
+        while let Some(message) = self.msg_receiver.next().await {
+            match message.commands {
+                Commands::None => (),
+                Commands::Single(command, result_sender) => {
+                    let result = self.send(&command);
+                    if let Some(result_sender) = result_sender {
+                        if let Err(e) = result_sender.send(result) {
+                            warn!("Cannot send value to caller because receiver is not there anymore: {e:?}");
+                        }
+                    }
+                }
+                Commands::Batch(commands, results_sender) => {
+                    let results = commands
+                        .iter()
+                        .map(|command| self.send(command))
+                        .collect::<Result<Vec<_>>>();
+                    if let Err(e) = results_sender.send(results) {
+                        warn!("Cannot send value to caller because receiver is not there anymore: {e:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    fn send(&mut self, command: &Command) -> Result<RespBuf> {
+        self.buffer.clear();
+        CommandEncoder.encode(command, &mut self.buffer)?;
+
+        let reply = self.transport.send(&self.buffer)?;
+        match BufferDecoder.decode(&mut BytesMut::from(&reply[..]))? {
+            Some(resp_buf) => Ok(resp_buf),
+            None => Err(Error::Client(format!(
+                "Incomplete reply from transport: {}",
+                String::from_utf8_lossy(&reply)
+            ))),
+        }
+    }
+}
@@ -13,6 +13,18 @@ pub fn cmd(name: &'static str) -> Command {
     Command::new(name)
 }
 
+/// Explicit routing of a command in cluster mode
+///
+/// See [`Command::route`], [`PreparedCommand::route_by_key`](crate::client::PreparedCommand::route_by_key)
+/// and [`PreparedCommand::route_to_node`](crate::client::PreparedCommand::route_to_node)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandRouting {
+    /// Send the command to the master node serving the hash slot of this key
+    Key(String),
+    /// Send the command to the node with this id
+    Node(String),
+}
+
 /// Generic command meant to be sent to the Redis Server
 #[derive(Debug, Clone)]
 pub struct Command {
@@ -24,6 +36,7 @@ pub struct Command {
     pub name: &'static str,
     /// Collection of arguments of the command.
     pub args: CommandArgs,
+    pub(crate) routing: Option<CommandRouting>,
     #[doc(hidden)]
     #[cfg(debug_assertions)]
     pub kill_connection_on_write: usize,
@@ -42,6 +55,7 @@ impl Command {
         Self {
             name,
             args: CommandArgs::default(),
+            routing: None,
             #[cfg(debug_assertions)]
             kill_connection_on_write: 0,
             #[cfg(debug_assertions)]
@@ -73,6 +87,24 @@ impl Command {
         self
     }
 
+    /// Builder function to route the command explicitly in cluster mode,
+    /// instead of the routing inferred from the command keys.
+    ///
+    /// Ignored outside of cluster mode.
+    #[must_use]
+    #[inline(always)]
+    pub fn route(mut self, routing: CommandRouting) -> Self {
+        self.routing = Some(routing);
+        self
+    }
+
+    /// Explicit routing of the command in cluster mode, if any.
+    #[must_use]
+    #[inline(always)]
+    pub fn routing(&self) -> Option<&CommandRouting> {
+        self.routing.as_ref()
+    }
+
     #[cfg(debug_assertions)]
     #[inline]
     pub fn kill_connection_on_write(mut self, num_kills: usize) -> Self {
@@ -23,26 +23,40 @@ impl Encoder<&Command> for CommandEncoder {
     }
 }
 
+/// Exact size of the encoded command,
+/// so that the buffer is grown at most once per command
 #[inline]
 fn calculate_buf_size(command: &Command) -> usize {
-    let mut buf_size = 0;
-
-    // *<num_args>\r\n 
+    // *<num_args>\r\n
     let num_args = command.args.len() + 1;
-    buf_size += if num_args <= 9 { 4 } else { 5 };
+    let mut buf_size = 3 + num_digits(num_args);
 
     // $<name_len>\r\n<name>\r\n
-    let name = command.name.as_bytes();
-    buf_size += if name.len() <= 9 { 6 + name.len() } else { 7 + name.len() };
+    buf_size += bulkstring_size(command.name.len());
 
     for arg in &command.args {
         // $<arg_len>\r\n<arg>\r\n
-        buf_size += if arg.len() <= 9 { 6 + arg.len() } else { 7 + arg.len() };
+        buf_size += bulkstring_size(arg.len());
     }
 
     buf_size
 }
 
+#[inline]
+fn bulkstring_size(len: usize) -> usize {
+    5 + num_digits(len) + len
+}
+
+#[inline]
+fn num_digits(mut n: usize) -> usize {
+    let mut digits = 1;
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+    digits
+}
+
 #[inline]
 fn encode_bulkstring(arg: &[u8], buf: &mut BytesMut) {
     buf.put_u8(b'$');
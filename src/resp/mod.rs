@@ -442,6 +442,7 @@ mod resp_buf;
 mod resp_deserializer;
 mod resp_serializer;
 mod response;
+mod timestamp;
 mod to_args;
 mod util;
 mod value;
@@ -459,6 +460,7 @@ pub use resp_buf::*;
 pub use resp_deserializer::*;
 pub use resp_serializer::*;
 pub use response::*;
+pub use timestamp::*;
 pub use to_args::*;
 pub use util::*;
 pub use value::*;
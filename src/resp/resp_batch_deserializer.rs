@@ -7,11 +7,19 @@ use std::slice;
 
 pub(crate) struct RespBatchDeserializer<'de> {
     bufs: &'de Vec<RespBuf>,
+    /// name of the command of each reply, to report conversion errors
+    command_names: &'de [&'static str],
 }
 
 impl<'de> RespBatchDeserializer<'de> {
-    pub fn new(bufs: &'de Vec<RespBuf>) -> RespBatchDeserializer<'de> {
-        RespBatchDeserializer { bufs }
+    pub fn new(
+        bufs: &'de Vec<RespBuf>,
+        command_names: &'de [&'static str],
+    ) -> RespBatchDeserializer<'de> {
+        RespBatchDeserializer {
+            bufs,
+            command_names,
+        }
     }
 }
 
@@ -35,7 +43,7 @@ impl<'de> Deserializer<'de> for &'de RespBatchDeserializer<'de> {
     where
         V: serde::de::Visitor<'de>,
     {
-        visitor.visit_seq(SeqAccess::new(self.bufs))
+        visitor.visit_seq(SeqAccess::new(self.bufs, self.command_names))
     }
 
     fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
@@ -52,14 +60,16 @@ impl<'de> Deserializer<'de> for &'de RespBatchDeserializer<'de> {
 
 struct SeqAccess<'de> {
     iter: slice::Iter<'de, RespBuf>,
+    command_names: slice::Iter<'de, &'static str>,
     len: usize,
 }
 
 impl<'de> SeqAccess<'de> {
-    pub fn new(bufs: &'de [RespBuf]) -> Self {
+    pub fn new(bufs: &'de [RespBuf], command_names: &'de [&'static str]) -> Self {
         Self {
             len: bufs.len(),
             iter: bufs.iter(),
+            command_names: command_names.iter(),
         }
     }
 }
@@ -73,7 +83,13 @@ impl<'de> serde::de::SeqAccess<'de> for SeqAccess<'de> {
     {
         match self.iter.next() {
             Some(buf) => {
-                seed.deserialize(&mut RespDeserializer::new(buf)).map(Some)
+                let command_name = self.command_names.next();
+                seed.deserialize(&mut RespDeserializer::new(buf))
+                    .map(Some)
+                    .map_err(|e| match command_name {
+                        Some(command_name) => e.with_command(command_name),
+                        None => e,
+                    })
             }
             None => Ok(None),
         }
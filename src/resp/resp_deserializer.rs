@@ -1,10 +1,13 @@
-use crate::{resp::PUSH_FAKE_FIELD, Error, RedisError, Result};
+use crate::{resp::PUSH_FAKE_FIELD, ConversionError, Error, RedisError, Result};
 use memchr::memchr;
 use serde::{
-    de::{DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess, Visitor},
+    de::{self, DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess, Visitor},
     forward_to_deserialize_any, Deserializer,
 };
-use std::str::{self, FromStr};
+use std::{
+    any::type_name,
+    str::{self, FromStr},
+};
 
 pub(crate) const SIMPLE_STRING_TAG: u8 = b'+';
 pub(crate) const ERROR_TAG: u8 = b'-';
@@ -25,6 +28,31 @@ fn eof<T>() -> Result<T> {
     Err(Error::EOF)
 }
 
+/// Failure to convert the RESP value starting with `tag` into a Rust type
+fn conversion_error(target: &str, tag: u8, content: Option<&[u8]>) -> Error {
+    let kind = match tag {
+        SIMPLE_STRING_TAG => "simple string",
+        ERROR_TAG | BLOB_ERROR_TAG => "error",
+        INTEGER_TAG => "integer",
+        BULK_STRING_TAG => "bulk string",
+        ARRAY_TAG => "array",
+        MAP_TAG => "map",
+        SET_TAG => "set",
+        DOUBLE_TAG => "double",
+        NIL_TAG => "nil",
+        BOOL_TAG => "boolean",
+        VERBATIM_STRING_TAG => "verbatim string",
+        PUSH_TAG => "push",
+        _ => "unknown value",
+    };
+
+    Error::Conversion(ConversionError::new(
+        target,
+        kind,
+        content.map(String::from_utf8_lossy).as_deref(),
+    ))
+}
+
 /// Serde deserializer for [`RESP3`](https://github.com/redis/redis-specifications/blob/master/protocol/RESP3.md)
 pub struct RespDeserializer<'de> {
     buf: &'de [u8],
@@ -138,8 +166,9 @@ impl<'de> RespDeserializer<'de> {
         let next_line = self.next_line()?;
         atoi::atoi(next_line).ok_or_else(|| {
             Error::Client(format!(
-                "Cannot parse integer from {}",
-                String::from_utf8_lossy(next_line)
+                "Cannot parse integer from {} into {}",
+                String::from_utf8_lossy(next_line),
+                std::any::type_name::<T>()
             ))
         })
     }
@@ -246,20 +275,14 @@ impl<'de> RespDeserializer<'de> {
                     Ok(Default::default())
                 } else {
                     atoi::atoi(bs).ok_or_else(|| {
-                        Error::Client(format!(
-                            "Cannot parse number from {}",
-                            String::from_utf8_lossy(bs)
-                        ))
+                        conversion_error(type_name::<T>(), BULK_STRING_TAG, Some(bs))
                     })
                 }
             }
             SIMPLE_STRING_TAG => {
                 let next_line = self.next_line()?;
                 atoi::atoi(next_line).ok_or_else(|| {
-                    Error::Client(format!(
-                        "Cannot parse number from {}",
-                        String::from_utf8_lossy(next_line)
-                    ))
+                    conversion_error(type_name::<T>(), SIMPLE_STRING_TAG, Some(next_line))
                 })
             }
             ARRAY_TAG => {
@@ -267,12 +290,12 @@ impl<'de> RespDeserializer<'de> {
                 if len == 1 && self.next()? == INTEGER_TAG {
                     self.parse_integer::<T>()
                 } else {
-                    Err(Error::Client("Cannot parse number".to_owned()))
+                    Err(conversion_error(type_name::<T>(), ARRAY_TAG, None))
                 }
             }
             ERROR_TAG => Err(Error::Redis(self.parse_error()?)),
             BLOB_ERROR_TAG => Err(Error::Redis(self.parse_blob_error()?)),
-            _ => Err(Error::Client("Cannot parse number".to_owned())),
+            tag => Err(conversion_error(type_name::<T>(), tag, None)),
         }
     }
 
@@ -292,18 +315,20 @@ impl<'de> RespDeserializer<'de> {
                 if bs.is_empty() {
                     Ok(Default::default())
                 } else {
-                    fast_float::parse(bs)
-                        .map_err(|_| Error::Client("Cannot parse number".to_owned()))
+                    fast_float::parse(bs).map_err(|_| {
+                        conversion_error(type_name::<T>(), BULK_STRING_TAG, Some(bs))
+                    })
                 }
             }
             SIMPLE_STRING_TAG => {
                 let next_line = self.next_line()?;
-                fast_float::parse(next_line)
-                    .map_err(|_| Error::Client("Cannot parse number".to_owned()))
+                fast_float::parse(next_line).map_err(|_| {
+                    conversion_error(type_name::<T>(), SIMPLE_STRING_TAG, Some(next_line))
+                })
             }
             ERROR_TAG => Err(Error::Redis(self.parse_error()?)),
             BLOB_ERROR_TAG => Err(Error::Redis(self.parse_blob_error()?)),
-            _ => Err(Error::Client("Cannot parse number".to_owned())),
+            tag => Err(conversion_error(type_name::<T>(), tag, None)),
         }
     }
 
@@ -430,7 +455,7 @@ impl<'de, 'a> Deserializer<'de> for &'a mut RespDeserializer<'de> {
                 match bs {
                     b"1" | b"true" => true,
                     b"0" | b"false" => false,
-                    _ => return Err(Error::Client("Cannot parse to bool".to_owned())),
+                    _ => return Err(conversion_error("bool", BULK_STRING_TAG, Some(bs))),
                 }
             }
             SIMPLE_STRING_TAG => self.parse_string()? == "OK",
@@ -441,7 +466,7 @@ impl<'de, 'a> Deserializer<'de> for &'a mut RespDeserializer<'de> {
             }
             ERROR_TAG => return Err(Error::Redis(self.parse_error()?)),
             BLOB_ERROR_TAG => return Err(Error::Redis(self.parse_blob_error()?)),
-            _ => return Err(Error::Client("Cannot parse to bool".to_owned())),
+            tag => return Err(conversion_error("bool", tag, None)),
         };
 
         visitor.visit_bool(result)
@@ -538,7 +563,7 @@ impl<'de, 'a> Deserializer<'de> for &'a mut RespDeserializer<'de> {
                 if str.len() == 1 {
                     str.chars().next().unwrap()
                 } else {
-                    return Err(Error::Client("Cannot parse to char".to_owned()));
+                    return Err(conversion_error("char", BULK_STRING_TAG, Some(bs)));
                 }
             }
             SIMPLE_STRING_TAG => {
@@ -546,7 +571,11 @@ impl<'de, 'a> Deserializer<'de> for &'a mut RespDeserializer<'de> {
                 if str.len() == 1 {
                     str.chars().next().unwrap()
                 } else {
-                    return Err(Error::Client("Cannot parse to char".to_owned()));
+                    return Err(conversion_error(
+                        "char",
+                        SIMPLE_STRING_TAG,
+                        Some(str.as_bytes()),
+                    ));
                 }
             }
             NIL_TAG => {
@@ -555,7 +584,7 @@ impl<'de, 'a> Deserializer<'de> for &'a mut RespDeserializer<'de> {
             }
             ERROR_TAG => return Err(Error::Redis(self.parse_error()?)),
             BLOB_ERROR_TAG => return Err(Error::Redis(self.parse_blob_error()?)),
-            _ => return Err(Error::Client("Cannot parse to char".to_owned())),
+            tag => return Err(conversion_error("char", tag, None)),
         };
 
         visitor.visit_char(result)
@@ -751,11 +780,18 @@ impl<'de, 'a> Deserializer<'de> for &'a mut RespDeserializer<'de> {
         }
     }
 
-    #[inline]
-    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    /// Tuples and arrays `[T; N]` must match the exact length of the RESP array
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
+        if let ARRAY_TAG | SET_TAG | PUSH_TAG = self.peek()? {
+            let array_len = self.peek_integer::<usize>()?;
+            if array_len != len {
+                return Err(de::Error::invalid_length(array_len, &visitor));
+            }
+        }
+
         self.deserialize_seq(visitor)
     }
 
@@ -938,7 +974,11 @@ impl<'de, 'a> serde::de::SeqAccess<'de> for SeqAccess<'a, 'de> {
     {
         if self.len > 0 {
             self.len -= 1;
-            seed.deserialize(&mut *self.de).map(Some)
+            seed.deserialize(SeqElementDeserializer {
+                de: self.de,
+                remaining: &mut self.len,
+            })
+            .map(Some)
         } else {
             Ok(None)
         }
@@ -950,6 +990,114 @@ impl<'de, 'a> serde::de::SeqAccess<'de> for SeqAccess<'a, 'de> {
     }
 }
 
+/// Deserializes an element of an array.
+///
+/// When a pair is expected where a scalar is found,
+/// the pair is read from 2 consecutive elements of the flat array
+/// (e.g. `[field1, value1, field2, value2]` to `Vec<(F, V)>`).
+struct SeqElementDeserializer<'a, 'de: 'a> {
+    de: &'a mut RespDeserializer<'de>,
+    remaining: &'a mut usize,
+}
+
+macro_rules! forward_to_resp_deserializer {
+    ($($method:ident)*) => {
+        $(
+            #[inline]
+            fn $method<V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                self.de.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> Deserializer<'de> for SeqElementDeserializer<'a, 'de> {
+    type Error = Error;
+
+    forward_to_resp_deserializer! {
+        deserialize_any deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32
+        deserialize_i64 deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32
+        deserialize_u64 deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char
+        deserialize_str deserialize_string deserialize_bytes deserialize_byte_buf
+        deserialize_option deserialize_unit deserialize_seq deserialize_map
+        deserialize_identifier deserialize_ignored_any
+    }
+
+    #[inline]
+    fn deserialize_unit_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.de.deserialize_unit_struct(name, visitor)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.de.deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.de.peek()? {
+            ARRAY_TAG | SET_TAG | PUSH_TAG | MAP_TAG | NIL_TAG | ERROR_TAG | BLOB_ERROR_TAG => {
+                self.de.deserialize_tuple(len, visitor)
+            }
+            _ if len == 2 && *self.remaining > 0 => {
+                *self.remaining -= 1;
+                visitor.visit_seq(PairSeqAccess { de: self.de, len })
+            }
+            _ => self.de.deserialize_tuple(len, visitor),
+        }
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.de.deserialize_tuple_struct(name, len, visitor)
+    }
+
+    #[inline]
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.de.deserialize_struct(name, fields, visitor)
+    }
+
+    #[inline]
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.de.deserialize_enum(name, variants, visitor)
+    }
+}
+
 impl<'de, 'a> serde::de::MapAccess<'de> for SeqAccess<'a, 'de> {
     type Error = Error;
 
@@ -1069,31 +1217,31 @@ impl<'de, 'a> Deserializer<'de> for PairDeserializer<'a, 'de> {
     where
         V: Visitor<'de>,
     {
-        pub struct PairSeqAccess<'a, 'de: 'a> {
-            de: &'a mut RespDeserializer<'de>,
-            len: usize,
-        }
+        visitor.visit_seq(PairSeqAccess { de: self.de, len })
+    }
+}
 
-        impl<'de, 'a> serde::de::SeqAccess<'de> for PairSeqAccess<'a, 'de> {
-            type Error = Error;
+struct PairSeqAccess<'a, 'de: 'a> {
+    de: &'a mut RespDeserializer<'de>,
+    len: usize,
+}
 
-            fn next_element_seed<T>(
-                &mut self,
-                seed: T,
-            ) -> std::result::Result<Option<T::Value>, Self::Error>
-            where
-                T: DeserializeSeed<'de>,
-            {
-                if self.len > 0 {
-                    self.len -= 1;
-                    seed.deserialize(&mut *self.de).map(Some)
-                } else {
-                    Ok(None)
-                }
-            }
-        }
+impl<'de, 'a> serde::de::SeqAccess<'de> for PairSeqAccess<'a, 'de> {
+    type Error = Error;
 
-        visitor.visit_seq(PairSeqAccess { de: self.de, len })
+    fn next_element_seed<T>(
+        &mut self,
+        seed: T,
+    ) -> std::result::Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.len > 0 {
+            self.len -= 1;
+            seed.deserialize(&mut *self.de).map(Some)
+        } else {
+            Ok(None)
+        }
     }
 }
 
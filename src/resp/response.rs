@@ -2,8 +2,9 @@ use crate::resp::{BulkString, Value};
 use serde::de::DeserializeOwned;
 use smallvec::SmallVec;
 use std::{
-    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     hash::{BuildHasher, Hash},
+    net::{IpAddr, SocketAddr},
 };
 
 /// Marker for a RESP Response
@@ -30,6 +31,8 @@ impl PrimitiveResponse for f64 {}
 impl PrimitiveResponse for bool {}
 impl PrimitiveResponse for String {}
 impl PrimitiveResponse for BulkString {}
+impl PrimitiveResponse for IpAddr {}
+impl PrimitiveResponse for SocketAddr {}
 impl<T: PrimitiveResponse + DeserializeOwned> PrimitiveResponse for Option<T> {}
 
 /// Marker for a collection response
@@ -55,6 +58,7 @@ impl<T> CollectionResponse<T> for [T; 13] where T: Response + DeserializeOwned {
 impl<T> CollectionResponse<T> for [T; 14] where T: Response + DeserializeOwned {}
 impl<T> CollectionResponse<T> for [T; 15] where T: Response + DeserializeOwned {}
 impl<T> CollectionResponse<T> for Vec<T> where T: Response + DeserializeOwned {}
+impl<T> CollectionResponse<T> for VecDeque<T> where T: Response + DeserializeOwned {}
 impl<T, A> CollectionResponse<T> for SmallVec<A>
 where
     A: smallvec::Array<Item = T>,
@@ -66,6 +70,12 @@ impl<T, S: BuildHasher + Default> CollectionResponse<T> for HashSet<T, S> where
 {
 }
 impl<T> CollectionResponse<T> for BTreeSet<T> where T: Response + Ord + DeserializeOwned {}
+#[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
+#[cfg(feature = "indexmap")]
+impl<T, S: BuildHasher + Default> CollectionResponse<T> for indexmap::IndexSet<T, S> where
+    T: Response + Eq + Hash + DeserializeOwned
+{
+}
 
 /// Marker for key/value collection response
 pub trait KeyValueCollectionResponse<K, V>: Response
@@ -89,6 +99,13 @@ where
 {
 }
 
+impl<K, V> KeyValueCollectionResponse<K, V> for VecDeque<(K, V)>
+where
+    K: PrimitiveResponse + DeserializeOwned,
+    V: Response + DeserializeOwned,
+{
+}
+
 impl<K, V, A> KeyValueCollectionResponse<K, V> for SmallVec<A>
 where
     A: smallvec::Array<Item = (K, V)>,
@@ -110,3 +127,13 @@ where
     V: Response + DeserializeOwned,
 {
 }
+
+#[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
+#[cfg(feature = "indexmap")]
+impl<K, V, S: BuildHasher + Default> KeyValueCollectionResponse<K, V>
+    for indexmap::IndexMap<K, V, S>
+where
+    K: PrimitiveResponse + Eq + Hash + DeserializeOwned,
+    V: Response + DeserializeOwned,
+{
+}
@@ -0,0 +1,141 @@
+use crate::resp::PrimitiveResponse;
+use serde::{
+    de::{self, SeqAccess, Unexpected, Visitor},
+    Deserialize, Deserializer,
+};
+use std::{
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// A Unix timestamp returned by Redis
+///
+/// It can be deserialized from an integer number of seconds
+/// (e.g. [`expiretime`](crate::commands::GenericCommands::expiretime)),
+/// or from the `[seconds, microseconds]` pair returned by [`time`](crate::commands::ServerCommands::time).
+///
+/// It converts into a [`SystemTime`] and, with the `chrono` feature, into a `chrono::DateTime<Utc>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(Duration);
+
+impl Timestamp {
+    /// Constructs a new `Timestamp` from a duration since the Unix epoch
+    #[inline]
+    pub fn new(since_epoch: Duration) -> Self {
+        Self(since_epoch)
+    }
+
+    /// Returns the duration elapsed since the Unix epoch
+    #[inline]
+    pub fn since_epoch(&self) -> Duration {
+        self.0
+    }
+
+    /// Returns the number of whole seconds since the Unix epoch
+    #[inline]
+    pub fn unix_timestamp(&self) -> u64 {
+        self.0.as_secs()
+    }
+
+    /// Returns the number of microseconds elapsed in the current second
+    #[inline]
+    pub fn subsec_micros(&self) -> u32 {
+        self.0.subsec_micros()
+    }
+}
+
+impl PrimitiveResponse for Timestamp {}
+
+impl From<Timestamp> for SystemTime {
+    #[inline]
+    fn from(timestamp: Timestamp) -> Self {
+        UNIX_EPOCH + timestamp.0
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+#[cfg(feature = "chrono")]
+impl From<Timestamp> for chrono::DateTime<chrono::Utc> {
+    #[inline]
+    fn from(timestamp: Timestamp) -> Self {
+        chrono::DateTime::<chrono::Utc>::from(SystemTime::from(timestamp))
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TimestampVisitor;
+
+        impl<'de> Visitor<'de> for TimestampVisitor {
+            type Value = Timestamp;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Timestamp")
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Timestamp(Duration::from_secs(v)))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let secs = u64::try_from(v).map_err(|_| {
+                    de::Error::invalid_value(Unexpected::Signed(v), &"a positive timestamp")
+                })?;
+                self.visit_u64(secs)
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let secs = v
+                    .parse::<u64>()
+                    .map_err(|_| de::Error::invalid_value(Unexpected::Str(v), &self))?;
+                self.visit_u64(secs)
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let v = std::str::from_utf8(v)
+                    .map_err(|_| de::Error::invalid_value(Unexpected::Bytes(v), &self))?;
+                self.visit_str(v)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let Some(secs) = seq.next_element::<u64>()? else {
+                    return Err(de::Error::invalid_length(0, &"[seconds, microseconds]"));
+                };
+                let Some(micros) = seq.next_element::<u64>()? else {
+                    return Err(de::Error::invalid_length(1, &"[seconds, microseconds]"));
+                };
+
+                let mut len = 2;
+                while seq.next_element::<de::IgnoredAny>()?.is_some() {
+                    len += 1;
+                }
+                if len != 2 {
+                    return Err(de::Error::invalid_length(len, &"[seconds, microseconds]"));
+                }
+                Ok(Timestamp(
+                    Duration::from_secs(secs) + Duration::from_micros(micros),
+                ))
+            }
+        }
+
+        deserializer.deserialize_any(TimestampVisitor)
+    }
+}
@@ -185,6 +185,8 @@ impl ToArgs for char {
     }
 }
 
+/// `None` does not write any argument: it must not be used
+/// for a positional argument whose presence is required by the command.
 impl<T: ToArgs> ToArgs for Option<T> {
     #[inline]
     fn write_args(&self, args: &mut CommandArgs) {
@@ -1,4 +1,4 @@
-use crate::{RedisError, Result};
+use crate::{Error, RedisError, Result};
 use serde::de::DeserializeOwned;
 use std::{
     collections::HashMap,
@@ -48,6 +48,131 @@ impl Value {
     {
         T::deserialize(&self)
     }
+
+    /// Converts a map reply to its key/value pairs.
+    ///
+    /// Both representations of a map are accepted:
+    /// a RESP3 [`Map`](Value::Map) and a RESP2 flat array of alternating keys and values.
+    /// A [`Nil`](Value::Nil) value is converted to an empty collection.
+    ///
+    /// The pairs of a RESP3 map are returned in no particular order.
+    ///
+    /// # Errors
+    /// * [`Error::Redis`](crate::Error::Redis) for an [`Error`](Value::Error) value
+    /// * [`Error::Client`](crate::Error::Client) for an array with an odd number of elements,
+    ///   or for any other variant
+    pub fn into_pairs(self) -> Result<Vec<(Value, Value)>> {
+        match self {
+            Value::Map(map) => Ok(map.into_iter().collect()),
+            Value::Array(values) => {
+                if values.len() % 2 != 0 {
+                    return Err(Error::Client(format!(
+                        "Cannot convert an array of {} elements to pairs",
+                        values.len()
+                    )));
+                }
+
+                let mut pairs = Vec::with_capacity(values.len() / 2);
+                let mut values = values.into_iter();
+                while let (Some(key), Some(value)) = (values.next(), values.next()) {
+                    pairs.push((key, value));
+                }
+                Ok(pairs)
+            }
+            Value::Nil => Ok(Vec::new()),
+            Value::Error(e) => Err(Error::Redis(e)),
+            _ => Err(Error::Client(format!("Cannot convert {self:?} to pairs"))),
+        }
+    }
+
+    /// Returns the raw bytes of a bulk string or simple string value, without consuming it.
+    ///
+    /// Returns `None` for any other variant.
+    #[inline]
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::BulkString(bs) => Some(bs),
+            Value::SimpleString(s) => Some(s.as_bytes()),
+            _ => None,
+        }
+    }
+
+    /// Renders the value in a human-readable form, similar to the output of `redis-cli`.
+    ///
+    /// Each variant is rendered with its type, bulk strings are quoted and escaped,
+    /// and nested collections are numbered and indented.
+    /// This is meant to inspect unexpected replies, e.g. when a conversion fails.
+    ///
+    /// # Example
+    /// ```
+    /// use rustis::resp::Value;
+    ///
+    /// let value = Value::Array(vec![
+    ///     Value::BulkString(b"key".to_vec()),
+    ///     Value::Array(vec![Value::Integer(12), Value::Nil]),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     "1) \"key\"\n2) 1) (integer) 12\n   2) (nil)",
+    ///     value.to_debug_string()
+    /// );
+    /// ```
+    pub fn to_debug_string(&self) -> String {
+        let mut str = String::new();
+        self.write_debug_string(&mut str, 0);
+        str
+    }
+
+    fn write_debug_string(&self, str: &mut String, indent: usize) {
+        match self {
+            Value::SimpleString(s) => str.push_str(s),
+            Value::Integer(i) => str.push_str(&format!("(integer) {i}")),
+            Value::Double(d) => str.push_str(&format!("(double) {d}")),
+            Value::BulkString(bs) => str.push_str(&format!("\"{}\"", bs.escape_ascii())),
+            Value::Boolean(b) => str.push_str(&format!("({b})")),
+            Value::Array(values) | Value::Set(values) | Value::Push(values) => {
+                if values.is_empty() {
+                    str.push_str("(empty array)");
+                    return;
+                }
+
+                let width = values.len().to_string().len();
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        str.push('\n');
+                        str.push_str(&" ".repeat(indent));
+                    }
+                    let prefix = format!("{:>width$}) ", i + 1);
+                    str.push_str(&prefix);
+                    value.write_debug_string(str, indent + prefix.len());
+                }
+            }
+            Value::Map(values) => {
+                if values.is_empty() {
+                    str.push_str("(empty hash)");
+                    return;
+                }
+
+                let width = values.len().to_string().len();
+                for (i, (key, value)) in values.iter().enumerate() {
+                    if i > 0 {
+                        str.push('\n');
+                        str.push_str(&" ".repeat(indent));
+                    }
+                    let prefix = format!("{:>width$}# ", i + 1);
+                    str.push_str(&prefix);
+                    key.write_debug_string(str, indent + prefix.len());
+                    str.push_str(" => ");
+                    // align nested values after the rendered key
+                    let line_start = str.rfind('\n').map(|i| i + 1).unwrap_or(0);
+                    let value_indent = str[line_start..].chars().count();
+                    value.write_debug_string(str, value_indent);
+                }
+            }
+            Value::Error(e) => str.push_str(&format!("(error) {e}")),
+            Value::Nil => str.push_str("(nil)"),
+        }
+    }
 }
 
 impl Hash for Value {
@@ -1,11 +1,13 @@
-use crate::{resp::Value, Error, Result};
+use crate::{resp::Value, ConversionError, Error, Result};
 use serde::{
-    de::{DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess, Visitor},
+    de::{self, DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess, Visitor},
     forward_to_deserialize_any, Deserialize, Deserializer,
 };
 use std::{
     collections::{hash_map, HashMap},
-    slice, str, vec,
+    slice,
+    str::{self, FromStr},
+    vec,
 };
 
 impl<'de> Deserializer<'de> for &'de Value {
@@ -44,12 +46,7 @@ impl<'de> Deserializer<'de> for &'de Value {
             Value::BulkString(s) if s == b"1" || s == b"true" => true,
             Value::Boolean(b) => *b,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse value {:?} to bool",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("bool", self)),
         };
 
         visitor.visit_bool(result)
@@ -60,18 +57,13 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
-            Value::Integer(i) => *i as i8,
+            Value::Integer(i) => integer_to::<i8>(*i)?,
             Value::Double(d) => *d as i8,
             Value::Nil => 0,
-            Value::BulkString(s) => str::from_utf8(s)?.parse::<i8>()?,
-            Value::SimpleString(s) => s.parse::<i8>()?,
+            Value::BulkString(s) => parse_str::<i8>(self, str::from_utf8(s)?)?,
+            Value::SimpleString(s) => parse_str::<i8>(self, s)?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse value {:?} to i8",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("i8", self)),
         };
 
         visitor.visit_i8(result)
@@ -82,18 +74,13 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
-            Value::Integer(i) => *i as i16,
+            Value::Integer(i) => integer_to::<i16>(*i)?,
             Value::Double(d) => *d as i16,
             Value::Nil => 0,
-            Value::BulkString(s) => str::from_utf8(s)?.parse::<i16>()?,
-            Value::SimpleString(s) => s.parse::<i16>()?,
+            Value::BulkString(s) => parse_str::<i16>(self, str::from_utf8(s)?)?,
+            Value::SimpleString(s) => parse_str::<i16>(self, s)?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse value {:?} to i16",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("i16", self)),
         };
 
         visitor.visit_i16(result)
@@ -104,18 +91,13 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
-            Value::Integer(i) => *i as i32,
+            Value::Integer(i) => integer_to::<i32>(*i)?,
             Value::Double(d) => *d as i32,
             Value::Nil => 0,
-            Value::BulkString(s) => str::from_utf8(s)?.parse::<i32>()?,
-            Value::SimpleString(s) => s.parse::<i32>()?,
+            Value::BulkString(s) => parse_str::<i32>(self, str::from_utf8(s)?)?,
+            Value::SimpleString(s) => parse_str::<i32>(self, s)?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse value {:?} to i32",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("i32", self)),
         };
 
         visitor.visit_i32(result)
@@ -130,16 +112,11 @@ impl<'de> Deserializer<'de> for &'de Value {
             Value::Integer(i) => *i,
             Value::Double(d) => *d as i64,
             Value::Nil => 0,
-            Value::BulkString(s) => str::from_utf8(s)?.parse::<i64>()?,
-            Value::SimpleString(s) => s.parse::<i64>()?,
+            Value::BulkString(s) => parse_str::<i64>(self, str::from_utf8(s)?)?,
+            Value::SimpleString(s) => parse_str::<i64>(self, s)?,
             Value::Array(a) if a.len() == 1 => i64::deserialize(&a[0])?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse value {:?} to i64",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("i64", self)),
         };
 
         visitor.visit_i64(result)
@@ -150,18 +127,13 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
-            Value::Integer(i) => *i as u8,
+            Value::Integer(i) => integer_to::<u8>(*i)?,
             Value::Double(d) => *d as u8,
             Value::Nil => 0,
-            Value::BulkString(s) => str::from_utf8(s)?.parse::<u8>()?,
-            Value::SimpleString(s) => s.parse::<u8>()?,
+            Value::BulkString(s) => parse_str::<u8>(self, str::from_utf8(s)?)?,
+            Value::SimpleString(s) => parse_str::<u8>(self, s)?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse value {:?} to u8",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("u8", self)),
         };
 
         visitor.visit_u8(result)
@@ -172,18 +144,13 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
-            Value::Integer(i) => *i as u16,
+            Value::Integer(i) => integer_to::<u16>(*i)?,
             Value::Double(d) => *d as u16,
             Value::Nil => 0,
-            Value::BulkString(s) => str::from_utf8(s)?.parse::<u16>()?,
-            Value::SimpleString(s) => s.parse::<u16>()?,
+            Value::BulkString(s) => parse_str::<u16>(self, str::from_utf8(s)?)?,
+            Value::SimpleString(s) => parse_str::<u16>(self, s)?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse value {:?} to u16",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("u16", self)),
         };
 
         visitor.visit_u16(result)
@@ -194,18 +161,13 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
-            Value::Integer(i) => *i as u32,
+            Value::Integer(i) => integer_to::<u32>(*i)?,
             Value::Double(d) => *d as u32,
             Value::Nil => 0,
-            Value::BulkString(s) => str::from_utf8(s)?.parse::<u32>()?,
-            Value::SimpleString(s) => s.parse::<u32>()?,
+            Value::BulkString(s) => parse_str::<u32>(self, str::from_utf8(s)?)?,
+            Value::SimpleString(s) => parse_str::<u32>(self, s)?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse value {:?} to u32",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("u32", self)),
         };
 
         visitor.visit_u32(result)
@@ -216,19 +178,14 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
-            Value::Integer(i) => *i as u64,
+            Value::Integer(i) => integer_to::<u64>(*i)?,
             Value::Double(d) => *d as u64,
             Value::Nil => 0,
-            Value::BulkString(s) => str::from_utf8(s)?.parse::<u64>()?,
-            Value::SimpleString(s) => s.parse::<u64>()?,
+            Value::BulkString(s) => parse_str::<u64>(self, str::from_utf8(s)?)?,
+            Value::SimpleString(s) => parse_str::<u64>(self, s)?,
             Value::Array(a) if a.len() == 1 => u64::deserialize(&a[0])?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse value {:?} to u64",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("u64", self)),
         };
 
         visitor.visit_u64(result)
@@ -241,16 +198,11 @@ impl<'de> Deserializer<'de> for &'de Value {
         let result = match self {
             Value::Integer(i) => *i as f32,
             Value::Double(d) => *d as f32,
-            Value::BulkString(bs) => str::from_utf8(bs)?.parse::<f32>()?,
+            Value::BulkString(bs) => parse_str::<f32>(self, str::from_utf8(bs)?)?,
             Value::Nil => 0.,
-            Value::SimpleString(s) => s.parse::<f32>()?,
+            Value::SimpleString(s) => parse_str::<f32>(self, s)?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse result {:?} to f32",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("f32", self)),
         };
 
         visitor.visit_f32(result)
@@ -263,16 +215,11 @@ impl<'de> Deserializer<'de> for &'de Value {
         let result = match self {
             Value::Integer(i) => *i as f64,
             Value::Double(d) => *d,
-            Value::BulkString(bs) => str::from_utf8(bs)?.parse::<f64>()?,
+            Value::BulkString(bs) => parse_str::<f64>(self, str::from_utf8(bs)?)?,
             Value::Nil => 0.,
-            Value::SimpleString(s) => s.parse::<f64>()?,
+            Value::SimpleString(s) => parse_str::<f64>(self, s)?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse result {:?} to f64",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("f64", self)),
         };
 
         visitor.visit_f64(result)
@@ -288,19 +235,19 @@ impl<'de> Deserializer<'de> for &'de Value {
                 if str.len() == 1 {
                     str.chars().next().unwrap()
                 } else {
-                    return Err(Error::Client("Cannot parse to char".to_owned()));
+                    return Err(conversion_error("char", self));
                 }
             }
             Value::SimpleString(str) => {
                 if str.len() == 1 {
                     str.chars().next().unwrap()
                 } else {
-                    return Err(Error::Client("Cannot parse to char".to_owned()));
+                    return Err(conversion_error("char", self));
                 }
             }
             Value::Nil => '\0',
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => return Err(Error::Client("Cannot parse to char".to_owned())),
+            _ => return Err(conversion_error("char", self)),
         };
 
         visitor.visit_char(result)
@@ -315,12 +262,7 @@ impl<'de> Deserializer<'de> for &'de Value {
             Value::Nil => "",
             Value::SimpleString(s) => s.as_str(),
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse value {:?} to str",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("str", self)),
         };
 
         visitor.visit_borrowed_str(result)
@@ -337,12 +279,7 @@ impl<'de> Deserializer<'de> for &'de Value {
             Value::Nil => String::from(""),
             Value::SimpleString(s) => s.clone(),
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse value {:?} to String",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("String", self)),
         };
 
         visitor.visit_string(result)
@@ -357,12 +294,7 @@ impl<'de> Deserializer<'de> for &'de Value {
             Value::Nil => &[],
             Value::SimpleString(s) => s.as_bytes(),
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse value {:?} to byte buffer",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("byte buffer", self)),
         };
 
         visitor.visit_borrowed_bytes(result)
@@ -377,12 +309,7 @@ impl<'de> Deserializer<'de> for &'de Value {
             Value::Nil => vec![],
             Value::SimpleString(s) => s.as_bytes().to_vec(),
             Value::Error(e) => return Err(Error::Redis(e.clone())),
-            _ => {
-                return Err(Error::Client(format!(
-                    "Cannot parse value {:?} to byte buffer",
-                    self
-                )))
-            }
+            _ => return Err(conversion_error("byte buffer", self)),
         };
 
         visitor.visit_byte_buf(result)
@@ -415,7 +342,7 @@ impl<'de> Deserializer<'de> for &'de Value {
             Value::Set(s) if s.is_empty() => visitor.visit_unit(),
             Value::Map(m) if m.is_empty() => visitor.visit_unit(),
             Value::Error(e) => Err(Error::Redis(e.clone())),
-            _ => Err(Error::Client("Expected nil".to_owned())),
+            _ => Err(conversion_error("()", self)),
         }
     }
 
@@ -450,17 +377,21 @@ impl<'de> Deserializer<'de> for &'de Value {
             }
             Value::Map(values) => visitor.visit_seq(MapAccess::new(values)),
             Value::Error(e) => Err(Error::Redis(e.clone())),
-            _ => Err(Error::Client(format!(
-                "Cannot parse sequence from value `{self}`"
-            ))),
+            _ => Err(conversion_error("sequence", self)),
         }
     }
 
-    #[inline]
-    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    /// Tuples and arrays `[T; N]` must match the exact length of the array value
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
+        if let Value::Array(values) | Value::Set(values) | Value::Push(values) = self {
+            if values.len() != len {
+                return Err(de::Error::invalid_length(values.len(), &visitor));
+            }
+        }
+
         self.deserialize_seq(visitor)
     }
 
@@ -485,13 +416,13 @@ impl<'de> Deserializer<'de> for &'de Value {
             Value::Array(values) => visitor.visit_map(SeqAccess::new(values)),
             Value::Map(values) => visitor.visit_map(MapAccess::new(values)),
             Value::Error(e) => Err(Error::Redis(e.clone())),
-            _ => Err(Error::Client("Cannot parse map".to_owned())),
+            _ => Err(conversion_error("map", self)),
         }
     }
 
     fn deserialize_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value>
@@ -503,6 +434,8 @@ impl<'de> Deserializer<'de> for &'de Value {
                 true
             } else if let Some(Value::SimpleString(s)) = values.first() {
                 fields.iter().any(|f| s == f)
+            } else if let Some(Value::BulkString(s)) = values.first() {
+                fields.iter().any(|f| s == f.as_bytes())
             } else {
                 false
             }
@@ -518,7 +451,7 @@ impl<'de> Deserializer<'de> for &'de Value {
             }
             Value::Map(values) => visitor.visit_map(MapAccess::new(values)),
             Value::Error(e) => Err(Error::Redis(e.clone())),
-            _ => Err(Error::Client("Cannot parse struct".to_owned())),
+            _ => Err(conversion_error(name, self)),
         }
     }
 
@@ -564,9 +497,7 @@ impl<'de> Deserializer<'de> for &'de Value {
                 }
             }
             Value::Error(e) => Err(Error::Redis(e.clone())),
-            _ => Err(Error::Client(format!(
-                "Cannot parse enum `{name}` from `{self}`"
-            ))),
+            _ => Err(conversion_error(name, self)),
         }
     }
 
@@ -585,6 +516,47 @@ impl<'de> Deserializer<'de> for &'de Value {
     }
 }
 
+/// Range-checked conversion of a Redis integer, instead of a truncating cast
+#[inline]
+fn integer_to<T: TryFrom<i64>>(i: i64) -> Result<T> {
+    T::try_from(i).map_err(|_| {
+        Error::Client(format!(
+            "Integer {i} is out of range for {}",
+            std::any::type_name::<T>()
+        ))
+    })
+}
+
+/// Parsing of a string value into a number, reporting the value on failure
+#[inline]
+fn parse_str<T: FromStr>(value: &Value, str: &str) -> Result<T> {
+    str.parse::<T>()
+        .map_err(|_| conversion_error(std::any::type_name::<T>(), value))
+}
+
+/// Failure to convert a [`Value`] into a Rust type
+fn conversion_error(target: &str, value: &Value) -> Error {
+    let kind = match value {
+        Value::SimpleString(_) => "simple string",
+        Value::Integer(_) => "integer",
+        Value::Double(_) => "double",
+        Value::BulkString(_) => "bulk string",
+        Value::Boolean(_) => "boolean",
+        Value::Array(_) => "array",
+        Value::Map(_) => "map",
+        Value::Set(_) => "set",
+        Value::Push(_) => "push",
+        Value::Error(_) => "error",
+        Value::Nil => "nil",
+    };
+
+    Error::Conversion(ConversionError::new(
+        target,
+        kind,
+        Some(&value.to_string()),
+    ))
+}
+
 struct NilSeqAccess;
 
 impl<'de> serde::de::SeqAccess<'de> for NilSeqAccess {
@@ -0,0 +1,70 @@
+use crate::{sleep, spawn, timeout, Error, Result};
+use std::time::{Duration, Instant};
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn timeout_elapsed() {
+    let start = Instant::now();
+    let result = timeout(Duration::from_millis(10), sleep(Duration::from_secs(5))).await;
+    assert!(matches!(result, Err(Error::Timeout(_))));
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn timeout_completed() -> Result<()> {
+    let result = timeout(Duration::from_secs(5), async { 12 }).await?;
+    assert_eq!(12, result);
+
+    // an infinite timeout must not panic
+    let result = timeout(Duration::MAX, async { 12 }).await?;
+    assert_eq!(12, result);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn spawn_and_sleep() -> Result<()> {
+    let start = Instant::now();
+    let result = spawn(async {
+        sleep(Duration::from_millis(10)).await;
+        12
+    })
+    .await?;
+    assert_eq!(12, result);
+    assert!(start.elapsed() >= Duration::from_millis(10));
+
+    Ok(())
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[tokio::test]
+async fn tcp_socket_options() -> Result<()> {
+    use crate::{client::Config, network::tcp_connect};
+    use socket2::SockRef;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let port = listener.local_addr()?.port();
+
+    let config = Config {
+        keep_alive: Some(Duration::from_secs(60)),
+        no_delay: true,
+        ..Default::default()
+    };
+    let (reader, writer) = tcp_connect("127.0.0.1", port, &config).await?;
+    let stream = reader.unsplit(writer);
+    assert!(stream.nodelay()?);
+    assert!(SockRef::from(&stream).keepalive()?);
+
+    let config = Config {
+        no_delay: false,
+        ..Default::default()
+    };
+    let (reader, writer) = tcp_connect("127.0.0.1", port, &config).await?;
+    let stream = reader.unsplit(writer);
+    assert!(!stream.nodelay()?);
+    assert!(!SockRef::from(&stream).keepalive()?);
+
+    Ok(())
+}
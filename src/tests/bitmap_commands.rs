@@ -1,10 +1,11 @@
 use crate::{
+    client::{BatchPreparedCommand, Client, RecordingTransport},
     commands::{
         BitFieldGetSubCommand, BitFieldOverflow, BitFieldSubCommand, BitOperation, BitRange,
-        BitUnit, BitmapCommands, StringCommands,
+        BitUnit, BitmapCommands, GenericCommands, StringCommands,
     },
     tests::get_test_client,
-    Result,
+    Error, Result,
 };
 use serial_test::serial;
 
@@ -135,11 +136,62 @@ async fn bitop() -> Result<()> {
     let value: String = client.get("dest").await?;
     assert_eq!("`bc`ab", value);
 
+    client.set("key1", vec![0b1100_0000u8]).await?;
+    client.set("key2", vec![0b1010_0000u8]).await?;
+
+    for (operation, expected) in [
+        (BitOperation::And, [true, false, false, false]),
+        (BitOperation::Or, [true, true, true, false]),
+        (BitOperation::Xor, [false, true, true, false]),
+    ] {
+        let len = client.bitop(operation, "dest", ["key1", "key2"]).await?;
+        assert_eq!(1, len);
+
+        for (offset, bit) in expected.into_iter().enumerate() {
+            assert_eq!(bit, client.getbit("dest", offset as u64).await?);
+        }
+    }
+
+    let len = client.bitop(BitOperation::Not, "dest", "key1").await?;
+    assert_eq!(1, len);
+
+    for (offset, bit) in [false, false, true, true, true, true, true, true]
+        .into_iter()
+        .enumerate()
+    {
+        assert_eq!(bit, client.getbit("dest", offset as u64).await?);
+    }
+
     client.close().await?;
     
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn bitop_not_multiple_keys() {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    let result = client
+        .bitop(BitOperation::Not, "dest", ["key1", "key2"])
+        .await;
+    assert!(
+        matches!(&result, Err(Error::Client(e)) if e == "BITOP NOT must be called with a single source key"),
+        "{result:?}"
+    );
+    assert!(transport.commands().is_empty());
+
+    let mut pipeline = client.create_pipeline();
+    pipeline.set("key1", "value1").queue();
+    pipeline
+        .bitop(BitOperation::Not, "dest", ["key1", "key2"])
+        .queue();
+    let result = pipeline.execute::<()>().await;
+    assert!(matches!(result, Err(Error::Client(_))), "{result:?}");
+    assert!(transport.commands().is_empty());
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -189,7 +241,7 @@ async fn getbit() -> Result<()> {
     client.set("mykey", "foobar").await?;
 
     let value = client.getbit("mykey", 6).await?;
-    assert_eq!(1, value);
+    assert!(value);
 
     client.close().await?;
 
@@ -204,14 +256,34 @@ async fn setbit() -> Result<()> {
 
     client.set("mykey", "foobar").await?;
 
-    let value = client.setbit("mykey", 7, 1).await?;
-    assert_eq!(0, value);
+    let value = client.setbit("mykey", 7, true).await?;
+    assert!(!value);
 
-    let value = client.setbit("mykey", 7, 0).await?;
-    assert_eq!(1, value);
+    let value = client.setbit("mykey", 7, false).await?;
+    assert!(value);
 
     let value = client.getbit("mykey", 7).await?;
-    assert_eq!(0, value);
+    assert!(!value);
+
+    client.close().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn setbit_sparse() -> Result<()> {
+    let client = get_test_client().await?;
+
+    client.del("mykey").await?;
+
+    let value = client.setbit("mykey", 100, true).await?;
+    assert!(!value);
+
+    assert!(!client.getbit("mykey", 99).await?);
+    assert!(client.getbit("mykey", 100).await?);
+    assert!(!client.getbit("mykey", 101).await?);
 
     client.close().await?;
 
@@ -1,12 +1,20 @@
-use std::time::Duration;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use crate::{
-    client::{Client, IntoConfig},
+    client::{
+        BatchPreparedCommand, Client, ConnectFuture, Connector, IntoConfig, ProxyProtocolHeader,
+        RecordingTransport,
+    },
     commands::{
-        BlockingCommands, ClientKillOptions, ConnectionCommands, FlushingMode, LMoveWhere,
-        ListCommands, ServerCommands, StringCommands,
+        BlockingCommands, ClientKillOptions, ConnectionCommands, FlushingMode, GenericCommands,
+        InfoSection, LMoveWhere, ListCommands, ServerCommands, SetCondition, SetExpiration,
+        StringCommands,
     },
-    resp::cmd,
+    resp::{cmd, Value},
+    spawn,
     tests::{get_default_addr, get_test_client, log_try_init},
     Error, Result,
 };
@@ -25,6 +33,30 @@ async fn send() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn send_command() -> Result<()> {
+    let client = get_test_client().await?;
+
+    client.set("key", "value").await?;
+
+    let value: Value = client.send_command(cmd("GET").arg("key")).await?;
+    assert_eq!(Value::BulkString(b"value".to_vec()), value);
+    let value: String = value.into()?;
+    assert_eq!("value", value);
+
+    let value: String = client.send_command(cmd("GET").arg("key")).await?;
+    assert_eq!("value", value);
+
+    let value: Value = client.send_command(cmd("DEBUG").arg("JMAP")).await?;
+    assert_eq!(Value::SimpleString("OK".to_owned()), value);
+
+    client.close().await?;
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -100,6 +132,181 @@ async fn command_timeout() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn auto_pipeline() -> Result<()> {
+    fn total_reads_processed(info: &str) -> u64 {
+        info.lines()
+            .find_map(|line| line.strip_prefix("total_reads_processed:"))
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or_default()
+    }
+
+    const NUM_COMMANDS: i64 = 1000;
+
+    let mut config = get_default_addr().into_config()?;
+    config.auto_pipeline = Some(Duration::from_millis(5));
+    let client = Client::connect(config).await?;
+    client.del("key").await?;
+
+    let info: String = client.info([InfoSection::Stats]).await?;
+    let reads_before = total_reads_processed(&info);
+
+    let join_handles = (0..NUM_COMMANDS)
+        .map(|_| {
+            let client = client.clone();
+            spawn(async move { client.incr("key").await })
+        })
+        .collect::<Vec<_>>();
+
+    let mut values = Vec::with_capacity(NUM_COMMANDS as usize);
+    for join_handle in join_handles {
+        values.push(join_handle.await??);
+    }
+
+    // each caller receives its own reply
+    values.sort_unstable();
+    assert_eq!((1..=NUM_COMMANDS).collect::<Vec<_>>(), values);
+
+    // commands have been coalesced in a few writes
+    let info: String = client.info([InfoSection::Stats]).await?;
+    let reads_after = total_reads_processed(&info);
+    assert!(
+        reads_after - reads_before < (NUM_COMMANDS / 10) as u64,
+        "reads: {}",
+        reads_after - reads_before
+    );
+
+    client.close().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn write_buffer_size() -> Result<()> {
+    const NUM_COMMANDS: usize = 1000;
+
+    // a tiny buffer forces many flushes within the same batch
+    let mut config = get_default_addr().into_config()?;
+    config.write_buffer_size = 64;
+    let client = Client::connect(config).await?;
+
+    let mut pipeline = client.create_pipeline();
+    for i in 0..NUM_COMMANDS {
+        pipeline
+            .set(format!("key{i}"), format!("value{i}"))
+            .forget();
+        pipeline.get::<_, String>(format!("key{i}")).queue();
+    }
+    let values: Vec<String> = pipeline.execute().await?;
+
+    // replies are matched in the order commands were sent
+    assert_eq!(
+        (0..NUM_COMMANDS)
+            .map(|i| format!("value{i}"))
+            .collect::<Vec<_>>(),
+        values
+    );
+
+    client.close().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn with_transport() -> Result<()> {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    let result = client
+        .set_with_options(
+            "key",
+            "value",
+            SetCondition::None,
+            SetExpiration::Ex(10),
+            false,
+        )
+        .await?;
+    assert!(result);
+
+    transport.push_reply("-ERR wrong number of arguments\r\n");
+    let result = client.get::<_, String>("key").await;
+    assert!(matches!(result, Err(Error::Redis(_))));
+
+    assert_eq!(
+        vec![
+            b"*5\r\n$3\r\nSET\r\n$3\r\nkey\r\n$5\r\nvalue\r\n$2\r\nEX\r\n$2\r\n10\r\n".to_vec(),
+            b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n".to_vec(),
+        ],
+        transport.commands()
+    );
+
+    client.close().await?;
+
+    Ok(())
+}
+
+/// Fake server answering the keepalive `PING`s before a regular command
+#[cfg(feature = "tokio-runtime")]
+#[tokio::test]
+async fn keepalive_interval() -> Result<()> {
+    use crate::{
+        sleep,
+        tests::{accept_fake_connection, bind_fake_server},
+    };
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (listener, port) = bind_fake_server().await?;
+
+    let server = spawn(async move {
+        let mut socket = accept_fake_connection(&listener).await?;
+        let ping = b"*1\r\n$4\r\nPING\r\n";
+        let get = b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n";
+        let mut buffer = Vec::new();
+        let mut num_pings = 0;
+
+        loop {
+            let mut chunk = [0u8; 1024];
+            let len = socket.read(&mut chunk).await?;
+            assert!(len > 0, "connection closed by the client");
+            buffer.extend_from_slice(&chunk[..len]);
+
+            while buffer.starts_with(ping) {
+                buffer.drain(..ping.len());
+                num_pings += 1;
+                socket.write_all(b"+PONG\r\n").await?;
+            }
+
+            if buffer.starts_with(get) {
+                socket.write_all(b"$5\r\nvalue\r\n").await?;
+                break;
+            }
+        }
+
+        Ok::<_, Error>((socket, num_pings))
+    });
+
+    let mut config = format!("127.0.0.1:{port}").into_config()?;
+    config.keepalive_interval = Some(Duration::from_millis(50));
+    let client = Client::connect(config).await?;
+
+    // stay idle
+    sleep(Duration::from_millis(175)).await;
+
+    // keepalive replies are not mixed with the replies of regular commands
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    let (_socket, num_pings) = server.await??;
+    assert!(num_pings >= 2);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -121,6 +328,214 @@ async fn connection_name() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn get_database() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushall(FlushingMode::Sync).await?;
+
+    let db1 = client.get_database(1).await?;
+    let db2 = client.get_database(2).await?;
+
+    db1.set("key", "value1").await?;
+    db2.set("key", "value2").await?;
+
+    let value: String = db1.get("key").await?;
+    assert_eq!("value1", value);
+    let value: String = db2.get("key").await?;
+    assert_eq!("value2", value);
+    assert_eq!(0, client.exists("key").await?);
+
+    assert_eq!(1, db1.client_info().await?.db);
+    assert_eq!(2, db2.client_info().await?.db);
+    assert_eq!(0, client.client_info().await?.db);
+
+    // not supported with a custom transport
+    let client = Client::with_transport(RecordingTransport::new());
+    assert!(matches!(
+        client.get_database(1).await,
+        Err(Error::Client(_))
+    ));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn with_connection() -> Result<()> {
+    let client = get_test_client().await?;
+    let client_id = client.client_id().await?;
+
+    let (id1, id2) = client
+        .with_connection(|connection| async move {
+            let id1 = connection.client_id().await?;
+            let id2 = connection.client_id().await?;
+            Ok((id1, id2))
+        })
+        .await?;
+    assert_eq!(id1, id2);
+    assert_ne!(client_id, id1);
+
+    // the dedicated connection is closed afterwards
+    let client_list = client.client_list(Default::default()).await?;
+    assert!(client_list.client_infos.iter().all(|c| c.id != id1));
+
+    // not supported with a custom transport
+    let client = Client::with_transport(RecordingTransport::new());
+    assert!(matches!(
+        client.with_connection(|_| async { Ok(()) }).await,
+        Err(Error::Client(_))
+    ));
+
+    Ok(())
+}
+
+/// Fake server checking that the pinned connection opens on the selected database
+/// and is not reestablished once lost
+#[cfg(feature = "tokio-runtime")]
+#[tokio::test]
+async fn with_connection_pinned() -> Result<()> {
+    use crate::tests::{accept_fake_connection, bind_fake_server, read_command};
+    use tokio::io::AsyncWriteExt;
+
+    let (listener, port) = bind_fake_server().await?;
+
+    let server = spawn(async move {
+        // shared connection
+        let mut shared_socket = accept_fake_connection(&listener).await?;
+        read_command(&mut shared_socket, b"SELECT").await?;
+        shared_socket.write_all(b"+OK\r\n").await?;
+
+        // pinned connection, on the database selected by the shared connection
+        let mut pinned_socket = accept_fake_connection(&listener).await?;
+        let buffer = read_command(&mut pinned_socket, b"SELECT").await?;
+        let select = b"SELECT\r\n$1\r\n3\r\n";
+        assert!(buffer.windows(select.len()).any(|w| w == select));
+        pinned_socket.write_all(b"+OK\r\n").await?;
+
+        // the pinned connection is lost while a command is pending
+        read_command(&mut pinned_socket, b"GET").await?;
+        drop(pinned_socket);
+
+        // no reconnection
+        let reconnection =
+            tokio::time::timeout(Duration::from_millis(200), listener.accept()).await;
+        assert!(
+            reconnection.is_err(),
+            "the pinned connection has been reestablished"
+        );
+
+        Ok::<_, Error>(shared_socket)
+    });
+
+    let client = Client::connect(format!("127.0.0.1:{port}")).await?;
+    client.select(3).await?;
+
+    let results = client
+        .with_connection(|connection| async move {
+            let first: Result<Option<String>> = connection.get("key").await;
+            let second: Result<Option<String>> = connection.get("key").await;
+            Ok((first, second))
+        })
+        .await?;
+    assert!(matches!(results.0, Err(Error::Client(_))));
+    assert!(results.1.is_err());
+
+    let _shared_socket = server.await??;
+
+    Ok(())
+}
+
+/// Fake server checking the database on which a pinned connection opens
+/// after `SELECT` in a pipeline, in a transaction, and after `RESET`
+#[cfg(feature = "tokio-runtime")]
+#[tokio::test]
+async fn selected_database_in_batches() -> Result<()> {
+    use crate::tests::{accept_fake_connection, bind_fake_server, read_command};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Accepts a pinned connection and returns what it sent before `PING`
+    async fn accept_pinned(listener: &TcpListener) -> Result<Vec<u8>> {
+        let mut socket = accept_fake_connection(listener).await?;
+
+        let mut buffer = Vec::new();
+        let mut is_select_answered = false;
+        loop {
+            let mut chunk = [0u8; 1024];
+            let len = socket.read(&mut chunk).await?;
+            assert!(len > 0, "connection closed by the client");
+            buffer.extend_from_slice(&chunk[..len]);
+
+            if !is_select_answered && buffer.windows(6).any(|w| w == b"SELECT") {
+                socket.write_all(b"+OK\r\n").await?;
+                is_select_answered = true;
+            }
+
+            if buffer.windows(4).any(|w| w == b"PING") {
+                socket.write_all(b"+PONG\r\n").await?;
+                return Ok(buffer);
+            }
+        }
+    }
+
+    let (listener, port) = bind_fake_server().await?;
+
+    let server = spawn(async move {
+        let mut shared_socket = accept_fake_connection(&listener).await?;
+
+        // pipeline
+        read_command(&mut shared_socket, b"SELECT").await?;
+        shared_socket.write_all(b"+OK\r\n+OK\r\n").await?;
+        let buffer = accept_pinned(&listener).await?;
+        let select = b"SELECT\r\n$1\r\n2\r\n";
+        assert!(buffer.windows(select.len()).any(|w| w == select));
+
+        // transaction
+        read_command(&mut shared_socket, b"EXEC").await?;
+        shared_socket
+            .write_all(b"+OK\r\n+QUEUED\r\n*1\r\n+OK\r\n")
+            .await?;
+        let buffer = accept_pinned(&listener).await?;
+        let select = b"SELECT\r\n$1\r\n5\r\n";
+        assert!(buffer.windows(select.len()).any(|w| w == select));
+
+        // reset
+        read_command(&mut shared_socket, b"RESET").await?;
+        shared_socket.write_all(b"+RESET\r\n").await?;
+        let buffer = accept_pinned(&listener).await?;
+        assert!(!buffer.windows(6).any(|w| w == b"SELECT"));
+
+        Ok::<_, Error>(shared_socket)
+    });
+
+    let client = Client::connect(format!("127.0.0.1:{port}")).await?;
+    let ping =
+        |connection: Client| async move { connection.ping::<String>(Default::default()).await };
+
+    let mut pipeline = client.create_pipeline();
+    pipeline.set("key", "value").queue();
+    pipeline.select(2).queue();
+    pipeline.execute::<((), ())>().await?;
+    client.with_connection(ping).await?;
+
+    let mut transaction = client.create_transaction();
+    transaction.queue(cmd("SELECT").arg(5));
+    transaction.execute::<()>().await?;
+    client.with_connection(ping).await?;
+
+    client.reset().await?;
+    client.with_connection(ping).await?;
+
+    let _shared_socket = server.await??;
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -155,3 +570,131 @@ async fn mget_mset() -> Result<()> {
 
     Ok(())
 }
+
+/// Records the targets and connects to the test server instead
+#[derive(Default)]
+struct LoopbackConnector {
+    targets: Mutex<Vec<(String, u16)>>,
+}
+
+impl Connector for LoopbackConnector {
+    fn connect<'a>(&'a self, host: &'a str, port: u16) -> ConnectFuture<'a> {
+        self.targets.lock().unwrap().push((host.to_owned(), port));
+
+        Box::pin(async move {
+            let addr = get_default_addr();
+
+            #[cfg(feature = "tokio-runtime")]
+            let stream = tokio::net::TcpStream::connect(addr.as_str()).await?;
+            #[cfg(feature = "async-std-runtime")]
+            let stream = tokio_util::compat::FuturesAsyncReadCompatExt::compat(
+                async_std::net::TcpStream::connect(addr.as_str()).await?,
+            );
+
+            Ok(Box::new(stream) as _)
+        })
+    }
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn connector() -> Result<()> {
+    log_try_init();
+
+    let connector = Arc::new(LoopbackConnector::default());
+
+    let mut config = "redis.invalid:1234".into_config()?;
+    config.connector = Some(connector.clone());
+    let client = Client::connect(config).await?;
+
+    client.set("key", "value").await?;
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    assert_eq!(
+        vec![("redis.invalid".to_owned(), 1234)],
+        *connector.targets.lock().unwrap()
+    );
+
+    client.close().await?;
+
+    Ok(())
+}
+
+/// Serves each connection with an in-memory server which only answers `HELLO 3`
+#[derive(Default)]
+struct FakeServerConnector {
+    received: Arc<Mutex<Vec<u8>>>,
+}
+
+impl Connector for FakeServerConnector {
+    fn connect<'a>(&'a self, _host: &'a str, _port: u16) -> ConnectFuture<'a> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let received = self.received.clone();
+
+        Box::pin(async move {
+            let (client_stream, mut server_stream) = tokio::io::duplex(1024);
+
+            spawn(async move {
+                let mut buf = [0u8; 1024];
+                while let Ok(n @ 1..) = server_stream.read(&mut buf).await {
+                    let hello_received = {
+                        let mut received = received.lock().unwrap();
+                        received.extend_from_slice(&buf[..n]);
+                        received.ends_with(b"*2\r\n$5\r\nHELLO\r\n$1\r\n3\r\n")
+                    };
+
+                    if hello_received {
+                        let reply = b"%5\r\n$6\r\nserver\r\n$5\r\nredis\r\n$7\r\nversion\r\n$5\r\n7.0.0\r\n$5\r\nproto\r\n:3\r\n$2\r\nid\r\n:1\r\n$4\r\nmode\r\n$10\r\nstandalone\r\n";
+                        if server_stream.write_all(reply).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+
+            Ok(Box::new(client_stream) as _)
+        })
+    }
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn send_proxy_protocol() -> Result<()> {
+    log_try_init();
+
+    let connector = Arc::new(FakeServerConnector::default());
+
+    let mut config = "127.0.0.1:6379".into_config()?;
+    config.connector = Some(connector.clone());
+    config.send_proxy_protocol = Some(ProxyProtocolHeader::V1 {
+        source: "192.168.0.1:56324".parse().unwrap(),
+        destination: "192.168.0.11:6379".parse().unwrap(),
+    });
+    let _client = Client::connect(config).await?;
+
+    assert_eq!(
+        b"PROXY TCP4 192.168.0.1 192.168.0.11 56324 6379\r\n*2\r\n$5\r\nHELLO\r\n$1\r\n3\r\n"
+            .to_vec(),
+        *connector.received.lock().unwrap()
+    );
+
+    let header = ProxyProtocolHeader::V1 {
+        source: "[2001:db8::1]:56324".parse().unwrap(),
+        destination: "[2001:db8::11]:6379".parse().unwrap(),
+    };
+    assert_eq!(
+        b"PROXY TCP6 2001:db8::1 2001:db8::11 56324 6379\r\n".to_vec(),
+        header.to_bytes()
+    );
+
+    let header = ProxyProtocolHeader::V1 {
+        source: "192.168.0.1:56324".parse().unwrap(),
+        destination: "[2001:db8::11]:6379".parse().unwrap(),
+    };
+    assert_eq!(b"PROXY UNKNOWN\r\n".to_vec(), header.to_bytes());
+
+    Ok(())
+}
@@ -4,14 +4,16 @@ use crate::{
         CallBuilder, ClusterCommands, ClusterNodeResult,
         ClusterSetSlotSubCommand::{Importing, Migrating, Node},
         ClusterShardResult, ConnectionCommands, FlushingMode, GenericCommands, HelloOptions,
-        MigrateOptions, ScriptingCommands, ServerCommands, StringCommands,
+        InfoSection, MigrateOptions, NodeSelector, PubSubCommands, ScriptingCommands,
+        ServerCommands, SetCommands, StringCommands,
     },
     network::{ClusterConnection, Version},
+    resp::{cmd, CommandRouting},
     sleep, spawn,
     tests::{get_cluster_test_client, get_cluster_test_client_with_command_timeout},
     Error, RedisError, RedisErrorKind, Result,
 };
-use futures_util::try_join;
+use futures_util::{try_join, StreamExt};
 use serial_test::serial;
 use std::{collections::HashSet, future::IntoFuture, time::Duration};
 
@@ -28,6 +30,71 @@ async fn no_request_policy_no_response_policy() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn del_matching() -> Result<()> {
+    let client = get_cluster_test_client().await?;
+    client.flushall(FlushingMode::Sync).await?;
+
+    client
+        .mset(
+            (0..1000)
+                .map(|i| (format!("session:{i}"), "value"))
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+    client
+        .mset(
+            (0..100)
+                .map(|i| (format!("other:{i}"), "value"))
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+
+    let deleted = client.del_matching("session:*", 100).await?;
+    assert_eq!(1000, deleted);
+
+    let deleted = client.del_matching("*", 100).await?;
+    assert_eq!(100, deleted);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn flush_all_shards() -> Result<()> {
+    let client = get_cluster_test_client().await?;
+
+    // keys spread on all the shards
+    let keys = (0..100).map(|i| format!("key{i}")).collect::<Vec<_>>();
+    client
+        .mset(
+            keys.iter()
+                .map(|k| (k.clone(), "value"))
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+    assert_eq!(100, client.dbsize().await?);
+
+    client.flushall(FlushingMode::Sync).await?;
+    assert_eq!(0, client.dbsize().await?);
+    assert_eq!(0, client.exists(keys.clone()).await?);
+
+    client
+        .mset(
+            keys.iter()
+                .map(|k| (k.clone(), "value"))
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+    client.flushdb(FlushingMode::Sync).await?;
+    assert_eq!(0, client.dbsize().await?);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -59,6 +126,38 @@ async fn multi_shard_all_succeeded() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn multi_shard_keys_spread_across_shards() -> Result<()> {
+    let client = get_cluster_test_client().await?;
+    client.flushall(FlushingMode::Sync).await?;
+
+    let items = (0..100)
+        .map(|i| (format!("key{i}"), format!("value{i}")))
+        .collect::<Vec<_>>();
+    client.mset(items.clone()).await?;
+
+    let keys = items.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+    let values: Vec<String> = client.mget(keys.clone()).await?;
+    let expected_values = items
+        .into_iter()
+        .map(|(_, value)| value)
+        .collect::<Vec<_>>();
+    assert_eq!(expected_values, values);
+
+    let deleted = client.del(keys).await?;
+    assert_eq!(100, deleted);
+
+    // the results of commands with keys in different slots cannot be merged
+    client.sadd("set1", "member1").await?;
+    client.sadd("set2", "member2").await?;
+    let result = client.sunionstore("set3", ["set1", "set2"]).await;
+    assert!(matches!(result, Err(Error::Client(_))));
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -180,6 +279,44 @@ async fn all_nodes_all_succeeded() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn broadcast() -> Result<()> {
+    let client = get_cluster_test_client().await?;
+    client.script_flush(FlushingMode::Sync).await?;
+
+    let shards: Vec<ClusterShardResult> = client.cluster_shards().await?;
+
+    let results = client
+        .broadcast(
+            cmd("SCRIPT").arg("LOAD").arg("return 'broadcast'"),
+            NodeSelector::AllMasters,
+        )
+        .await?;
+    assert_eq!(shards.len(), results.len());
+    let sha1s = results
+        .into_values()
+        .map(|r| r.and_then(|v| v.into::<String>()))
+        .collect::<Result<HashSet<_>>>()?;
+    assert_eq!(1, sha1s.len());
+    let sha1 = sha1s.into_iter().next().unwrap();
+
+    let results = client
+        .broadcast(
+            cmd("SCRIPT").arg("EXISTS").arg(&sha1),
+            NodeSelector::AllMasters,
+        )
+        .await?;
+    assert_eq!(shards.len(), results.len());
+    for result in results.into_values() {
+        let exists: Vec<bool> = result?.into()?;
+        assert_eq!(vec![true], exists);
+    }
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -296,6 +433,242 @@ async fn moved() -> Result<()> {
     Ok(())
 }
 
+/// Moves `slot` from the source node to the destination node
+async fn migrate_slot(
+    slot: u16,
+    src_client: &Client,
+    src_id: &str,
+    dst_client: &Client,
+    dst_id: &str,
+) -> Result<()> {
+    dst_client
+        .cluster_setslot(
+            slot,
+            Importing {
+                node_id: src_id.to_owned(),
+            },
+        )
+        .await?;
+
+    src_client
+        .cluster_setslot(
+            slot,
+            Migrating {
+                node_id: dst_id.to_owned(),
+            },
+        )
+        .await?;
+
+    dst_client
+        .cluster_setslot(
+            slot,
+            Node {
+                node_id: dst_id.to_owned(),
+            },
+        )
+        .await?;
+
+    src_client
+        .cluster_setslot(
+            slot,
+            Node {
+                node_id: dst_id.to_owned(),
+            },
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Number of `MOVED` errors replied by a node since its stats were reset
+async fn moved_count(client: &Client) -> Result<usize> {
+    let info: String = client.info([InfoSection::Errorstats]).await?;
+    Ok(info
+        .lines()
+        .find_map(|line| line.strip_prefix("errorstat_MOVED:count="))
+        .and_then(|count| count.trim().parse::<usize>().ok())
+        .unwrap_or_default())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn refresh_slots() -> Result<()> {
+    let client = get_cluster_test_client().await?;
+    client.flushall(FlushingMode::Sync).await?;
+    client.set("key", "value").await?;
+    client.del("key").await?;
+
+    let shard_info_list: Vec<ClusterShardResult> = client.cluster_shards().await?;
+    let slot = client.cluster_keyslot("key").await?;
+
+    let src_node = &shard_info_list
+        .iter()
+        .find(|s| s.slots.iter().any(|s| s.0 <= slot && slot <= s.1))
+        .unwrap()
+        .nodes[0];
+    let dst_node = &shard_info_list
+        .iter()
+        .find(|s| s.slots.iter().all(|s| s.0 > slot || slot > s.1))
+        .unwrap()
+        .nodes[0];
+    let src_client = Client::connect((src_node.ip.clone(), src_node.port.unwrap())).await?;
+    let dst_client = Client::connect((dst_node.ip.clone(), dst_node.port.unwrap())).await?;
+
+    // the client adapts after a single redirection
+    migrate_slot(slot, &src_client, &src_node.id, &dst_client, &dst_node.id).await?;
+    src_client.config_resetstat().await?;
+
+    client.set("key", "value").await?;
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+    client.del("key").await?;
+    assert!(moved_count(&src_client).await? <= 1);
+
+    // no redirection after a manual refresh
+    migrate_slot(slot, &dst_client, &dst_node.id, &src_client, &src_node.id).await?;
+    dst_client.config_resetstat().await?;
+    client.refresh_cluster_slots().await?;
+
+    client.set("key", "value").await?;
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+    client.del("key").await?;
+    assert_eq!(0, moved_count(&dst_client).await?);
+
+    // a standalone client has no slot map
+    let result = src_client.refresh_cluster_slots().await;
+    assert!(matches!(result, Err(Error::Client(_))));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn ssubscribe_moved() -> Result<()> {
+    let pub_sub_client = get_cluster_test_client().await?;
+    let client = get_cluster_test_client().await?;
+    client.flushall(FlushingMode::Sync).await?;
+
+    let shard_info_list: Vec<ClusterShardResult> = client.cluster_shards().await?;
+
+    let slot = client.cluster_keyslot("mychannel").await?;
+
+    let src_node = &shard_info_list
+        .iter()
+        .find(|s| s.slots.iter().any(|s| s.0 <= slot && slot <= s.1))
+        .unwrap()
+        .nodes[0];
+    let dst_node = &shard_info_list
+        .iter()
+        .find(|s| s.slots.iter().all(|s| s.0 > slot || slot > s.1))
+        .unwrap()
+        .nodes[0];
+    let src_id = &src_node.id;
+    let dst_id = &dst_node.id;
+    let src_client = Client::connect((src_node.ip.clone(), src_node.port.unwrap())).await?;
+    let dst_client = Client::connect((dst_node.ip.clone(), dst_node.port.unwrap())).await?;
+
+    let mut pub_sub_stream = pub_sub_client.ssubscribe("mychannel").await?;
+
+    // migrate
+    dst_client
+        .cluster_setslot(
+            slot,
+            Importing {
+                node_id: src_id.clone(),
+            },
+        )
+        .await?;
+
+    src_client
+        .cluster_setslot(
+            slot,
+            Migrating {
+                node_id: dst_id.clone(),
+            },
+        )
+        .await?;
+
+    dst_client
+        .cluster_setslot(
+            slot,
+            Node {
+                node_id: dst_id.clone(),
+            },
+        )
+        .await?;
+
+    src_client
+        .cluster_setslot(
+            slot,
+            Node {
+                node_id: dst_id.clone(),
+            },
+        )
+        .await?;
+
+    // let the pub/sub client resubscribe on the new node
+    sleep(Duration::from_millis(100)).await;
+
+    let num_receivers = client.spublish("mychannel", "mymessage").await?;
+    assert_eq!(1, num_receivers);
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"mychannel".to_vec(), message.channel);
+    assert_eq!(b"mymessage".to_vec(), message.payload);
+
+    // migrate back
+    src_client
+        .cluster_setslot(
+            slot,
+            Importing {
+                node_id: dst_id.clone(),
+            },
+        )
+        .await?;
+
+    dst_client
+        .cluster_setslot(
+            slot,
+            Migrating {
+                node_id: src_id.clone(),
+            },
+        )
+        .await?;
+
+    src_client
+        .cluster_setslot(
+            slot,
+            Node {
+                node_id: src_id.clone(),
+            },
+        )
+        .await?;
+
+    dst_client
+        .cluster_setslot(
+            slot,
+            Node {
+                node_id: src_id.clone(),
+            },
+        )
+        .await?;
+
+    sleep(Duration::from_millis(100)).await;
+
+    let num_receivers = client.spublish("mychannel", "mymessage2").await?;
+    assert_eq!(1, num_receivers);
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"mymessage2".to_vec(), message.payload);
+
+    pub_sub_stream.close().await?;
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -480,3 +853,167 @@ async fn get_loop() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn count_and_get_keys_in_slot() -> Result<()> {
+    let client = get_cluster_test_client().await?;
+    client.flushall(FlushingMode::Sync).await?;
+
+    // same hash tag, same slot
+    client
+        .mset([("{user1}a", "a"), ("{user1}b", "b"), ("{user1}c", "c")])
+        .await?;
+    let slot = client.cluster_keyslot("{user1}").await?;
+
+    // keys in slot are only known by the node owning the slot
+    let shard_info_list: Vec<ClusterShardResult> = client.cluster_shards().await?;
+    let node = &shard_info_list
+        .iter()
+        .find(|s| s.slots.iter().any(|s| s.0 <= slot && slot <= s.1))
+        .unwrap()
+        .nodes[0];
+    let node_client = Client::connect((node.ip.clone(), node.port.unwrap())).await?;
+
+    let count = node_client.cluster_countkeysinslot(slot).await?;
+    assert_eq!(3, count);
+
+    let keys: HashSet<String> = node_client.cluster_getkeysinslot(slot, 10).await?;
+    assert_eq!(
+        HashSet::from([
+            "{user1}a".to_owned(),
+            "{user1}b".to_owned(),
+            "{user1}c".to_owned()
+        ]),
+        keys
+    );
+
+    let keys: Vec<String> = node_client.cluster_getkeysinslot(slot, 2).await?;
+    assert_eq!(2, keys.len());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn slot_administration() -> Result<()> {
+    let client = get_cluster_test_client().await?;
+    client.flushall(FlushingMode::Sync).await?;
+
+    let slot = client.cluster_keyslot("key").await?;
+    let owner = |shards: &[ClusterShardResult]| {
+        shards
+            .iter()
+            .find(|s| s.slots.iter().any(|s| s.0 <= slot && slot <= s.1))
+            .map(|s| s.nodes[0].id.clone())
+    };
+
+    let shard_info_list: Vec<ClusterShardResult> = client.cluster_shards().await?;
+    let src_node = &shard_info_list
+        .iter()
+        .find(|s| s.slots.iter().any(|s| s.0 <= slot && slot <= s.1))
+        .unwrap()
+        .nodes[0];
+    let dst_node = &shard_info_list
+        .iter()
+        .find(|s| s.slots.iter().all(|s| s.0 > slot || slot > s.1))
+        .unwrap()
+        .nodes[0];
+    let src_client = Client::connect((src_node.ip.clone(), src_node.port.unwrap())).await?;
+    let dst_client = Client::connect((dst_node.ip.clone(), dst_node.port.unwrap())).await?;
+
+    // the slot is empty: its ownership can be moved without migrating keys
+    for node_client in [&dst_client, &src_client] {
+        node_client
+            .cluster_setslot(
+                slot,
+                Node {
+                    node_id: dst_node.id.clone(),
+                },
+            )
+            .await?;
+    }
+    let shards: Vec<ClusterShardResult> = dst_client.cluster_shards().await?;
+    assert_eq!(Some(&dst_node.id), owner(&shards).as_ref());
+
+    // move it back
+    for node_client in [&src_client, &dst_client] {
+        node_client
+            .cluster_setslot(
+                slot,
+                Node {
+                    node_id: src_node.id.clone(),
+                },
+            )
+            .await?;
+    }
+    let shards: Vec<ClusterShardResult> = src_client.cluster_shards().await?;
+    assert_eq!(Some(&src_node.id), owner(&shards).as_ref());
+
+    // unassign and reassign the slot on its owner
+    src_client.cluster_delslots(slot).await?;
+    let shards: Vec<ClusterShardResult> = src_client.cluster_shards().await?;
+    assert_eq!(None, owner(&shards));
+    src_client.cluster_addslots(slot).await?;
+
+    src_client.cluster_delslotsrange((slot, slot)).await?;
+    src_client.cluster_addslotsrange((slot, slot)).await?;
+    let shards: Vec<ClusterShardResult> = src_client.cluster_shards().await?;
+    assert_eq!(Some(&src_node.id), owner(&shards).as_ref());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn explicit_routing() -> Result<()> {
+    let client = get_cluster_test_client().await?;
+
+    let shard_info_list: Vec<ClusterShardResult> = client.cluster_shards().await?;
+    let slot = client.cluster_keyslot("key").await?;
+    let master_id = &shard_info_list
+        .iter()
+        .find(|s| s.slots.iter().any(|s| s.0 <= slot && slot <= s.1))
+        .unwrap()
+        .nodes[0]
+        .id;
+
+    // without a hint, CLUSTER MYID would be sent to a random node
+    for _ in 0..10 {
+        let node_id: String = client
+            .send_command(cmd("CLUSTER").arg("MYID"))
+            .route_by_key("key")
+            .await?;
+        assert_eq!(*master_id, node_id);
+    }
+
+    let command = cmd("CLUSTER")
+        .arg("MYID")
+        .route(CommandRouting::Key("key".to_owned()));
+    assert_eq!(
+        Some(&CommandRouting::Key("key".to_owned())),
+        command.routing()
+    );
+    let node_id: String = client.send(command, None).await?.to()?;
+    assert_eq!(*master_id, node_id);
+
+    for shard_info in &shard_info_list {
+        let node_id = &shard_info.nodes[0].id;
+        let result: String = client
+            .send_command(cmd("CLUSTER").arg("MYID"))
+            .route_to_node(node_id.as_str())
+            .await?;
+        assert_eq!(*node_id, result);
+    }
+
+    let result: Result<String> = client
+        .send_command(cmd("CLUSTER").arg("MYID"))
+        .route_to_node("unknown")
+        .await;
+    assert!(matches!(result, Err(Error::Client(_))));
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use crate::{
     client::Client,
-    commands::{ClusterCommands, ClusterShardResult, LegacyClusterShardResult},
+    commands::{
+        ClusterCommands, ClusterNode, ClusterNodesResult, ClusterShardResult,
+        LegacyClusterShardResult,
+    },
     tests::log_try_init,
     Result,
 };
@@ -43,6 +46,91 @@ async fn cluster_slots() -> Result<()> {
     let shards: Vec<LegacyClusterShardResult> = client.cluster_slots().await?;
     debug!("shards: {shards:?}");
     assert_eq!(3, shards.len());
+    assert_eq!(
+        16384,
+        shards
+            .iter()
+            .map(|s| (s.slot.1 - s.slot.0) as usize + 1)
+            .sum::<usize>()
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn cluster_nodes() -> Result<()> {
+    log_try_init();
+    let client = Client::connect("127.0.0.1:7000").await?;
+
+    let result: ClusterNodesResult = client.cluster_nodes().await?;
+    debug!("nodes: {result:?}");
+    assert_eq!(6, result.nodes.len());
+    assert_eq!(
+        1,
+        result
+            .nodes
+            .iter()
+            .filter(|n| n.flags.iter().any(|f| f == "myself"))
+            .count()
+    );
+
+    let masters = result
+        .nodes
+        .iter()
+        .filter(|n| n.is_master())
+        .collect::<Vec<_>>();
+    assert_eq!(3, masters.len());
+    assert_eq!(
+        16384,
+        masters
+            .iter()
+            .flat_map(|n| &n.slots)
+            .map(|(start, end)| (end - start) as usize + 1)
+            .sum::<usize>()
+    );
+
+    for replica in result.nodes.iter().filter(|n| n.is_replica()) {
+        let master_id = replica.master_id.as_deref().unwrap();
+        assert!(masters.iter().any(|m| m.id == master_id));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn cluster_node_from_line() -> Result<()> {
+    let node = ClusterNode::from_line(
+        "07c37dfeb235213a872192d90877d0cd55635b91 127.0.0.1:30004@31004,hostname4 slave e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 0 1426238317239 4 connected",
+    )?;
+    assert_eq!("07c37dfeb235213a872192d90877d0cd55635b91", node.id);
+    assert_eq!("127.0.0.1:30004", node.address);
+    assert_eq!(Some(31004), node.cluster_bus_port);
+    assert_eq!(Some("hostname4"), node.hostname.as_deref());
+    assert!(node.is_replica());
+    assert_eq!(
+        Some("e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca"),
+        node.master_id.as_deref()
+    );
+    assert_eq!(0, node.ping_sent);
+    assert_eq!(1426238317239, node.pong_recv);
+    assert_eq!(4, node.config_epoch);
+    assert_eq!("connected", node.link_state);
+    assert!(node.slots.is_empty());
+
+    let node = ClusterNode::from_line(
+        "e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 127.0.0.1:30001@31001 myself,master - 0 0 1 connected 0-5460 5462 [5461->-67ed2db8d677e59ec4a4cefb06858cf2a1a89fa1]",
+    )?;
+    assert_eq!(None, node.hostname);
+    assert!(node.is_master());
+    assert_eq!(vec!["myself", "master"], node.flags);
+    assert_eq!(None, node.master_id);
+    assert_eq!(vec![(0, 5460), (5462, 5462)], node.slots);
+
+    assert!(
+        ClusterNode::from_line("e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 127.0.0.1:30001").is_err()
+    );
 
     Ok(())
 }
@@ -1,5 +1,6 @@
 use crate::{
     commands::{GenericCommands, HashCommands, SetCommands},
+    resp::{CommandArgs, ToArgs},
     tests::get_test_client,
     Result,
 };
@@ -87,3 +88,15 @@ async fn set_collection() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn option() {
+    let mut args = CommandArgs::default();
+    args.arg("key").arg(Some("x")).arg(None::<&str>);
+    assert_eq!(2, args.len());
+    assert_eq!(b"x", args[1].as_slice());
+
+    assert_eq!(1, Some("x").num_args());
+    assert_eq!(0, None::<&str>.num_args());
+    assert_eq!(2, Some(("x", 12)).num_args());
+}
@@ -0,0 +1,76 @@
+use bytes::BytesMut;
+use tokio_util::codec::Encoder;
+
+use crate::{
+    resp::{cmd, Command, CommandEncoder},
+    Result,
+};
+
+fn encode(command: &Command, buf: &mut BytesMut) -> Result<()> {
+    CommandEncoder.encode(command, buf)
+}
+
+#[test]
+fn encode_command() -> Result<()> {
+    let mut buf = BytesMut::new();
+    encode(&cmd("SET").arg("key").arg("value"), &mut buf)?;
+    assert_eq!(
+        b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$5\r\nvalue\r\n".to_vec(),
+        buf.to_vec()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn single_reservation() -> Result<()> {
+    // long arguments and many arguments have multi-digit lengths
+    let commands = [
+        cmd("SET").arg("key").arg("v".repeat(150)),
+        cmd("SET").arg("key").arg("v".repeat(12345)),
+        cmd("MGET").arg((0..150).map(|i| format!("key{i}")).collect::<Vec<_>>()),
+    ];
+
+    for command in &commands {
+        // the buffer is allocated once, with the exact size of the encoded command
+        let mut buf = BytesMut::new();
+        encode(command, &mut buf)?;
+        assert_eq!(buf.len(), buf.capacity());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn reuse_buffer() -> Result<()> {
+    let command = cmd("SET").arg("key").arg("v".repeat(150));
+
+    let mut buf = BytesMut::new();
+    encode(&command, &mut buf)?;
+    let ptr = buf.as_ptr();
+    let capacity = buf.capacity();
+
+    // a cleared buffer is reused without reallocation
+    for _ in 0..100 {
+        buf.clear();
+        encode(&command, &mut buf)?;
+        assert_eq!(ptr, buf.as_ptr());
+        assert_eq!(capacity, buf.capacity());
+    }
+
+    // a buffer large enough for every command is never reallocated
+    let commands = (0..1000)
+        .map(|i| cmd("SET").arg(format!("key{i}")).arg("v".repeat(i)))
+        .collect::<Vec<_>>();
+    let mut buf = BytesMut::with_capacity(16 * 1024);
+    let ptr = buf.as_ptr();
+    let capacity = buf.capacity();
+    for command in &commands {
+        buf.clear();
+        encode(command, &mut buf)?;
+        assert_eq!(ptr, buf.as_ptr());
+        assert_eq!(capacity, buf.capacity());
+    }
+
+    Ok(())
+}
@@ -1,10 +1,18 @@
 use crate::{
-    client::{Client, IntoConfig},
-    commands::{ClientKillOptions, ConnectionCommands, ServerCommands, FlushingMode},
-    tests::{get_default_host, get_default_port, get_test_client, log_try_init},
-    Result,
+    client::{Client, Config, IntoConfig, ServerConfig},
+    commands::{
+        ClientKillOptions, ConnectionCommands, FlushingMode, ServerCommands, StringCommands,
+    },
+    tests::{
+        get_default_addr, get_default_host, get_default_port, get_test_client, log_try_init,
+    },
+    Error, Result,
 };
 use serial_test::serial;
+use std::{
+    net::{Ipv6Addr, SocketAddr},
+    time::{Duration, Instant},
+};
 
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
@@ -44,6 +52,50 @@ async fn password() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn username() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushall(FlushingMode::Sync).await?;
+
+    // create an ACL user allowed to run GET and SET only
+    client
+        .acl_setuser(
+            "acluser",
+            ["reset", "on", ">pwd", "~*", "+get", "+set", "+acl|whoami"],
+        )
+        .await?;
+
+    // authentication at connect time
+    let config = Config {
+        server: ServerConfig::Standalone {
+            host: get_default_host(),
+            port: get_default_port(),
+        },
+        username: Some("acluser".to_owned()),
+        password: Some("pwd".to_owned()),
+        ..Default::default()
+    };
+    let acl_client = Client::connect(config).await?;
+
+    let username: String = acl_client.acl_whoami().await?;
+    assert_eq!("acluser", username);
+    acl_client.set("key", "value").await?;
+    let value: String = acl_client.get("key").await?;
+    assert_eq!("value", value);
+
+    // authentication at runtime
+    let default_client = Client::connect(get_default_addr()).await?;
+    default_client.auth(Some("acluser"), "pwd").await?;
+    let username: String = default_client.acl_whoami().await?;
+    assert_eq!("acluser", username);
+
+    client.acl_deluser("acluser").await?;
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -175,6 +227,48 @@ fn into_config() -> Result<()> {
             .into_config()?
             .to_string()
     );
+    assert_eq!(
+        "redis://127.0.0.1?pub_sub_buffer_size=100&pub_sub_overflow_policy=drop_newest",
+        "redis://127.0.0.1?pub_sub_buffer_size=100&pub_sub_overflow_policy=drop_newest"
+            .into_config()?
+            .to_string()
+    );
+    assert_eq!(
+        "redis://127.0.0.1?pub_sub_keepalive=30000",
+        "redis://127.0.0.1?pub_sub_keepalive=30000"
+            .into_config()?
+            .to_string()
+    );
+    assert_eq!(
+        "redis://127.0.0.1?subscribe_timeout=5000",
+        "redis://127.0.0.1?subscribe_timeout=5s"
+            .into_config()?
+            .to_string()
+    );
+    assert_eq!(
+        "redis://127.0.0.1?keepalive_interval=60000",
+        "redis://127.0.0.1?keepalive_interval=1m"
+            .into_config()?
+            .to_string()
+    );
+    assert_eq!(
+        "redis://127.0.0.1?auto_pipeline=2",
+        "redis://127.0.0.1?auto_pipeline=2"
+            .into_config()?
+            .to_string()
+    );
+    assert_eq!(
+        "redis://127.0.0.1?write_buffer_size=1024",
+        "redis://127.0.0.1?write_buffer_size=1024"
+            .into_config()?
+            .to_string()
+    );
+    assert_eq!(
+        "redis://127.0.0.1",
+        "redis://127.0.0.1?pub_sub_overflow_policy=drop_oldest"
+            .into_config()?
+            .to_string()
+    );
     assert_eq!(
         "redis+sentinel://127.0.0.1:6379,127.0.0.1:6380,127.0.0.1:6381/myservice/1",
         "redis+sentinel://127.0.0.1:6379,127.0.0.1:6380,127.0.0.1:6381/myservice/1"
@@ -237,7 +331,39 @@ fn into_config() -> Result<()> {
             .to_string()
     );
 
+    assert_eq!(
+        "redis://[::1]",
+        "redis://[::1]:6379".into_config()?.to_string()
+    );
+    assert_eq!(
+        "redis://[2001:db8::1]:6380/3",
+        "redis://[2001:db8::1]:6380/3".into_config()?.to_string()
+    );
+    assert_eq!(
+        "redis://[fe80::1%25eth0]:6380",
+        "redis://[fe80::1%25eth0]:6380".into_config()?.to_string()
+    );
+    assert_eq!(
+        "redis+cluster://127.0.0.1:7000,127.0.0.1:7001?slots_refresh_interval=30000",
+        "redis+cluster://127.0.0.1:7000,127.0.0.1:7001?slots_refresh_interval=30s"
+            .into_config()?
+            .to_string()
+    );
+    assert_eq!(
+        "redis+cluster://[2001:db8::1]:7000,[2001:db8::2]:7001",
+        "redis+cluster://[2001:db8::1]:7000,[2001:db8::2]:7001"
+            .into_config()?
+            .to_string()
+    );
+    assert_eq!("redis://[::1]", "::1".into_config()?.to_string());
+    assert_eq!(
+        "redis://[::1]:6380",
+        "[::1]:6380".into_config()?.to_string()
+    );
+
     assert!("127.0.0.1:xyz".into_config().is_err());
+    assert!("[::1]6380".into_config().is_err());
+    assert!("redis://[::1:6380".into_config().is_err());
     assert!("redis://127.0.0.1:xyz".into_config().is_err());
     assert!("redis://username@127.0.0.1".into_config().is_err());
     assert!("http://username@127.0.0.1".into_config().is_err());
@@ -247,7 +373,7 @@ fn into_config() -> Result<()> {
             .is_err()
     );
     assert!("redis://127.0.0.1?param".into_config().is_err());
-    assert!("redis://127.0.0.1?param=value".into_config().is_ok());
+    assert!("redis://127.0.0.1?param=value".into_config().is_err());
 
     Ok(())
 }
@@ -262,3 +388,125 @@ async fn connect_timeout() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn try_connect() -> Result<()> {
+    log_try_init();
+
+    // closed port
+    let port = std::net::TcpListener::bind("127.0.0.1:0")?
+        .local_addr()?
+        .port();
+    let start = Instant::now();
+    let result =
+        Client::try_connect(format!("redis://127.0.0.1:{port}?connect_timeout=1000")).await;
+    assert!(start.elapsed() < Duration::from_millis(1000));
+    let Err(Error::Connection(e)) = result else {
+        panic!("Expected a connection error");
+    };
+    assert!(e.contains(&format!("127.0.0.1:{port}")));
+
+    // server accepting connections but never answering
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+    let start = Instant::now();
+    let result = Client::try_connect(format!("redis://127.0.0.1:{port}?connect_timeout=200")).await;
+    assert!(start.elapsed() < Duration::from_millis(1000));
+    let Err(Error::Connection(e)) = result else {
+        panic!("Expected a connection error");
+    };
+    assert!(e.contains(&format!("127.0.0.1:{port}")));
+
+    Ok(())
+}
+
+#[test]
+fn ipv6() -> Result<()> {
+    let config = "redis://[2001:db8::1]:6380/3".into_config()?;
+    assert!(
+        matches!(&config.server, ServerConfig::Standalone { host, port } if host == "2001:db8::1" && *port == 6380)
+    );
+    assert_eq!(3, config.database);
+
+    let config = "redis://[::1]".into_config()?;
+    assert!(
+        matches!(&config.server, ServerConfig::Standalone { host, port } if host == "::1" && *port == 6379)
+    );
+
+    let config = "redis://[fe80::1%25eth0]".into_config()?;
+    assert!(
+        matches!(&config.server, ServerConfig::Standalone { host, port } if host == "fe80::1%eth0" && *port == 6379)
+    );
+
+    let config = SocketAddr::from((Ipv6Addr::LOCALHOST, 6380)).into_config()?;
+    assert!(
+        matches!(&config.server, ServerConfig::Standalone { host, port } if host == "::1" && *port == 6380)
+    );
+
+    let config = SocketAddr::from(([127, 0, 0, 1], 6379)).into_config()?;
+    assert_eq!("redis://127.0.0.1", config.to_string());
+
+    Ok(())
+}
+
+#[test]
+fn uri_database_and_query() -> Result<()> {
+    let config =
+        "redis://127.0.0.1:6380/2?timeout=5s&protocol=3&client_name=foo&connect_timeout=250ms"
+            .into_config()?;
+    assert!(
+        matches!(&config.server, ServerConfig::Standalone { host, port } if host == "127.0.0.1" && *port == 6380)
+    );
+    assert_eq!(2, config.database);
+    assert_eq!(Duration::from_secs(5), config.command_timeout);
+    assert_eq!(Duration::from_millis(250), config.connect_timeout);
+    assert_eq!("foo", config.connection_name);
+    assert_eq!(
+        "redis://127.0.0.1:6380/2?connect_timeout=250&command_timeout=5000&connection_name=foo",
+        config.to_string()
+    );
+
+    let config = "redis://127.0.0.1?keep_alive=2m&command_timeout=1500".into_config()?;
+    assert_eq!(Some(Duration::from_secs(120)), config.keep_alive);
+    assert_eq!(Duration::from_millis(1500), config.command_timeout);
+
+    let result = "redis://127.0.0.1/abc".into_config();
+    assert!(
+        matches!(&result, Err(Error::Config(e)) if e.contains("Invalid database index `abc`")),
+        "{result:?}"
+    );
+
+    let result = "redis://127.0.0.1?timeout=5x".into_config();
+    assert!(
+        matches!(&result, Err(Error::Config(e)) if e.contains("`5x` for parameter `timeout`")),
+        "{result:?}"
+    );
+
+    let result = "redis://127.0.0.1?no_delay=maybe".into_config();
+    assert!(
+        matches!(&result, Err(Error::Config(e)) if e.contains("`maybe` for parameter `no_delay`")),
+        "{result:?}"
+    );
+
+    let result = "redis://127.0.0.1?protocol=2".into_config();
+    assert!(
+        matches!(&result, Err(Error::Config(e)) if e.contains("only RESP3")),
+        "{result:?}"
+    );
+
+    let result = "redis://127.0.0.1?timeout=5s&conect_timeout=250".into_config();
+    assert!(
+        matches!(&result, Err(Error::Config(e)) if e.contains("Unknown parameter `conect_timeout`")),
+        "{result:?}"
+    );
+
+    // sentinel parameters are unknown outside of sentinel URIs
+    let result = "redis://127.0.0.1?sentinel_username=user".into_config();
+    assert!(
+        matches!(&result, Err(Error::Config(e)) if e.contains("Unknown parameter `sentinel_username`")),
+        "{result:?}"
+    );
+
+    Ok(())
+}
@@ -2,11 +2,12 @@ use crate::{
     client::{BatchPreparedCommand, Client, ClientPreparedCommand},
     commands::{
         ClientCachingMode, ClientKillOptions, ClientListOptions, ClientPauseMode, ClientReplyMode,
-        ClientTrackingOptions, ClientTrackingStatus, ClientUnblockMode, ConnectionCommands,
-        FlushingMode, GenericCommands, HelloOptions, PingOptions, ServerCommands,
-        StringCommands,
+        ClientTrackingOptions, ClientTrackingStatus, ClientType, ClientUnblockMode,
+        ConnectionCommands, FlushingMode, GenericCommands, HelloOptions, PingOptions,
+        PubSubCommands, ServerCommands, StringCommands,
     },
     network::spawn,
+    resp::BulkString,
     sleep,
     tests::{get_test_client, log_try_init},
     Error, RedisError, RedisErrorKind, Result,
@@ -100,9 +101,58 @@ async fn client_kill() -> Result<()> {
     let client2 = get_test_client().await?;
 
     let client_id = client1.client_id().await?;
-    client2
+    let killed = client2
         .client_kill(ClientKillOptions::default().id(client_id))
         .await?;
+    assert_eq!(1, killed);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn client_kill_by_type() -> Result<()> {
+    let pub_sub_client = get_test_client().await?;
+    let client = get_test_client().await?;
+
+    let _pub_sub_stream = pub_sub_client.subscribe("mychannel").await?;
+
+    let killed = client
+        .client_kill(ClientKillOptions::default().client_type(ClientType::PubSub))
+        .await?;
+    assert_eq!(1, killed);
+
+    let killed = client
+        .client_kill(ClientKillOptions::default().client_type(ClientType::PubSub))
+        .await?;
+    assert_eq!(0, killed);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn client_kill_by_max_age() -> Result<()> {
+    let client1 = get_test_client().await?;
+    let client_id = client1.client_id().await?;
+
+    sleep(std::time::Duration::from_secs(2)).await;
+
+    let client2 = get_test_client().await?;
+    let client2_id = client2.client_id().await?;
+
+    // a connection younger than max_age does not match
+    let killed = client1
+        .client_kill(ClientKillOptions::default().id(client2_id).max_age(1))
+        .await?;
+    assert_eq!(0, killed);
+
+    let killed = client2
+        .client_kill(ClientKillOptions::default().id(client_id).max_age(1))
+        .await?;
+    assert_eq!(1, killed);
 
     Ok(())
 }
@@ -308,6 +358,26 @@ async fn client_tracking_info() -> Result<()> {
     assert_eq!(client2_id, tracking_info.redirect);
     assert_eq!(0, tracking_info.prefixes.len());
 
+    client1
+        .client_tracking(ClientTrackingStatus::Off, ClientTrackingOptions::default())
+        .await?;
+    client1
+        .client_tracking(
+            ClientTrackingStatus::On,
+            ClientTrackingOptions::default()
+                .redirect(client2_id)
+                .broadcasting()
+                .prefix("user:"),
+        )
+        .await?;
+
+    let tracking_info = client1.client_trackinginfo().await?;
+    assert_eq!(2, tracking_info.flags.len());
+    assert!(tracking_info.flags.contains(&"on".to_owned()));
+    assert!(tracking_info.flags.contains(&"bcast".to_owned()));
+    assert_eq!(client2_id, tracking_info.redirect);
+    assert_eq!(vec!["user:".to_owned()], tracking_info.prefixes);
+
     Ok(())
 }
 
@@ -348,6 +418,11 @@ async fn echo() -> Result<()> {
     let result: String = client.echo("hello").await?;
     assert_eq!("hello", result);
 
+    // binary payload
+    let payload = vec![0u8, 255, b'\r', b'\n', 128];
+    let result: BulkString = client.echo(BulkString::from(payload.clone())).await?;
+    assert_eq!(payload, result.as_bytes());
+
     Ok(())
 }
 
@@ -376,9 +451,19 @@ async fn ping() -> Result<()> {
     let client = get_test_client().await?;
 
     client.ping(PingOptions::default()).await?;
+    client.ping::<()>(PingOptions::default()).await?;
+    let result: String = client.ping(PingOptions::default()).await?;
+    assert_eq!("PONG", result);
     let result: String = client.ping(PingOptions::default().message("value")).await?;
     assert_eq!("value", result);
 
+    // binary payload
+    let payload = vec![0u8, 255, b'\r', b'\n', 128];
+    let result: BulkString = client
+        .ping(PingOptions::default().message(BulkString::from(payload.clone())))
+        .await?;
+    assert_eq!(payload, result.as_bytes());
+
     Ok(())
 }
 
@@ -422,3 +507,31 @@ async fn select() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn select_after_reconnect() -> Result<()> {
+    let client = get_test_client().await?;
+    let client2 = get_test_client().await?;
+    client.flushall(FlushingMode::Sync).await?;
+
+    client.select(4).await?;
+    client.set("key", "value").await?;
+
+    let mut receiver = client.on_reconnect();
+    let client_id = client.client_id().await?;
+    client2
+        .client_kill(ClientKillOptions::default().id(client_id))
+        .await?;
+
+    // the first command after the kill triggers the reconnection
+    let client_info = client.client_info().await?;
+    assert!(receiver.try_recv().is_ok());
+    assert_eq!(4, client_info.db);
+
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    Ok(())
+}
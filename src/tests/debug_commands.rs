@@ -1,5 +1,5 @@
 use crate::{
-    commands::{ConnectionCommands, DebugCommands, PingOptions},
+    commands::{ConnectionCommands, DebugCommands, PingOptions, ServerCommands, StringCommands},
     tests::{get_cluster_test_client_with_command_timeout, get_test_client},
     Error, Result,
 };
@@ -40,3 +40,33 @@ async fn cluster_server_panic() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn debug_reload() -> Result<()> {
+    let client = get_test_client().await?;
+    client.set("key", "value").await?;
+
+    client.debug_reload().await?;
+
+    // the dataset survives the save & reload
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn debug_change_repl_id() -> Result<()> {
+    let client = get_test_client().await?;
+
+    let replid = client.info_replication().await?.master_replid;
+    client.debug_change_repl_id().await?;
+    let new_replid = client.info_replication().await?.master_replid;
+    assert_ne!(replid, new_replid);
+
+    Ok(())
+}
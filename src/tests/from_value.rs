@@ -1,6 +1,12 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+};
 
-use crate::{resp::Value, Result};
+use crate::{
+    resp::{RespBuf, Value},
+    Result,
+};
 use serde::Deserialize;
 use smallvec::SmallVec;
 
@@ -80,3 +86,35 @@ fn value_to_value() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn value_to_ip_addr() -> Result<()> {
+    let value = Value::BulkString(b"127.0.0.1".to_vec());
+    let ip_addr: IpAddr = value.into()?;
+    assert_eq!(IpAddr::V4(Ipv4Addr::LOCALHOST), ip_addr);
+
+    let value = Value::SimpleString("::1".to_owned());
+    let ip_addr: IpAddr = value.into()?;
+    assert!(ip_addr.is_loopback());
+
+    let result: Result<IpAddr> = Value::BulkString(b"127.0.0".to_vec()).into();
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn value_to_socket_addr() -> Result<()> {
+    let value = Value::BulkString(b"127.0.0.1:6379".to_vec());
+    let addr: SocketAddr = value.into()?;
+    assert_eq!(SocketAddr::from((Ipv4Addr::LOCALHOST, 6379)), addr);
+
+    let resp_buf = RespBuf::new(bytes::Bytes::from_static(b"$14\r\n127.0.0.1:6379\r\n"));
+    let addr: SocketAddr = resp_buf.to()?;
+    assert_eq!(SocketAddr::from((Ipv4Addr::LOCALHOST, 6379)), addr);
+
+    let result: Result<SocketAddr> = Value::BulkString(b"127.0.0.1".to_vec()).into();
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -1,14 +1,21 @@
 use crate::{
+    client::{Client, RecordingTransport, ScanStreamOptions},
     commands::{
-        ConnectionCommands, ExpireOption, FlushingMode, GenericCommands, ListCommands,
-        RestoreOptions, ScanOptions, ServerCommands, SetCommands, SortOptions, StringCommands,
+        ConnectionCommands, ExpireOption, FlushingMode, GenericCommands, InfoSection, ListCommands,
+        RestoreOptions, ScanCursor, ScanOptions, ServerCommands, SetCommands, SortOptions,
+        StringCommands, TtlStatus,
     },
-    resp::Value,
+    resp::{BulkString, Value},
+    sleep,
     tests::get_test_client,
     Result,
 };
+use futures_util::TryStreamExt;
 use serial_test::serial;
-use std::{collections::HashSet, time::SystemTime};
+use std::{
+    collections::HashSet,
+    time::{Duration, SystemTime},
+};
 
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
@@ -70,6 +77,119 @@ async fn del() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn del_lazy() -> Result<()> {
+    let client = get_test_client().await?;
+
+    client.set("key1", "value1").await?;
+    client.set("key2", "value2").await?;
+
+    let deleted = client.del_lazy(["key1", "key2", "key3"]).await?;
+    assert_eq!(2, deleted);
+
+    let deleted = client.del_lazy("key1").await?;
+    assert_eq!(0, deleted);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn del_if_smaller_than() -> Result<()> {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    // large key
+    transport.push_reply(":10485760\r\n");
+    transport.push_reply(":1\r\n");
+    let deleted = client.del_if_smaller_than("key", 1024 * 1024).await?;
+    assert_eq!(1, deleted);
+
+    // small key
+    transport.push_reply(":64\r\n");
+    transport.push_reply(":1\r\n");
+    let deleted = client.del_if_smaller_than("key", 1024 * 1024).await?;
+    assert_eq!(1, deleted);
+
+    // missing key
+    transport.push_reply("_\r\n");
+    let deleted = client.del_if_smaller_than("key", 1024 * 1024).await?;
+    assert_eq!(0, deleted);
+
+    assert_eq!(
+        vec![
+            b"*3\r\n$6\r\nMEMORY\r\n$5\r\nUSAGE\r\n$3\r\nkey\r\n".to_vec(),
+            b"*2\r\n$6\r\nUNLINK\r\n$3\r\nkey\r\n".to_vec(),
+            b"*3\r\n$6\r\nMEMORY\r\n$5\r\nUSAGE\r\n$3\r\nkey\r\n".to_vec(),
+            b"*2\r\n$3\r\nDEL\r\n$3\r\nkey\r\n".to_vec(),
+            b"*3\r\n$6\r\nMEMORY\r\n$5\r\nUSAGE\r\n$3\r\nkey\r\n".to_vec(),
+        ],
+        transport.commands()
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn del_matching() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    client
+        .mset(
+            (0..1000)
+                .map(|i| (format!("session:{i}"), "value"))
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+    client
+        .mset(
+            (0..100)
+                .map(|i| (format!("other:{i}"), "value"))
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+
+    let deleted = client.del_matching("session:*", 100).await?;
+    assert_eq!(1000, deleted);
+    assert_eq!(100, client.dbsize().await?);
+
+    let deleted = client.del_matching("session:*", 100).await?;
+    assert_eq!(0, deleted);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn del_matching_batches() -> Result<()> {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    transport.push_reply("*2\r\n$1\r\n5\r\n*2\r\n$2\r\nk1\r\n$2\r\nk2\r\n");
+    transport.push_reply("*2\r\n$1\r\n0\r\n*2\r\n$2\r\nk3\r\n$2\r\nk4\r\n");
+    transport.push_reply(":4\r\n");
+    let deleted = client.del_matching("k*", 3).await?;
+    assert_eq!(4, deleted);
+
+    assert_eq!(
+        vec![
+            b"*6\r\n$4\r\nSCAN\r\n$1\r\n0\r\n$5\r\nMATCH\r\n$2\r\nk*\r\n$5\r\nCOUNT\r\n$1\r\n3\r\n"
+                .to_vec(),
+            b"*6\r\n$4\r\nSCAN\r\n$1\r\n5\r\n$5\r\nMATCH\r\n$2\r\nk*\r\n$5\r\nCOUNT\r\n$1\r\n3\r\n"
+                .to_vec(),
+            b"*5\r\n$6\r\nUNLINK\r\n$2\r\nk1\r\n$2\r\nk2\r\n$2\r\nk3\r\n$2\r\nk4\r\n".to_vec(),
+        ],
+        transport.commands()
+    );
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -104,6 +224,16 @@ async fn exists() -> Result<()> {
     let result = client.exists("key2").await?;
     assert_eq!(0, result);
 
+    // duplicates are counted
+    let result = client.exists(["key1", "key1", "key2"]).await?;
+    assert_eq!(2, result);
+
+    let result = client.exists_one("key1").await?;
+    assert!(result);
+
+    let result = client.exists_one("key2").await?;
+    assert!(!result);
+
     Ok(())
 }
 
@@ -149,6 +279,97 @@ async fn expire() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn expire_in() -> Result<()> {
+    let client = get_test_client().await?;
+
+    client.set("key", "value").await?;
+    let result = client.expire_in("key", Duration::from_millis(500)).await?;
+    assert!(result);
+    let pttl = client.pttl("key").await?;
+    assert!(pttl > 0 && pttl <= 500);
+
+    sleep(Duration::from_millis(600)).await;
+    assert_eq!(0, client.exists("key").await?);
+
+    client.set("key", "value").await?;
+    let result = client.expire_in("key", Duration::from_secs(10)).await?;
+    assert!(result);
+    assert_eq!(10, client.ttl("key").await?);
+
+    let result = client.expire_in("unknown", Duration::from_secs(10)).await?;
+    assert!(!result);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn expire_in_encoding() -> Result<()> {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    for _ in 0..6 {
+        transport.push_reply(":1\r\n");
+    }
+    client.expire_in("key", Duration::from_secs(2)).await?;
+    client.expire_in("key", Duration::from_millis(1500)).await?;
+    client.expire_in("key", Duration::from_micros(10)).await?;
+    client
+        .expire_at_instant("key", SystemTime::UNIX_EPOCH + Duration::from_secs(10))
+        .await?;
+    client
+        .expire_at_instant("key", SystemTime::UNIX_EPOCH + Duration::from_millis(10500))
+        .await?;
+    client
+        .expire_at_instant(
+            "key",
+            SystemTime::UNIX_EPOCH + Duration::from_micros(10_500_001),
+        )
+        .await?;
+
+    assert_eq!(
+        vec![
+            b"*3\r\n$6\r\nEXPIRE\r\n$3\r\nkey\r\n$1\r\n2\r\n".to_vec(),
+            b"*3\r\n$7\r\nPEXPIRE\r\n$3\r\nkey\r\n$4\r\n1500\r\n".to_vec(),
+            // sub-millisecond durations are rounded up instead of expiring the key right away
+            b"*3\r\n$7\r\nPEXPIRE\r\n$3\r\nkey\r\n$1\r\n1\r\n".to_vec(),
+            b"*3\r\n$8\r\nEXPIREAT\r\n$3\r\nkey\r\n$2\r\n10\r\n".to_vec(),
+            b"*3\r\n$9\r\nPEXPIREAT\r\n$3\r\nkey\r\n$5\r\n10500\r\n".to_vec(),
+            b"*3\r\n$9\r\nPEXPIREAT\r\n$3\r\nkey\r\n$5\r\n10501\r\n".to_vec(),
+        ],
+        transport.commands()
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn expire_at_instant() -> Result<()> {
+    let client = get_test_client().await?;
+
+    client.set("key", "value").await?;
+    let result = client
+        .expire_at_instant("key", SystemTime::now() + Duration::from_secs(10))
+        .await?;
+    assert!(result);
+    let ttl = client.ttl("key").await?;
+    assert!(ttl > 8 && ttl <= 10);
+
+    // a time in the past deletes the key
+    let result = client
+        .expire_at_instant("key", SystemTime::now() - Duration::from_secs(10))
+        .await?;
+    assert!(result);
+    assert_eq!(0, client.exists("key").await?);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -259,17 +480,27 @@ async fn keys() -> Result<()> {
 #[serial]
 async fn move_() -> Result<()> {
     let client0 = get_test_client().await?;
-    let client1 = get_test_client().await?;
-    client1.select(1).await?;
+    let client3 = get_test_client().await?;
+    client3.select(3).await?;
 
     // cleanup
     client0.del("key").await?;
-    client1.del("key").await?;
+    client3.del("key").await?;
 
     client0.set("key", "value").await?;
-    client0.move_("key", 1).await?;
+    assert!(client0.move_("key", 3).await?);
     assert_eq!(0, client0.exists("key").await?);
-    assert_eq!(1, client1.exists("key").await?);
+    assert_eq!(1, client3.exists("key").await?);
+    let value: String = client3.get("key").await?;
+    assert_eq!("value", value);
+
+    // missing in the source database
+    assert!(!client0.move_("key", 3).await?);
+
+    // already existing in the destination database
+    client0.set("key", "other").await?;
+    assert!(!client0.move_("key", 3).await?);
+    assert_eq!(1, client0.exists("key").await?);
 
     Ok(())
 }
@@ -488,6 +719,10 @@ async fn randomkey() -> Result<()> {
     let client = get_test_client().await?;
 
     client.flushdb(FlushingMode::Sync).await?;
+
+    let key: Option<String> = client.randomkey().await?;
+    assert_eq!(None, key);
+
     client.set("key1", "value1").await?;
     client.set("key2", "value2").await?;
     client.set("key3", "value3").await?;
@@ -495,6 +730,9 @@ async fn randomkey() -> Result<()> {
     let key: String = client.randomkey().await?;
     assert!(["key1", "key2", "key3"].contains(&key.as_str()));
 
+    let key: Option<BulkString> = client.randomkey().await?;
+    assert!(matches!(key.as_deref(), Some(b"key1" | b"key2" | b"key3")));
+
     Ok(())
 }
 
@@ -578,6 +816,99 @@ async fn scan() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn scan_from() -> Result<()> {
+    let client = get_test_client().await?;
+
+    client.flushdb(FlushingMode::Sync).await?;
+
+    let keys = (0..100).map(|i| format!("key{i}")).collect::<Vec<_>>();
+    let items = keys
+        .iter()
+        .map(|k| (k.as_str(), "value"))
+        .collect::<Vec<_>>();
+    client.mset(items).await?;
+
+    // first batch, then checkpoint the cursor
+    let (cursor, mut scanned_keys): (ScanCursor, HashSet<String>) = client
+        .scan_from(ScanCursor::START, ScanOptions::default().count(10))
+        .await?;
+    assert!(!cursor.is_complete());
+    let checkpoint: u64 = cursor.into();
+
+    // resume from the checkpoint
+    let mut cursor = ScanCursor::from(checkpoint);
+    while !cursor.is_complete() {
+        let (next_cursor, batch): (ScanCursor, Vec<String>) = client
+            .scan_from(cursor, ScanOptions::default().count(10))
+            .await?;
+        scanned_keys.extend(batch);
+        cursor = next_cursor;
+    }
+
+    assert_eq!(keys.into_iter().collect::<HashSet<_>>(), scanned_keys);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn scan_stream() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    // only 1% of the keys match
+    let items = (0..10_000)
+        .map(|i| {
+            if i % 100 == 0 {
+                (format!("match:{i}"), i)
+            } else {
+                (format!("other:{i}"), i)
+            }
+        })
+        .collect::<Vec<_>>();
+    client.mset_chunked(&items, 1000).await?;
+
+    async fn scan_calls(client: &Client) -> Result<usize> {
+        let info: String = client.info([InfoSection::Commandstats]).await?;
+        Ok(info
+            .lines()
+            .find_map(|line| line.strip_prefix("cmdstat_scan:calls="))
+            .and_then(|stats| stats.split(',').next())
+            .and_then(|calls| calls.parse::<usize>().ok())
+            .unwrap_or_default())
+    }
+
+    client.config_resetstat().await?;
+    let keys: HashSet<String> = client
+        .scan_stream(ScanStreamOptions::default().match_pattern("match:*"))
+        .try_collect()
+        .await?;
+    assert_eq!(100, keys.len());
+    let fixed_count_calls = scan_calls(&client).await?;
+
+    client.config_resetstat().await?;
+    let mut stream = client.scan_stream::<String>(
+        ScanStreamOptions::default()
+            .match_pattern("match:*")
+            .adaptive_count(1000, 10),
+    );
+    let mut keys = HashSet::new();
+    while let Some(key) = stream.try_next().await? {
+        keys.insert(key);
+    }
+    assert_eq!(100, keys.len());
+    assert!(stream.count() > 10);
+    let adaptive_count_calls = scan_calls(&client).await?;
+
+    assert!(adaptive_count_calls * 10 < fixed_count_calls);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -625,6 +956,45 @@ async fn touch() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn ttl_status() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    let status = client.ttl_status("key").await?;
+    assert_eq!(TtlStatus::NoKey, status);
+
+    client.set("key", "value").await?;
+    let status = client.ttl_status("key").await?;
+    assert_eq!(TtlStatus::NoExpiry, status);
+
+    client.expire("key", 10, ExpireOption::None).await?;
+    let status = client.ttl_status("key").await?;
+    let TtlStatus::Expires(ttl) = status else {
+        panic!("Unexpected status {status:?}");
+    };
+    assert!(ttl > Duration::from_secs(9) && ttl <= Duration::from_secs(10));
+
+    client.persist("key").await?;
+    let status = client.ttl_status("key").await?;
+    assert_eq!(TtlStatus::NoExpiry, status);
+
+    Ok(())
+}
+
+#[test]
+fn ttl_status_from_pttl() {
+    assert_eq!(TtlStatus::NoKey, TtlStatus::from(-2));
+    assert_eq!(TtlStatus::NoExpiry, TtlStatus::from(-1));
+    assert_eq!(TtlStatus::Expires(Duration::ZERO), TtlStatus::from(0));
+    assert_eq!(
+        TtlStatus::Expires(Duration::from_millis(1500)),
+        TtlStatus::from(1500)
+    );
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
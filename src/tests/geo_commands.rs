@@ -1,4 +1,5 @@
 use crate::{
+    client::{Client, RecordingTransport},
     commands::{
         GenericCommands, GeoAddCondition, GeoCommands, GeoSearchBy, GeoSearchFrom,
         GeoSearchOptions, GeoSearchOrder, GeoSearchResult, GeoSearchStoreOptions, GeoUnit,
@@ -78,6 +79,26 @@ async fn geoadd() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn geoadd_options_order() -> Result<()> {
+    let transport = RecordingTransport::new();
+    transport.push_reply(":1\r\n");
+    let client = Client::with_transport(transport.clone());
+
+    let len = client
+        .geoadd("key", GeoAddCondition::XX, true, [(1.5, 2.5, "member")])
+        .await?;
+    assert_eq!(1, len);
+
+    assert_eq!(
+        vec![b"*7\r\n$6\r\nGEOADD\r\n$3\r\nkey\r\n$2\r\nXX\r\n$2\r\nCH\r\n$3\r\n1.5\r\n$3\r\n2.5\r\n$6\r\nmember\r\n".to_vec()],
+        transport.commands()
+    );
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -1,9 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::{
-    commands::{GenericCommands, HScanOptions, HScanResult, HashCommands},
+    client::{Client, RecordingTransport},
+    commands::{
+        ExpireOption, GenericCommands, GetExOptions, HExpireResult, HScanOptions, HScanResult,
+        HashCommands,
+    },
+    sleep,
     tests::get_test_client,
-    Result,
+    Error, RedisError, RedisErrorKind, Result,
 };
 use serial_test::serial;
 
@@ -51,6 +56,91 @@ async fn hexists() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn hexpire() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.del("key").await?;
+
+    client
+        .hset("key", [("field1", "Hello"), ("field2", "World")])
+        .await?;
+
+    let results = client
+        .hexpire("key", 1, ExpireOption::None, ["field1", "unknown"])
+        .await?;
+    assert_eq!(
+        vec![HExpireResult::Set, HExpireResult::NoSuchField],
+        results
+    );
+
+    let results = client
+        .hexpire("key", 10, ExpireOption::Nx, "field1")
+        .await?;
+    assert_eq!(vec![HExpireResult::ConditionNotMet], results);
+
+    let ttls = client.httl("key", ["field1", "field2", "unknown"]).await?;
+    assert_eq!(3, ttls.len());
+    assert!(ttls[0] > 0 && ttls[0] <= 1);
+    assert_eq!(-1, ttls[1]);
+    assert_eq!(-2, ttls[2]);
+
+    let pttls = client.hpttl("key", "field1").await?;
+    assert!(pttls[0] > 0 && pttls[0] <= 1000);
+
+    sleep(std::time::Duration::from_millis(1100)).await;
+
+    let exists = client.hexists("key", "field1").await?;
+    assert!(!exists);
+    let len = client.hlen("key").await?;
+    assert_eq!(1, len);
+
+    let results = client
+        .hpexpire("key", 0, ExpireOption::None, "field2")
+        .await?;
+    assert_eq!(vec![HExpireResult::Deleted], results);
+    let exists = client.exists("key").await?;
+    assert_eq!(0, exists);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn hexpireat() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.del("key").await?;
+
+    client.hset("key", ("field", "value")).await?;
+
+    let results = client
+        .hexpireat("key", 33177117420, ExpireOption::None, "field")
+        .await?;
+    assert_eq!(vec![HExpireResult::Set], results);
+    let times = client.hexpiretime("key", "field").await?;
+    assert_eq!(vec![33177117420], times);
+
+    let results = client
+        .hpexpireat("key", 33177117420000, ExpireOption::Lt, "field")
+        .await?;
+    assert_eq!(vec![HExpireResult::ConditionNotMet], results);
+    let times = client.hpexpiretime("key", "field").await?;
+    assert_eq!(vec![33177117420000], times);
+
+    let results = client.hpersist("key", ["field", "unknown"]).await?;
+    assert_eq!(vec![1, -2], results);
+    let results = client.hpersist("key", "field").await?;
+    assert_eq!(vec![-1], results);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -84,6 +174,105 @@ async fn hget_all() -> Result<()> {
     assert_eq!(Some(&"Hello".to_owned()), result.get("field1"));
     assert_eq!(Some(&"World".to_owned()), result.get("field2"));
 
+    // server order is preserved
+    let result: Vec<(String, String)> = client.hgetall("key").await?;
+    assert_eq!(
+        vec![
+            ("field1".to_owned(), "Hello".to_owned()),
+            ("field2".to_owned(), "World".to_owned())
+        ],
+        result
+    );
+
+    let result: BTreeMap<String, String> = client.hgetall("key").await?;
+    assert_eq!(
+        vec![("field1", "Hello"), ("field2", "World")],
+        result
+            .iter()
+            .map(|(f, v)| (f.as_str(), v.as_str()))
+            .collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn hgetdel() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.del("key").await?;
+
+    client
+        .hset("key", [("field1", "Hello"), ("field2", "World")])
+        .await?;
+
+    let values: Vec<Option<String>> = client.hgetdel("key", ["field1", "unknown"]).await?;
+    assert_eq!(vec![Some("Hello".to_owned()), None], values);
+    let len = client.hlen("key").await?;
+    assert_eq!(1, len);
+
+    let values: Vec<String> = client.hgetdel("key", "field2").await?;
+    assert_eq!(vec!["World".to_owned()], values);
+    let exists = client.exists("key").await?;
+    assert_eq!(0, exists);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn hgetex() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.del("key").await?;
+
+    client
+        .hset("key", [("field1", "Hello"), ("field2", "World")])
+        .await?;
+
+    let values: Vec<String> = client
+        .hgetex("key", GetExOptions::Ex(60), ["field1", "field2"])
+        .await?;
+    assert_eq!(vec!["Hello".to_owned(), "World".to_owned()], values);
+    let ttls = client.httl("key", ["field1", "field2"]).await?;
+    assert!(ttls.iter().all(|ttl| *ttl > 0 && *ttl <= 60));
+
+    let values: Vec<String> = client
+        .hgetex("key", GetExOptions::Persist, "field1")
+        .await?;
+    assert_eq!(vec!["Hello".to_owned()], values);
+    let ttls = client.httl("key", "field1").await?;
+    assert_eq!(vec![-1], ttls);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn hgetall_collections() -> Result<()> {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    transport.push_reply("*4\r\n$6\r\nfield2\r\n$5\r\nWorld\r\n$6\r\nfield1\r\n$5\r\nHello\r\n");
+    let values: std::collections::VecDeque<(String, String)> = client.hgetall("key").await?;
+    assert_eq!(
+        Some(&("field2".to_owned(), "World".to_owned())),
+        values.front()
+    );
+
+    #[cfg(feature = "indexmap")]
+    {
+        transport
+            .push_reply("*4\r\n$6\r\nfield2\r\n$5\r\nWorld\r\n$6\r\nfield1\r\n$5\r\nHello\r\n");
+        let values: indexmap::IndexMap<String, String> = client.hgetall("key").await?;
+        assert_eq!(vec!["field2", "field1"], values.keys().collect::<Vec<_>>());
+    }
+
     Ok(())
 }
 
@@ -125,6 +314,17 @@ async fn hincrbyfloat() -> Result<()> {
     let value = client.hincrbyfloat("key", "field", 2.0e2).await?;
     assert_eq!(5200.0, value);
 
+    // non-numeric field
+    client.hset("key", ("field", "abc")).await?;
+    let result = client.hincrbyfloat("key", "field", 1.0).await;
+    assert!(matches!(
+        result,
+        Err(Error::Redis(RedisError {
+            kind: RedisErrorKind::Err,
+            description: _
+        }))
+    ));
+
     Ok(())
 }
 
@@ -187,6 +387,29 @@ async fn hmget() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn hmget_missing_field() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.del("key").await?;
+
+    client
+        .hset("key", [("field1", "Hello"), ("field3", "World")])
+        .await?;
+    let values: Vec<Option<String>> = client
+        .hmget("key", ["field1", "field2", "field3"])
+        .await?;
+    assert_eq!(
+        vec![Some("Hello".to_owned()), None, Some("World".to_owned())],
+        values
+    );
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -254,6 +477,44 @@ async fn hscan() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn hscan_novalues() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.del("key").await?;
+
+    let fields_and_values: Vec<_> = (1..21)
+        .map(|i| (format!("field{}", i), "v".repeat(1024)))
+        .collect();
+
+    client.hset("key", fields_and_values).await?;
+
+    // with the hashtable encoding, a single call may stop before the end of the iteration
+    let mut fields = Vec::<String>::new();
+    let mut cursor = 0;
+    loop {
+        let (next_cursor, page): (u64, Vec<String>) = client
+            .hscan_novalues("key", cursor, HScanOptions::default().count(20))
+            .await?;
+        fields.extend(page);
+        cursor = next_cursor;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    // a field may be returned more than once during a full iteration
+    fields.sort_by_key(|f| f[5..].parse::<u32>().unwrap());
+    fields.dedup();
+    let expected: Vec<_> = (1..21).map(|i| format!("field{}", i)).collect();
+    assert_eq!(expected, fields);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -49,6 +49,50 @@ async fn pfcount() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn pfcount_union() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    let elements1 = (0..1000).map(|i| format!("element{i}")).collect::<Vec<_>>();
+    let elements2 = (500..1500)
+        .map(|i| format!("element{i}"))
+        .collect::<Vec<_>>();
+    client.pfadd("key1", elements1).await?;
+    client.pfadd("key2", elements2).await?;
+
+    // standard error of the HyperLogLog implementation is 0.81%
+    let count = client.pfcount(["key1", "key2"]).await?;
+    assert!((1470..=1530).contains(&count), "count: {count}");
+
+    // source keys are not altered
+    let count = client.pfcount("key1").await?;
+    assert!((980..=1020).contains(&count), "count: {count}");
+    let count = client.pfcount("key2").await?;
+    assert!((980..=1020).contains(&count), "count: {count}");
+
+    Ok(())
+}
+
+#[cfg(feature = "debug-commands")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn pfdebug_getreg() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    client.pfadd("key", ["a", "b", "c"]).await?;
+
+    let registers = client.pfdebug_getreg("key").await?;
+    assert_eq!(16384, registers.len());
+    assert!(registers.iter().filter(|r| **r != 0).count() <= 3);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -67,3 +111,15 @@ async fn pfmerge() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "debug-commands")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn pfselftest() -> Result<()> {
+    let client = get_test_client().await?;
+
+    client.pfselftest().await?;
+
+    Ok(())
+}
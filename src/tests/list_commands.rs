@@ -1,12 +1,16 @@
 use crate::{
+    client::{Client, RecordingTransport},
     commands::{
         BlockingCommands, FlushingMode, GenericCommands, LInsertWhere, LMoveWhere::Left,
         LMoveWhere::Right, ListCommands, ServerCommands,
     },
     resp::Value,
     sleep, spawn,
-    tests::get_test_client,
-    Result,
+    tests::{
+        assert_encoding, get_test_client, set_list_encoding_thresholds,
+        DEFAULT_LIST_MAX_LISTPACK_SIZE, DEFAULT_QUICKLIST_PACKED_THRESHOLD,
+    },
+    timeout, Error, Result,
 };
 use serial_test::serial;
 use std::time::Duration;
@@ -154,6 +158,34 @@ async fn blpop() -> Result<()> {
     Ok(())
 }
 
+/// A blocking command waits on its own client, without stalling the other clients,
+/// whatever the async runtime
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn blpop_on_dedicated_client() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    let blocking_client = get_test_client().await?;
+    let join_handle = spawn(async move {
+        let result: Result<Option<(String, String)>> = blocking_client.blpop("list", 5.0).await;
+        result
+    });
+
+    // the regular client is still responsive while the other one is blocked
+    sleep(Duration::from_millis(100)).await;
+    let len = client.llen("list").await?;
+    assert_eq!(0, len);
+
+    client.rpush("list", "element").await?;
+
+    let result = timeout(Duration::from_secs(1), join_handle).await???;
+    assert_eq!(Some(("list".to_owned(), "element".to_owned())), result);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -238,6 +270,68 @@ async fn linsert() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn linsert_quicklist() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.del("mylist").await?;
+
+    // one element per node, elements over 16 bytes in plain nodes
+    set_list_encoding_thresholds(&client, 1, 16).await?;
+
+    let result = async {
+        client.rpush("mylist", ["element1", "element3"]).await?;
+        assert_encoding(&client, "mylist", "quicklist").await?;
+
+        // insert at a node boundary
+        let result = client
+            .linsert("mylist", LInsertWhere::After, "element1", "element2")
+            .await?;
+        assert_eq!(3, result);
+
+        // insert next to a plain node
+        let large_element = "e".repeat(32);
+        client.rpush("mylist", &large_element).await?;
+        let result = client
+            .linsert(
+                "mylist",
+                LInsertWhere::Before,
+                large_element.as_str(),
+                "element4",
+            )
+            .await?;
+        assert_eq!(5, result);
+
+        let elements: Vec<String> = client.lrange("mylist", 0, -1).await?;
+        assert_eq!(
+            vec![
+                "element1".to_owned(),
+                "element2".to_owned(),
+                "element3".to_owned(),
+                "element4".to_owned(),
+                large_element
+            ],
+            elements
+        );
+
+        Ok(())
+    }
+    .await;
+
+    // restore defaults
+    set_list_encoding_thresholds(
+        &client,
+        DEFAULT_LIST_MAX_LISTPACK_SIZE,
+        DEFAULT_QUICKLIST_PACKED_THRESHOLD,
+    )
+    .await?;
+
+    result
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -304,11 +398,39 @@ async fn lmpop() -> Result<()> {
         )
         .await?;
 
-    let result: (String, Vec<String>) = client.lmpop("mylist", Left, 1).await?;
+    let result: Option<(String, Vec<String>)> = client.lmpop("mylist", Left, 1).await?;
+    let Some(result) = result else {
+        panic!("lmpop returned nil");
+    };
     assert_eq!("mylist", result.0);
     assert_eq!(1, result.1.len());
     assert_eq!("element5".to_string(), result.1[0]);
 
+    // count greater than the length of the list
+    client.del("mylist").await?;
+    client
+        .rpush("mylist", ["element1", "element2", "element3"])
+        .await?;
+
+    let result: Option<(String, Vec<String>)> =
+        client.lmpop(["unknown", "mylist"], Left, 100).await?;
+    assert_eq!(
+        Some((
+            "mylist".to_owned(),
+            vec![
+                "element1".to_owned(),
+                "element2".to_owned(),
+                "element3".to_owned()
+            ]
+        )),
+        result
+    );
+
+    // all lists are empty or missing
+    let result: Option<(String, Vec<String>)> =
+        client.lmpop(["unknown", "mylist"], Left, 1).await?;
+    assert_eq!(None, result);
+
     Ok(())
 }
 
@@ -376,6 +498,56 @@ async fn lpos() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn lpos_rank_and_count() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.del("mylist").await?;
+
+    client.rpush("mylist", ["a", "b", "c", "b", "b"]).await?;
+
+    // negative rank searches from the tail
+    let pos = client.lpos("mylist", "b", Some(-1), None).await?;
+    assert_eq!(Some(4), pos);
+
+    let pos = client.lpos("mylist", "b", Some(-3), None).await?;
+    assert_eq!(Some(1), pos);
+
+    let pos: Vec<usize> = client
+        .lpos_with_count("mylist", "b", 2, Some(-1), None)
+        .await?;
+    assert_eq!(vec![4, 3], pos);
+
+    // count 0 returns all the matches
+    let pos: Vec<usize> = client.lpos_with_count("mylist", "b", 0, None, None).await?;
+    assert_eq!(vec![1, 3, 4], pos);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn lpos_rank_zero() {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    let result = client.lpos("mylist", "b", Some(0), None).await;
+    assert!(
+        matches!(&result, Err(Error::Client(e)) if e == "LPOS rank must not be 0"),
+        "{result:?}"
+    );
+
+    let result: Result<Vec<usize>> = client
+        .lpos_with_count("mylist", "b", 2, Some(0), None)
+        .await;
+    assert!(matches!(result, Err(Error::Client(_))), "{result:?}");
+
+    assert!(transport.commands().is_empty());
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
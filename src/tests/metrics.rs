@@ -0,0 +1,191 @@
+#[cfg(feature = "pool")]
+use crate::client::PooledClientManager;
+use crate::{
+    client::{Client, Config, IntoConfig, Metrics, MetricsCrateRecorder, RecordingTransport},
+    commands::{PubSubCommands, StringCommands},
+    resp::cmd,
+    tests::{get_default_addr, get_test_client},
+    Error, Result,
+};
+use futures_util::StreamExt;
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use serial_test::serial;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+#[derive(Default)]
+struct MockMetrics {
+    commands: Mutex<Vec<(String, bool)>>,
+    forgotten_commands: Mutex<Vec<String>>,
+    pub_sub_messages: Mutex<Vec<Vec<u8>>>,
+    pool_checkouts: AtomicUsize,
+}
+
+impl Metrics for MockMetrics {
+    fn on_command(&self, name: &str, _duration: Duration, result: std::result::Result<(), &Error>) {
+        self.commands
+            .lock()
+            .unwrap()
+            .push((name.to_owned(), result.is_ok()));
+    }
+
+    fn on_send_and_forget(&self, name: &str, _result: std::result::Result<(), &Error>) {
+        self.forgotten_commands.lock().unwrap().push(name.to_owned());
+    }
+
+    fn on_pub_sub_message(&self, channel: &[u8]) {
+        self.pub_sub_messages.lock().unwrap().push(channel.to_vec());
+    }
+
+    fn on_pool_checkout(&self) {
+        self.pool_checkouts.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn on_command() -> Result<()> {
+    let metrics = Arc::new(MockMetrics::default());
+
+    let mut config = get_default_addr().into_config()?;
+    config.metrics = Some(metrics.clone());
+    let client = Client::connect(config).await?;
+
+    client.set("key", "value").await?;
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    assert_eq!(
+        vec![("SET".to_owned(), true), ("GET".to_owned(), true)],
+        *metrics.commands.lock().unwrap()
+    );
+
+    client.close().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn on_send_and_forget() -> Result<()> {
+    let metrics = Arc::new(MockMetrics::default());
+
+    let config = Config {
+        metrics: Some(metrics.clone()),
+        ..Default::default()
+    };
+    let client = Client::with_transport_and_config(RecordingTransport::new(), config);
+
+    client.send_and_forget(cmd("PUBLISH").arg("mychannel").arg("mymessage"), None)?;
+    client.set("key", "value").await?;
+
+    assert_eq!(
+        vec!["PUBLISH".to_owned()],
+        *metrics.forgotten_commands.lock().unwrap()
+    );
+    assert_eq!(
+        vec![("SET".to_owned(), true)],
+        *metrics.commands.lock().unwrap()
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn on_pub_sub_message() -> Result<()> {
+    let metrics = Arc::new(MockMetrics::default());
+
+    let mut config = get_default_addr().into_config()?;
+    config.metrics = Some(metrics.clone());
+    let pub_sub_client = Client::connect(config).await?;
+    let regular_client = get_test_client().await?;
+
+    let mut pub_sub_stream = pub_sub_client.subscribe("mychannel").await?;
+    regular_client.publish("mychannel", "mymessage").await?;
+    let _message = pub_sub_stream.next().await.unwrap()?;
+
+    assert_eq!(
+        vec![b"mychannel".to_vec()],
+        *metrics.pub_sub_messages.lock().unwrap()
+    );
+
+    pub_sub_stream.close().await?;
+
+    Ok(())
+}
+
+#[cfg(feature = "pool")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn on_pool_checkout() -> Result<()> {
+    let metrics = Arc::new(MockMetrics::default());
+
+    let mut config = get_default_addr().into_config()?;
+    config.metrics = Some(metrics.clone());
+    let manager = PooledClientManager::new(config)?;
+    let pool = crate::bb8::Pool::builder().max_size(1).build(manager).await?;
+
+    // validating the clients of the pool is not a checkout
+    assert_eq!(0, metrics.pool_checkouts.load(Ordering::Relaxed));
+
+    let client = PooledClientManager::checkout(&pool).await?;
+    client.set("key", "value").await?;
+    drop(client);
+    let client = PooledClientManager::checkout(&pool).await?;
+    drop(client);
+
+    // checkouts through bb8 directly are reported too
+    let _client = pool
+        .get()
+        .await
+        .map_err(|e| Error::Client(e.to_string()))?;
+
+    assert_eq!(3, metrics.pool_checkouts.load(Ordering::Relaxed));
+
+    Ok(())
+}
+
+#[test]
+fn metrics_crate_recorder() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+
+    metrics::with_local_recorder(&recorder, || {
+        let metrics = MetricsCrateRecorder;
+        metrics.on_command("GET", Duration::from_millis(2), Ok(()));
+        metrics.on_command("GET", Duration::from_millis(3), Err(&Error::Client("error".to_owned())));
+        metrics.on_send_and_forget("PUBLISH", Ok(()));
+        metrics.on_pub_sub_message(b"mychannel");
+        metrics.on_reconnect();
+        metrics.on_pool_checkout();
+    });
+
+    let snapshot = snapshotter.snapshot().into_vec();
+    let values = |name: &str| {
+        snapshot
+            .iter()
+            .filter(|(key, ..)| key.key().name() == name)
+            .map(|(.., value)| match value {
+                DebugValue::Counter(count) => *count as usize,
+                DebugValue::Histogram(values) => values.len(),
+                DebugValue::Gauge(_) => 0,
+            })
+            .sum::<usize>()
+    };
+
+    assert_eq!(2, values("rustis_commands_total"));
+    assert_eq!(2, values("rustis_command_duration_seconds"));
+    assert_eq!(1, values("rustis_forgotten_commands_total"));
+    assert_eq!(1, values("rustis_pub_sub_messages_total"));
+    assert_eq!(1, values("rustis_reconnections_total"));
+    assert_eq!(1, values("rustis_pool_checkouts_total"));
+}
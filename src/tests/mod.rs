@@ -1,3 +1,4 @@
+mod async_excutor_strategy;
 mod bitmap_commands;
 #[cfg(feature = "redis-bloom")]
 mod bloom_commands;
@@ -6,6 +7,7 @@ mod client;
 mod cluster;
 mod cluster_commands;
 mod command_args;
+mod command_encoder;
 mod command_info_manager;
 mod config;
 mod connection_commands;
@@ -24,6 +26,8 @@ mod hyper_log_log_commands;
 #[cfg(feature = "redis-json")]
 mod json_commands;
 mod list_commands;
+#[cfg(feature = "metrics")]
+mod metrics;
 mod multiplexed_client;
 mod pipeline;
 #[cfg(feature = "pool")]
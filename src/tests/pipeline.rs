@@ -1,9 +1,9 @@
 use crate::{
-    client::BatchPreparedCommand,
+    client::{BatchPreparedCommand, Client, RecordingTransport},
     commands::{FlushingMode, ServerCommands, StringCommands},
     resp::{cmd, Value},
     tests::{get_test_client, get_cluster_test_client},
-    Result,
+    Error, Result,
 };
 use serial_test::serial;
 
@@ -66,4 +66,24 @@ async fn pipeline_on_cluster() -> Result<()> {
     assert_eq!("value2", value2);
 
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn pipeline_conversion_error() -> Result<()> {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    transport.push_reply(":1\r\n");
+    transport.push_reply("$5\r\nhello\r\n");
+
+    let mut pipeline = client.create_pipeline();
+    pipeline.incr("key1").queue();
+    pipeline.get::<_, i64>("key2").queue();
+    let result: Result<(i64, i64)> = pipeline.execute().await;
+    assert!(
+        matches!(&result, Err(Error::Conversion(e)) if e.command == Some("GET")),
+        "{result:?}"
+    );
+
+    Ok(())
+}
@@ -1,12 +1,17 @@
 use crate::{
-    client::{Client, IntoConfig},
+    client::{
+        Client, IntoConfig, PubSubEvent, PubSubMessage, PubSubMessageKind,
+        PubSubOverflowPolicy, PubSubStream, RecordingTransport,
+    },
     commands::{
-        ClientKillOptions, ClusterCommands, ClusterShardResult, ConnectionCommands, FlushingMode,
-        ListCommands, PubSubChannelsOptions, PubSubCommands, ServerCommands, StringCommands,
+        ClientKillOptions, ClientListOptions, ConnectionCommands, FlushingMode, ListCommands,
+        PubSubChannelsOptions, PubSubCommands, ServerCommands, StringCommands,
     },
-    spawn,
+    network::pub_sub_channel,
+    resp::RespBuf,
+    sleep, spawn,
     tests::{get_cluster_test_client, get_default_addr, get_test_client, log_try_init},
-    Result,
+    Error, Result,
 };
 use futures_util::{FutureExt, StreamExt, TryStreamExt};
 use serial_test::serial;
@@ -123,6 +128,84 @@ async fn subscribe_to_multiple_channels() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn subscribe_confirmed_on_all_channels() -> Result<()> {
+    let pub_sub_client = get_test_client().await?;
+    let regular_client = get_test_client().await?;
+
+    // the stream is returned once all the channels are confirmed
+    let mut pub_sub_stream = pub_sub_client
+        .subscribe(["mychannel1", "mychannel2", "mychannel3"])
+        .await?;
+
+    // publish right away, no message must be missed
+    for i in 1..=3 {
+        let num_receivers = regular_client
+            .publish(format!("mychannel{i}"), format!("mymessage{i}"))
+            .await?;
+        assert_eq!(1, num_receivers);
+    }
+
+    for i in 1..=3 {
+        let message = pub_sub_stream.next().await.unwrap()?;
+        assert_eq!(format!("mychannel{i}").into_bytes(), message.channel);
+        assert_eq!(format!("mymessage{i}").into_bytes(), message.payload);
+    }
+
+    pub_sub_stream.close().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn subscribe_rejected() -> Result<()> {
+    let client = get_test_client().await?;
+    let regular_client = get_test_client().await?;
+
+    // an ACL user allowed to subscribe to the "allowed*" channels only
+    client
+        .acl_setuser(
+            "pubsubuser",
+            [
+                "reset",
+                "on",
+                ">pwd",
+                "resetchannels",
+                "&allowed*",
+                "+@pubsub",
+            ],
+        )
+        .await?;
+
+    let pub_sub_client = get_test_client().await?;
+    pub_sub_client.auth(Some("pubsubuser"), "pwd").await?;
+
+    // one channel rejected: the whole subscription fails
+    let result = pub_sub_client
+        .subscribe(["allowed1", "forbidden", "allowed2"])
+        .await;
+    assert!(matches!(result, Err(Error::Redis(_))));
+
+    // the rejected subscription does not disturb the next ones
+    let mut pub_sub_stream = pub_sub_client.subscribe(["allowed1", "allowed2"]).await?;
+    regular_client.publish("allowed1", "mymessage1").await?;
+    regular_client.publish("allowed2", "mymessage2").await?;
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"allowed1".to_vec(), message.channel);
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"allowed2".to_vec(), message.channel);
+
+    pub_sub_stream.close().await?;
+    client.acl_deluser("pubsubuser").await?;
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -183,6 +266,39 @@ async fn subscribe_to_multiple_patterns() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn filter_channel() -> Result<()> {
+    let pub_sub_client = get_test_client().await?;
+    let regular_client = get_test_client().await?;
+
+    // cleanup
+    regular_client.flushdb(FlushingMode::Sync).await?;
+
+    let mut pub_sub_stream = pub_sub_client
+        .psubscribe("news.*")
+        .await?
+        .filter_channel(|channel| channel == b"news.sports");
+
+    regular_client.publish("news.weather", "sunny").await?;
+    regular_client.publish("news.sports", "goal").await?;
+    regular_client.publish("news.politics", "vote").await?;
+    regular_client.publish("news.sports", "match point").await?;
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"news.sports".to_vec(), message.channel);
+    assert_eq!(b"goal".to_vec(), message.payload);
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"news.sports".to_vec(), message.channel);
+    assert_eq!(b"match point".to_vec(), message.payload);
+
+    pub_sub_stream.close().await?;
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -352,23 +468,7 @@ async fn subscribe_to_multiple_shardchannels() -> Result<()> {
 #[serial]
 async fn pub_sub_shardchannels() -> Result<()> {
     let pub_sub_client = get_cluster_test_client().await?;
-
-    // find the master node matching the {1} hashtag
-    let slot = pub_sub_client.cluster_keyslot("{1}").await?;
-    let shard_results: Vec<ClusterShardResult> = pub_sub_client.cluster_shards().await?;
-    let shard_index = shard_results
-        .iter()
-        .position(|s| s.slots[0].0 <= slot && slot <= s.slots[0].1)
-        .unwrap();
-    let shard_result = &shard_results[shard_index];
-    let master_node = shard_result
-        .nodes
-        .iter()
-        .find(|n| n.role == "master")
-        .unwrap();
-
-    let master_client =
-        Client::connect((master_node.ip.clone(), master_node.port.unwrap()).into_config()?).await?;
+    let regular_client = get_cluster_test_client().await?;
 
     let pub_sub_stream = pub_sub_client
         .ssubscribe([
@@ -379,7 +479,7 @@ async fn pub_sub_shardchannels() -> Result<()> {
         ])
         .await?;
 
-    let channels: HashSet<String> = master_client
+    let channels: HashSet<String> = regular_client
         .pub_sub_shardchannels(Default::default())
         .await?;
     assert_eq!(4, channels.len());
@@ -388,7 +488,7 @@ async fn pub_sub_shardchannels() -> Result<()> {
     assert!(channels.contains("mychannel3{1}"));
     assert!(channels.contains("otherchannel{1}"));
 
-    let channels: HashSet<String> = master_client
+    let channels: HashSet<String> = regular_client
         .pub_sub_shardchannels(PubSubChannelsOptions::default().pattern("mychannel*"))
         .await?;
     assert_eq!(3, channels.len());
@@ -398,7 +498,7 @@ async fn pub_sub_shardchannels() -> Result<()> {
 
     pub_sub_stream.close().await?;
 
-    let channels: HashSet<String> = master_client
+    let channels: HashSet<String> = regular_client
         .pub_sub_shardchannels(Default::default())
         .await?;
     assert_eq!(0, channels.len());
@@ -411,25 +511,9 @@ async fn pub_sub_shardchannels() -> Result<()> {
 #[serial]
 async fn pub_sub_shardnumsub() -> Result<()> {
     let pub_sub_client = get_cluster_test_client().await?;
+    let regular_client = get_cluster_test_client().await?;
 
-    // find the master node matching the {1} hashtag
-    let slot = pub_sub_client.cluster_keyslot("{1}").await?;
-    let shard_results: Vec<ClusterShardResult> = pub_sub_client.cluster_shards().await?;
-    let shard_index = shard_results
-        .iter()
-        .position(|s| s.slots[0].0 <= slot && slot <= s.slots[0].1)
-        .unwrap();
-    let shard_result = &shard_results[shard_index];
-    let master_node = shard_result
-        .nodes
-        .iter()
-        .find(|n| n.role == "master")
-        .unwrap();
-
-    let master_client =
-        Client::connect((master_node.ip.clone(), master_node.port.unwrap()).into_config()?).await?;
-
-    let num_sub: HashMap<String, usize> = master_client
+    let num_sub: HashMap<String, usize> = regular_client
         .pub_sub_shardnumsub(["mychannel1{1}", "mychannel2{1}"])
         .await?;
     assert_eq!(2, num_sub.len());
@@ -440,7 +524,7 @@ async fn pub_sub_shardnumsub() -> Result<()> {
         .ssubscribe(["mychannel1{1}", "mychannel2{1}"])
         .await?;
 
-    let num_sub: HashMap<String, usize> = master_client
+    let num_sub: HashMap<String, usize> = regular_client
         .pub_sub_shardnumsub(["mychannel1{1}", "mychannel2{1}"])
         .await?;
     assert_eq!(2, num_sub.len());
@@ -790,4 +874,593 @@ async fn subscribe_twice() -> Result<()> {
     assert!(pub_sub_stream.ssubscribe("mychannel").await.is_err());
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn message_kinds() -> Result<()> {
+    let pub_sub_client = get_test_client().await?;
+    let regular_client = get_test_client().await?;
+
+    // cleanup
+    regular_client.flushdb(FlushingMode::Sync).await?;
+
+    let mut pub_sub_stream = pub_sub_client.subscribe("mychannel").await?;
+    pub_sub_stream.psubscribe("other*").await?;
+    pub_sub_stream.ssubscribe("myshardchannel").await?;
+
+    regular_client.publish("mychannel", "mymessage").await?;
+    regular_client.publish("otherchannel", "othermessage").await?;
+    regular_client
+        .spublish("myshardchannel", "myshardmessage")
+        .await?;
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(PubSubMessageKind::Message, message.kind);
+    assert_eq!(b"mychannel".to_vec(), message.channel);
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(PubSubMessageKind::PMessage, message.kind);
+    assert_eq!(b"other*".to_vec(), message.pattern);
+    assert_eq!(b"otherchannel".to_vec(), message.channel);
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(PubSubMessageKind::SMessage, message.kind);
+    assert_eq!(b"myshardchannel".to_vec(), message.channel);
+
+    pub_sub_stream.close().await?;
+
+    Ok(())
+}
+
+#[test]
+fn message_kind_from_resp() -> Result<()> {
+    let message: PubSubMessage =
+        RespBuf::from_slice(b">3\r\n$7\r\nmessage\r\n$2\r\nch\r\n$3\r\nmsg\r\n").to()?;
+    assert_eq!(PubSubMessageKind::Message, message.kind);
+    assert_eq!(b"ch".to_vec(), message.channel);
+    assert_eq!(b"msg".to_vec(), message.payload);
+
+    let message: PubSubMessage = RespBuf::from_slice(
+        b">4\r\n$8\r\npmessage\r\n$3\r\nch*\r\n$2\r\nch\r\n$3\r\nmsg\r\n",
+    )
+    .to()?;
+    assert_eq!(PubSubMessageKind::PMessage, message.kind);
+    assert_eq!(b"ch*".to_vec(), message.pattern);
+    assert_eq!(b"ch".to_vec(), message.channel);
+    assert_eq!(b"msg".to_vec(), message.payload);
+
+    let message: PubSubMessage =
+        RespBuf::from_slice(b">3\r\n$8\r\nsmessage\r\n$2\r\nch\r\n$3\r\nmsg\r\n").to()?;
+    assert_eq!(PubSubMessageKind::SMessage, message.kind);
+    assert_eq!(b"ch".to_vec(), message.channel);
+    assert_eq!(b"msg".to_vec(), message.payload);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn subscription_events() -> Result<()> {
+    let pub_sub_client = get_test_client().await?;
+    let regular_client = get_test_client().await?;
+
+    // cleanup
+    regular_client.flushdb(FlushingMode::Sync).await?;
+
+    let mut pub_sub_stream = pub_sub_client.create_pub_sub().with_events();
+    pub_sub_stream
+        .subscribe(["mychannel1", "mychannel2"])
+        .await?;
+
+    regular_client.publish("mychannel1", "mymessage").await?;
+
+    let event = pub_sub_stream.next().await.unwrap()?;
+    assert!(
+        matches!(event, PubSubEvent::Subscribed { channel, count: 1 } if channel == b"mychannel1")
+    );
+    let event = pub_sub_stream.next().await.unwrap()?;
+    assert!(
+        matches!(event, PubSubEvent::Subscribed { channel, count: 2 } if channel == b"mychannel2")
+    );
+    let event = pub_sub_stream.next().await.unwrap()?;
+    assert!(matches!(event, PubSubEvent::Message(message) if message.payload == b"mymessage"));
+
+    pub_sub_stream.unsubscribe("mychannel1").await?;
+
+    let event = pub_sub_stream.next().await.unwrap()?;
+    assert!(
+        matches!(event, PubSubEvent::Unsubscribed { channel, count: 1 } if channel == b"mychannel1")
+    );
+
+    pub_sub_stream.close().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn subscription_events_offline() -> Result<()> {
+    let subscribed = || {
+        Ok(RespBuf::from_slice(
+            b">3\r\n$9\r\nsubscribe\r\n$9\r\nmychannel\r\n:1\r\n",
+        ))
+    };
+    let message = || {
+        Ok(RespBuf::from_slice(
+            b">3\r\n$7\r\nmessage\r\n$9\r\nmychannel\r\n$9\r\nmymessage\r\n",
+        ))
+    };
+
+    let (sender, receiver) = pub_sub_channel(10, PubSubOverflowPolicy::Block);
+    let client = Client::with_transport(RecordingTransport::new());
+    let mut pub_sub_stream = PubSubStream::new(sender.clone(), receiver, client).with_events();
+    assert!(sender.events_enabled());
+
+    sender.send(subscribed()).await?;
+    sender.send(message()).await?;
+
+    // the confirmation is yielded before the first message
+    let event = pub_sub_stream.next().await.unwrap()?;
+    assert!(
+        matches!(event, PubSubEvent::Subscribed { channel, count: 1 } if channel == b"mychannel")
+    );
+    let event = pub_sub_stream.next().await.unwrap()?;
+    assert!(matches!(event, PubSubEvent::Message(message) if message.payload == b"mymessage"));
+
+    // by default, confirmations are skipped
+    let mut pub_sub_stream = pub_sub_stream.into_inner();
+    assert!(!sender.events_enabled());
+
+    sender.send(subscribed()).await?;
+    sender.send(message()).await?;
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"mymessage".to_vec(), message.payload);
+    assert!(pub_sub_stream.next().now_or_never().is_none());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn try_next_now() -> Result<()> {
+    let pub_sub_client = get_test_client().await?;
+    let regular_client = get_test_client().await?;
+
+    // cleanup
+    regular_client.flushdb(FlushingMode::Sync).await?;
+
+    let mut pub_sub_stream = pub_sub_client.subscribe("mychannel").await?;
+    assert!(pub_sub_stream.try_next_now()?.is_none());
+
+    regular_client.publish("mychannel", "mymessage").await?;
+
+    // let the network handler receive the message
+    sleep(std::time::Duration::from_millis(100)).await;
+
+    let message = pub_sub_stream.try_next_now()?.unwrap();
+    assert_eq!(b"mychannel".to_vec(), message.channel);
+    assert_eq!(b"mymessage".to_vec(), message.payload);
+    assert!(pub_sub_stream.try_next_now()?.is_none());
+
+    pub_sub_stream.close().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn pub_sub_keepalive() -> Result<()> {
+    log_try_init();
+
+    let mut config = get_default_addr().into_config()?;
+    config.pub_sub_keepalive = Some(std::time::Duration::from_millis(100));
+    let pub_sub_client = Client::connect(config).await?;
+    let regular_client = get_test_client().await?;
+
+    // cleanup
+    regular_client.flushdb(FlushingMode::Sync).await?;
+
+    let pub_sub_client_id = pub_sub_client.client_id().await?;
+    let mut pub_sub_stream = pub_sub_client.subscribe("mychannel").await?;
+
+    // stay idle
+    sleep(std::time::Duration::from_millis(1500)).await;
+
+    let client_list = regular_client
+        .client_list(ClientListOptions::default().client_ids(pub_sub_client_id))
+        .await?;
+    assert_eq!(1, client_list.client_infos.len());
+    assert_eq!("ping", client_list.client_infos[0].cmd);
+    assert_eq!(0, client_list.client_infos[0].idle);
+    assert_eq!(1, client_list.client_infos[0].sub);
+
+    pub_sub_stream.ping().await?;
+
+    regular_client.publish("mychannel", "mymessage").await?;
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"mychannel".to_vec(), message.channel);
+    assert_eq!(b"mymessage".to_vec(), message.payload);
+
+    pub_sub_stream.close().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn publish_to_at_least() -> Result<()> {
+    let pub_sub_client = get_test_client().await?;
+    let regular_client = get_test_client().await?;
+
+    // cleanup
+    regular_client.flushdb(FlushingMode::Sync).await?;
+
+    let (pub_sub_stream, num_receivers) = futures_util::join!(
+        pub_sub_client.subscribe("mychannel"),
+        regular_client.publish_to_at_least(
+            "mychannel",
+            "mymessage",
+            1,
+            std::time::Duration::from_secs(1)
+        )
+    );
+    let mut pub_sub_stream = pub_sub_stream?;
+    assert_eq!(1, num_receivers?);
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"mychannel".to_vec(), message.channel);
+    assert_eq!(b"mymessage".to_vec(), message.payload);
+
+    // nobody else is listening: give up after the timeout
+    let num_receivers = regular_client
+        .publish_to_at_least(
+            "mychannel",
+            "mymessage",
+            2,
+            std::time::Duration::from_millis(50),
+        )
+        .await?;
+    assert_eq!(1, num_receivers);
+
+    pub_sub_stream.close().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn subscribe_once() -> Result<()> {
+    let pub_sub_client = get_test_client().await?;
+    let regular_client = get_test_client().await?;
+
+    // cleanup
+    regular_client.flushdb(FlushingMode::Sync).await?;
+
+    let (message, num_receivers) = futures_util::join!(
+        pub_sub_client.subscribe_once("mychannel", std::time::Duration::from_secs(1)),
+        regular_client.publish_to_at_least(
+            "mychannel",
+            "mymessage",
+            1,
+            std::time::Duration::from_secs(1)
+        )
+    );
+    assert_eq!(1, num_receivers?);
+    let message = message?.unwrap();
+    assert_eq!(b"mychannel".to_vec(), message.channel);
+    assert_eq!(b"mymessage".to_vec(), message.payload);
+
+    // the channel has been unsubscribed
+    let num_receivers = regular_client.publish("mychannel", "mymessage").await?;
+    assert_eq!(0, num_receivers);
+
+    // nothing published: give up after the timeout
+    let message = pub_sub_client
+        .subscribe_once("mychannel", std::time::Duration::from_millis(50))
+        .await?;
+    assert!(message.is_none());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn slow_consumer() -> Result<()> {
+    log_try_init();
+
+    let mut config = get_default_addr().into_config()?;
+    config.pub_sub_buffer_size = 10;
+    let pub_sub_client = Client::connect(config).await?;
+    let regular_client = get_test_client().await?;
+
+    // cleanup
+    regular_client.flushdb(FlushingMode::Sync).await?;
+
+    let mut pub_sub_stream = pub_sub_client.subscribe("mychannel").await?;
+
+    for i in 0..100 {
+        regular_client.publish("mychannel", i).await?;
+    }
+
+    // let the network handler receive all the messages before consuming them
+    sleep(std::time::Duration::from_millis(100)).await;
+
+    assert_eq!(90, pub_sub_stream.dropped_count());
+
+    for i in 90..100 {
+        let message = pub_sub_stream.next().await.unwrap()?;
+        assert_eq!(i.to_string().into_bytes(), message.payload);
+    }
+
+    assert!(pub_sub_stream.next().now_or_never().is_none());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn pub_sub_overflow_policies() -> Result<()> {
+    let message = |payload: &str| {
+        Ok(RespBuf::from_slice(
+            format!(">3\r\n$7\r\nmessage\r\n$9\r\nmychannel\r\n$1\r\n{payload}\r\n")
+                .as_bytes(),
+        ))
+    };
+
+    // drop oldest
+    let (sender, mut receiver) = pub_sub_channel(2, PubSubOverflowPolicy::DropOldest);
+    for payload in ["1", "2", "3"] {
+        sender.send(message(payload)).await?;
+    }
+    assert_eq!(1, receiver.dropped_count());
+    let resp_buf = receiver.next().await.unwrap()?;
+    assert_eq!(message("2")?.as_bytes(), resp_buf.as_bytes());
+    let resp_buf = receiver.next().await.unwrap()?;
+    assert_eq!(message("3")?.as_bytes(), resp_buf.as_bytes());
+
+    // errors are never dropped
+    let (sender, mut receiver) = pub_sub_channel(2, PubSubOverflowPolicy::DropOldest);
+    sender.send(Err(Error::Client("error".to_owned()))).await?;
+    for payload in ["1", "2"] {
+        sender.send(message(payload)).await?;
+    }
+    assert_eq!(1, receiver.dropped_count());
+    assert!(matches!(receiver.next().await, Some(Err(Error::Client(_)))));
+    let resp_buf = receiver.next().await.unwrap()?;
+    assert_eq!(message("2")?.as_bytes(), resp_buf.as_bytes());
+
+    let (sender, mut receiver) = pub_sub_channel(1, PubSubOverflowPolicy::DropOldest);
+    sender.send(Err(Error::Client("error".to_owned()))).await?;
+    sender.send(message("1")).await?;
+    sender.send(Err(Error::Client("error".to_owned()))).await?;
+    assert_eq!(1, receiver.dropped_count());
+    assert!(matches!(receiver.next().await, Some(Err(Error::Client(_)))));
+    assert!(matches!(receiver.next().await, Some(Err(Error::Client(_)))));
+
+    let (sender, mut receiver) = pub_sub_channel(1, PubSubOverflowPolicy::DropNewest);
+    sender.send(message("1")).await?;
+    sender.send(Err(Error::Client("error".to_owned()))).await?;
+    assert_eq!(0, receiver.dropped_count());
+    let resp_buf = receiver.next().await.unwrap()?;
+    assert_eq!(message("1")?.as_bytes(), resp_buf.as_bytes());
+    assert!(matches!(receiver.next().await, Some(Err(Error::Client(_)))));
+
+    // drop newest
+    let (sender, mut receiver) = pub_sub_channel(2, PubSubOverflowPolicy::DropNewest);
+    for payload in ["1", "2", "3"] {
+        sender.send(message(payload)).await?;
+    }
+    assert_eq!(1, receiver.dropped_count());
+    let resp_buf = receiver.next().await.unwrap()?;
+    assert_eq!(message("1")?.as_bytes(), resp_buf.as_bytes());
+    let resp_buf = receiver.next().await.unwrap()?;
+    assert_eq!(message("2")?.as_bytes(), resp_buf.as_bytes());
+
+    // block
+    let (sender, mut receiver) = pub_sub_channel(1, PubSubOverflowPolicy::Block);
+    sender.send(message("1")).await?;
+    let mut send = Box::pin(sender.send(message("2")));
+    assert!((&mut send).now_or_never().is_none());
+    let resp_buf = receiver.next().await.unwrap()?;
+    assert_eq!(message("1")?.as_bytes(), resp_buf.as_bytes());
+    send.await?;
+    assert_eq!(0, receiver.dropped_count());
+    let resp_buf = receiver.next().await.unwrap()?;
+    assert_eq!(message("2")?.as_bytes(), resp_buf.as_bytes());
+
+    // receiver dropped
+    drop(receiver);
+    assert!(sender.send(message("3")).await.is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn filter_channel_offline() -> Result<()> {
+    let message = |channel: &str, payload: &str| {
+        Ok(RespBuf::from_slice(
+            format!(
+                ">4\r\n$8\r\npmessage\r\n$6\r\nnews.*\r\n${}\r\n{channel}\r\n${}\r\n{payload}\r\n",
+                channel.len(),
+                payload.len()
+            )
+            .as_bytes(),
+        ))
+    };
+
+    let (sender, receiver) = pub_sub_channel(10, PubSubOverflowPolicy::Block);
+    let client = Client::with_transport(RecordingTransport::new());
+    let mut pub_sub_stream = PubSubStream::new(sender.clone(), receiver, client)
+        .filter_channel(|channel| channel == b"news.sports");
+
+    sender.send(message("news.weather", "sunny")).await?;
+    sender.send(message("news.sports", "goal")).await?;
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(PubSubMessageKind::PMessage, message.kind);
+    assert_eq!(b"news.sports".to_vec(), message.channel);
+    assert_eq!(b"goal".to_vec(), message.payload);
+    assert!(pub_sub_stream.next().now_or_never().is_none());
+
+    Ok(())
+}
+
+/// Fake server confirming a subscription only after the client stopped waiting for it
+#[cfg(feature = "tokio-runtime")]
+#[tokio::test]
+async fn subscribe_timeout() -> Result<()> {
+    use crate::tests::{accept_fake_connection, bind_fake_server, read_command};
+    use tokio::io::AsyncWriteExt;
+
+    let (listener, port) = bind_fake_server().await?;
+
+    let server = spawn(async move {
+        let mut socket = accept_fake_connection(&listener).await?;
+
+        // the subscription is confirmed after the client has unsubscribed
+        // and subscribed to another channel
+        read_command(&mut socket, b"SUBSCRIBE").await?;
+        let buffer = read_command(&mut socket, b"otherchannel").await?;
+        let unsubscribe = b"UNSUBSCRIBE\r\n$9\r\nmychannel\r\n";
+        assert!(buffer.windows(unsubscribe.len()).any(|w| w == unsubscribe));
+        socket
+            .write_all(b">3\r\n$9\r\nsubscribe\r\n$9\r\nmychannel\r\n:1\r\n")
+            .await?;
+        socket
+            .write_all(b">3\r\n$11\r\nunsubscribe\r\n$9\r\nmychannel\r\n:0\r\n")
+            .await?;
+        socket
+            .write_all(b">3\r\n$9\r\nsubscribe\r\n$12\r\notherchannel\r\n:1\r\n")
+            .await?;
+        socket
+            .write_all(b">3\r\n$7\r\nmessage\r\n$12\r\notherchannel\r\n$5\r\nvalue\r\n")
+            .await?;
+
+        // keep the connection open until the end of the test
+        Ok::<_, Error>(socket)
+    });
+
+    let mut config = format!("127.0.0.1:{port}").into_config()?;
+    config.subscribe_timeout = Some(std::time::Duration::from_millis(50));
+    let client = Client::connect(config).await?;
+
+    let result = client.subscribe("mychannel").await;
+    assert!(matches!(result, Err(Error::Timeout(_))));
+
+    // the late confirmations do not disturb the next subscription
+    let mut pub_sub_stream = client.subscribe("otherchannel").await?;
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"otherchannel".to_vec(), message.channel);
+    assert_eq!(b"value".to_vec(), message.payload);
+
+    let _socket = server.await??;
+
+    Ok(())
+}
+
+/// Fake server unsubscribing a shard channel by itself, like after a slot migration
+#[cfg(feature = "tokio-runtime")]
+#[tokio::test]
+async fn shardchannel_moved_without_auto_resubscribe() -> Result<()> {
+    use crate::tests::{accept_fake_connection, bind_fake_server, read_command};
+    use tokio::io::AsyncWriteExt;
+
+    let (listener, port) = bind_fake_server().await?;
+
+    let server = spawn(async move {
+        let mut socket = accept_fake_connection(&listener).await?;
+
+        read_command(&mut socket, b"SSUBSCRIBE").await?;
+        socket
+            .write_all(b">3\r\n$10\r\nssubscribe\r\n$9\r\nmychannel\r\n:1\r\n")
+            .await?;
+        socket
+            .write_all(b">3\r\n$12\r\nsunsubscribe\r\n$9\r\nmychannel\r\n:0\r\n")
+            .await?;
+
+        // keep the connection open until the end of the test
+        Ok::<_, Error>(socket)
+    });
+
+    let mut config = format!("127.0.0.1:{port}").into_config()?;
+    config.auto_resubscribe = false;
+    let client = Client::connect(config).await?;
+
+    let mut pub_sub_stream = client.ssubscribe("mychannel").await?;
+    let result = pub_sub_stream.next().await;
+    assert!(
+        matches!(&result, Some(Err(Error::Client(e))) if e.contains("mychannel")),
+        "{:?}",
+        result.map(|r| r.map(|m| m.channel))
+    );
+
+    let _socket = server.await??;
+
+    Ok(())
+}
+
+/// Fake server sending a malformed reply on a subscribed connection,
+/// then accepting the reconnection and resubscription
+#[cfg(feature = "tokio-runtime")]
+#[tokio::test]
+async fn recover_from_protocol_error() -> Result<()> {
+    use crate::tests::{accept_fake_connection, bind_fake_server, read_command};
+    use tokio::io::AsyncWriteExt;
+
+    let (listener, port) = bind_fake_server().await?;
+
+    let server = spawn(async move {
+        for payload in ["value1", "value2"] {
+            let mut socket = accept_fake_connection(&listener).await?;
+
+            read_command(&mut socket, b"SUBSCRIBE").await?;
+            socket
+                .write_all(b">3\r\n$9\r\nsubscribe\r\n$9\r\nmychannel\r\n:1\r\n")
+                .await?;
+            socket
+                .write_all(
+                    format!(">3\r\n$7\r\nmessage\r\n$9\r\nmychannel\r\n$6\r\n{payload}\r\n")
+                        .as_bytes(),
+                )
+                .await?;
+
+            if payload == "value1" {
+                // malformed reply
+                socket.write_all(b"?garbage\r\n").await?;
+            } else {
+                // keep the connection open until the end of the test
+                return Ok(socket);
+            }
+        }
+        Err(crate::Error::Client("unreachable".to_owned()))
+    });
+
+    let client = Client::connect(format!("127.0.0.1:{port}")).await?;
+    let mut pub_sub_stream = client.subscribe("mychannel").await?;
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"value1".to_vec(), message.payload);
+
+    // the error is yielded once...
+    let result = pub_sub_stream.next().await.unwrap();
+    assert!(result.is_err());
+
+    // ...then the stream resumes on the new connection
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"mychannel".to_vec(), message.channel);
+    assert_eq!(b"value2".to_vec(), message.payload);
+
+    let _socket = server.await??;
+
+    Ok(())
+}
@@ -1,9 +1,14 @@
 use crate::{
-    resp::RespDeserializer, tests::log_try_init, Error, RedisError, RedisErrorKind, Result,
+    resp::{RespDeserializer, Timestamp},
+    tests::log_try_init,
+    Error, RedisError, RedisErrorKind, Result,
 };
 use serde::Deserialize;
 use smallvec::SmallVec;
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    time::Duration,
+};
 
 fn deserialize<'a, T>(str: &'a str) -> Result<T>
 where
@@ -94,6 +99,19 @@ fn integer() -> Result<()> {
     let result: u64 = deserialize("*1\r\n:12\r\n")?; // [12]
     assert_eq!(12, result);
 
+    // out of range
+    let result: Result<i32> = deserialize(":2147483648\r\n");
+    assert!(
+        matches!(&result, Err(Error::Client(e)) if e.contains("2147483648 into i32")),
+        "{result:?}"
+    );
+
+    let result: i64 = deserialize(":2147483648\r\n")?;
+    assert_eq!(2147483648, result);
+
+    let result: Result<u64> = deserialize(":-1\r\n");
+    assert!(matches!(result, Err(Error::Client(_))));
+
     Ok(())
 }
 
@@ -369,6 +387,29 @@ fn tuple() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn array() -> Result<()> {
+    log_try_init();
+
+    let result: [f64; 2] = deserialize("*2\r\n,1.5\r\n,2.5\r\n")?; // [1.5, 2.5]
+    assert_eq!([1.5, 2.5], result);
+
+    let result: Vec<Option<[f64; 2]>> =
+        deserialize("*2\r\n*2\r\n,1.5\r\n,2.5\r\n_\r\n")?; // [[1.5, 2.5], nil]
+    assert_eq!(vec![Some([1.5, 2.5]), None], result);
+
+    let result: Result<[f64; 2]> = deserialize("*3\r\n,1.5\r\n,2.5\r\n,3.5\r\n"); // [1.5, 2.5, 3.5]
+    assert!(matches!(result, Err(Error::Client(_))));
+
+    let result: Result<[f64; 2]> = deserialize("*1\r\n,1.5\r\n"); // [1.5]
+    assert!(matches!(result, Err(Error::Client(_))));
+
+    let result: Result<(i32, i32)> = deserialize("*3\r\n:12\r\n:13\r\n:14\r\n"); // [12, 13, 14]
+    assert!(matches!(result, Err(Error::Client(_))));
+
+    Ok(())
+}
+
 #[test]
 fn tuple_struct() -> Result<()> {
     log_try_init();
@@ -419,6 +460,78 @@ fn map() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn key_value_collections() -> Result<()> {
+    log_try_init();
+
+    let expected = vec![("b".to_owned(), 2), ("a".to_owned(), 1), ("c".to_owned(), 3)];
+
+    // RESP2 flat array
+    let resp2 = "*6\r\n$1\r\nb\r\n:2\r\n$1\r\na\r\n:1\r\n$1\r\nc\r\n:3\r\n";
+    // RESP3 map
+    let resp3 = "%3\r\n$1\r\nb\r\n:2\r\n$1\r\na\r\n:1\r\n$1\r\nc\r\n:3\r\n";
+
+    for resp in [resp2, resp3] {
+        let result: Vec<(String, i32)> = deserialize(resp)?;
+        assert_eq!(expected, result);
+
+        let result: BTreeMap<String, i32> = deserialize(resp)?;
+        assert_eq!(
+            vec![("a".to_owned(), 1), ("b".to_owned(), 2), ("c".to_owned(), 3)],
+            result.into_iter().collect::<Vec<_>>()
+        );
+
+        let result: HashMap<String, i32> = deserialize(resp)?;
+        assert_eq!(3, result.len());
+        assert_eq!(Some(&2), result.get("b"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn vec_deque() -> Result<()> {
+    log_try_init();
+
+    let result: VecDeque<i32> = deserialize("*3\r\n:12\r\n:13\r\n:14\r\n")?; // [12, 13, 14]
+    assert_eq!(VecDeque::from([12, 13, 14]), result);
+
+    let result: VecDeque<i32> = deserialize("*0\r\n")?; // []
+    assert!(result.is_empty());
+
+    Ok(())
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn index_map() -> Result<()> {
+    use indexmap::{IndexMap, IndexSet};
+
+    log_try_init();
+
+    let expected = vec![
+        ("b".to_owned(), 2),
+        ("a".to_owned(), 1),
+        ("c".to_owned(), 3),
+    ];
+
+    // RESP2 flat array
+    let resp2 = "*6\r\n$1\r\nb\r\n:2\r\n$1\r\na\r\n:1\r\n$1\r\nc\r\n:3\r\n";
+    // RESP3 map
+    let resp3 = "%3\r\n$1\r\nb\r\n:2\r\n$1\r\na\r\n:1\r\n$1\r\nc\r\n:3\r\n";
+
+    // the order of the reply is preserved
+    for resp in [resp2, resp3] {
+        let result: IndexMap<String, i32> = deserialize(resp)?;
+        assert_eq!(expected, result.into_iter().collect::<Vec<_>>());
+    }
+
+    let result: IndexSet<String> = deserialize("*3\r\n$1\r\nb\r\n$1\r\na\r\n$1\r\nc\r\n")?;
+    assert_eq!(vec!["b", "a", "c"], result.into_iter().collect::<Vec<_>>());
+
+    Ok(())
+}
+
 #[test]
 fn _struct() -> Result<()> {
     log_try_init();
@@ -560,3 +673,41 @@ fn array_chunks() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn timestamp() -> Result<()> {
+    log_try_init();
+
+    let result: Timestamp = deserialize(":1700000000\r\n")?; // integer seconds
+    assert_eq!(Duration::from_secs(1700000000), result.since_epoch());
+
+    let result: Timestamp = deserialize("*2\r\n$10\r\n1700000000\r\n$6\r\n250000\r\n")?; // TIME reply
+    assert_eq!(Duration::from_millis(1700000000250), result.since_epoch());
+    assert_eq!(1700000000, result.unix_timestamp());
+    assert_eq!(250000, result.subsec_micros());
+
+    let result: Result<Timestamp> = deserialize(":-2\r\n");
+    assert!(result.is_err());
+
+    // the TIME reply has exactly 2 elements
+    let result: Result<Timestamp> = deserialize("*1\r\n$10\r\n1700000000\r\n");
+    assert!(result.is_err());
+    let result: Result<Timestamp> =
+        deserialize("*3\r\n$10\r\n1700000000\r\n$6\r\n250000\r\n:1\r\n");
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn timestamp_to_chrono() -> Result<()> {
+    log_try_init();
+
+    let result: Timestamp = deserialize("*2\r\n$10\r\n1700000000\r\n$6\r\n250000\r\n")?;
+    let date_time: chrono::DateTime<chrono::Utc> = result.into();
+    assert_eq!(1700000000, date_time.timestamp());
+    assert_eq!(250, date_time.timestamp_subsec_millis());
+
+    Ok(())
+}
@@ -1,8 +1,8 @@
 use crate::{
-    client::ClientPreparedCommand,
+    client::{Client, ClientPreparedCommand, RecordingTransport, ScriptRegistry},
     commands::{
-        CallBuilder, FlushingMode, FunctionListOptions, LibraryInfo, ScriptingCommands,
-        ServerCommands, StringCommands,
+        CallBuilder, ClientKillOptions, ConnectionCommands, FlushingMode, FunctionListOptions,
+        LibraryInfo, ScriptingCommands, ServerCommands, StringCommands,
     },
     sleep, spawn,
     tests::get_test_client,
@@ -394,3 +394,72 @@ async fn script_kill() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn script_registry() -> Result<()> {
+    let client = get_test_client().await?;
+    let other_client = get_test_client().await?;
+    client.script_flush(FlushingMode::Sync).await?;
+
+    let registry = ScriptRegistry::new(client.clone());
+    let sha1 = registry.load("echo", "return ARGV[1]").await?;
+    assert_eq!(Some(sha1), registry.sha1("echo"));
+    registry
+        .load("incr", "return redis.call('INCRBY', KEYS[1], ARGV[1])")
+        .await?;
+    client.set("counter", 1).await?;
+
+    let value: String = registry.evalsha("echo", [] as [&str; 0], "hello").await?;
+    assert_eq!("hello", value);
+
+    // the script cache is flushed and the registry client reconnects
+    other_client.script_flush(FlushingMode::Sync).await?;
+    let client_id = client.client_id().await?;
+    other_client
+        .client_kill(ClientKillOptions::default().id(client_id))
+        .await?;
+
+    let value: String = registry.evalsha("echo", [] as [&str; 0], "hello").await?;
+    assert_eq!("hello", value);
+    let value: i64 = registry.evalsha("incr", "counter", 2).await?;
+    assert_eq!(3, value);
+
+    let result = registry
+        .evalsha::<_, _, _, _, String>("unknown", [] as [&str; 0], [] as [&str; 0])
+        .await;
+    assert!(matches!(result, Err(Error::Client(_))));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn script_registry_noscript() -> Result<()> {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+    let registry = ScriptRegistry::new(client);
+
+    let sha1 = "a42059b356c875f0717db19a51f6aaca9ae659ea";
+    let sha1_reply = format!("${}\r\n{sha1}\r\n", sha1.len());
+    transport.push_reply(sha1_reply.clone());
+    registry.load("twelve", "return 12").await?;
+
+    transport.push_reply("-NOSCRIPT No matching script.\r\n");
+    transport.push_reply(sha1_reply);
+    transport.push_reply(":12\r\n");
+    let value: i64 = registry
+        .evalsha("twelve", [] as [&str; 0], [] as [&str; 0])
+        .await?;
+    assert_eq!(12, value);
+
+    let script_load = b"*3\r\n$6\r\nSCRIPT\r\n$4\r\nLOAD\r\n$9\r\nreturn 12\r\n".to_vec();
+    let evalsha = format!("*3\r\n$7\r\nEVALSHA\r\n$40\r\n{sha1}\r\n$1\r\n0\r\n").into_bytes();
+    assert_eq!(
+        vec![script_load.clone(), evalsha.clone(), script_load, evalsha],
+        transport.commands()
+    );
+
+    Ok(())
+}
@@ -1,20 +1,27 @@
 use crate::{
-    client::Client,
+    client::{Client, IntoConfig, RecordingTransport},
     commands::{
-        AclCatOptions, AclDryRunOptions, AclGenPassOptions, AclLogOptions, BlockingCommands,
-        ClientInfo, ClientKillOptions, CommandDoc, CommandHistogram, CommandListOptions,
-        ConnectionCommands, FailOverOptions, FlushingMode, InfoSection, LatencyHistoryEvent,
-        MemoryUsageOptions, ModuleInfo, ModuleLoadOptions, ReplicaOfOptions, RoleResult,
-        ServerCommands, SlowLogOptions, StringCommands,
+        AclCatOptions, AclDryRunOptions, AclGenPassOptions, AclLogOptions, AclUser,
+        BlockingCommands, ClientInfo, ClientKillOptions, CommandDoc, CommandHistogram,
+        CommandListOptions, ConnectionCommands, FailOverOptions, FlushingMode, InfoSection,
+        LatencyHistoryEvent, MemoryUsageOptions, ModuleInfo, ModuleLoadOptions, PingOptions,
+        ReplicaOfOptions, RoleResult, ServerCommands, ShutdownOptions, SlowLogOptions,
+        StringCommands,
     },
     resp::{cmd, Value},
-    spawn,
-    tests::{get_sentinel_test_client, get_test_client},
+    sleep, spawn,
+    tests::{
+        get_default_addr, get_default_host, get_default_port, get_sentinel_test_client,
+        get_test_client,
+    },
     Error, RedisError, RedisErrorKind, Result,
 };
 use futures_util::StreamExt;
 use serial_test::serial;
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, SystemTime},
+};
 
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
@@ -118,6 +125,56 @@ async fn acl_getuser() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn acl_restricted_user() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushall(FlushingMode::Sync).await?;
+
+    client
+        .acl_setuser(
+            "restricted",
+            ["on", ">pwd", "~*", "&*", "+get", "+acl|whoami"],
+        )
+        .await?;
+
+    let user: AclUser = client.acl_getuser("restricted").await?;
+    assert!(user.flags.iter().any(|f| f == "on"));
+    assert_eq!(1, user.passwords.len());
+    assert_eq!("-@all +get +acl|whoami", user.commands);
+    assert_eq!("~*", user.keys);
+    assert_eq!("&*", user.channels);
+    assert!(user.selectors.is_empty());
+
+    let restricted_client = Client::connect(format!(
+        "redis://restricted:pwd@{}:{}",
+        get_default_host(),
+        get_default_port()
+    ))
+    .await?;
+
+    let username: String = restricted_client.acl_whoami().await?;
+    assert_eq!("restricted", username);
+
+    let value: Option<String> = restricted_client.get("key").await?;
+    assert_eq!(None, value);
+
+    let result = restricted_client.set("key", "value").await;
+    assert!(matches!(
+        result,
+        Err(Error::Redis(RedisError {
+            kind: RedisErrorKind::NoPerm,
+            description: _
+        }))
+    ));
+
+    restricted_client.close().await?;
+    client.acl_deluser("restricted").await?;
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -494,6 +551,9 @@ async fn dbsize() -> Result<()> {
     let client = get_test_client().await?;
     client.flushdb(FlushingMode::Sync).await?;
 
+    let size = client.dbsize().await?;
+    assert_eq!(0, size);
+
     client
         .mset([("key1", "value1"), ("key2", "value2")])
         .await?;
@@ -501,6 +561,10 @@ async fn dbsize() -> Result<()> {
     let size = client.dbsize().await?;
     assert_eq!(2, size);
 
+    client.set("key3", "value3").await?;
+    let size = client.dbsize().await?;
+    assert_eq!(3, size);
+
     Ok(())
 }
 
@@ -604,6 +668,50 @@ async fn info() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn bgsave() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+    client.set("key", "value").await?;
+
+    let lastsave_before = client.lastsave().await?;
+
+    // LASTSAVE has a resolution of one second
+    sleep(Duration::from_millis(1100)).await;
+
+    let status = client.bgsave(true).await?;
+    assert!(status.starts_with("Background saving"), "{status}");
+
+    let mut lastsave_after = lastsave_before;
+    for _ in 0..100 {
+        lastsave_after = client.lastsave().await?;
+        if lastsave_after > lastsave_before {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    assert!(lastsave_after > lastsave_before);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn bgrewriteaof() -> Result<()> {
+    let client = get_test_client().await?;
+
+    let status = client.bgrewriteaof().await?;
+    assert!(
+        status.starts_with("Background append only file rewriting"),
+        "{status}"
+    );
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -1031,6 +1139,79 @@ async fn replicaof() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn info_replication() -> Result<()> {
+    let client = get_test_client().await?;
+
+    let replication_info = client.info_replication().await?;
+    assert_eq!("master", replication_info.role);
+    assert!(replication_info.master_host.is_none());
+    assert_eq!(40, replication_info.master_replid.len());
+
+    client
+        .replicaof(ReplicaOfOptions::master("127.0.0.1", 6379))
+        .await?;
+
+    let replication_info = client.info_replication().await?;
+    assert_eq!("slave", replication_info.role);
+    assert_eq!(Some("127.0.0.1".to_owned()), replication_info.master_host);
+    assert_eq!(Some(6379), replication_info.master_port);
+    assert!(replication_info.master_link_status.is_some());
+
+    client.replicaof(ReplicaOfOptions::no_one()).await?;
+
+    let replication_info = client.info_replication().await?;
+    assert_eq!("master", replication_info.role);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn info_replication_master() -> Result<()> {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    let info = "# Replication\r\n\
+        role:master\r\n\
+        connected_slaves:2\r\n\
+        slave0:ip=10.0.0.1,port=6380,state=online,offset=1234,lag=0\r\n\
+        slave1:ip=10.0.0.2,port=6381,state=wait_bgsave,offset=0,lag=3\r\n\
+        master_failover_state:no-failover\r\n\
+        master_replid:8f2a3c2cf1c7b0f8d65e5b6f2a0e0a0f6c4b1d2e\r\n\
+        master_repl_offset:1234\r\n";
+    transport.push_reply(format!("${}\r\n{info}\r\n", info.len()));
+
+    let replication_info = client.info_replication().await?;
+    assert_eq!("master", replication_info.role);
+    assert_eq!(2, replication_info.connected_slaves);
+    assert_eq!(2, replication_info.slaves.len());
+    assert_eq!("10.0.0.1", replication_info.slaves[0].ip);
+    assert_eq!(6380, replication_info.slaves[0].port);
+    assert_eq!("online", replication_info.slaves[0].state);
+    assert_eq!(1234, replication_info.slaves[0].offset);
+    assert_eq!(0, replication_info.slaves[0].lag);
+    assert_eq!("wait_bgsave", replication_info.slaves[1].state);
+    assert_eq!(3, replication_info.slaves[1].lag);
+    assert!(replication_info.master_host.is_none());
+    assert!(replication_info.master_link_status.is_none());
+    assert_eq!(
+        "8f2a3c2cf1c7b0f8d65e5b6f2a0e0a0f6c4b1d2e",
+        replication_info.master_replid
+    );
+    assert_eq!(1234, replication_info.master_repl_offset);
+    assert_eq!(
+        Some(&"no-failover".to_owned()),
+        replication_info
+            .additional_arguments
+            .get("master_failover_state")
+    );
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -1089,6 +1270,44 @@ async fn save() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn shutdown_abort() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // no shutdown in progress
+    let result = client.shutdown(ShutdownOptions::default().abort()).await;
+    assert!(matches!(result, Err(Error::Redis(_))));
+
+    Ok(())
+}
+
+/// stops the test server: must be run against a throwaway instance
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[ignore]
+async fn shutdown() -> Result<()> {
+    serial_shutdown().await
+}
+
+/// `#[serial]` drops the `#[ignore]` attribute of a test,
+/// so the lock is taken by this helper instead
+#[serial]
+async fn serial_shutdown() -> Result<()> {
+    let client = get_test_client().await?;
+
+    client
+        .shutdown(ShutdownOptions::default().save(false))
+        .await?;
+
+    // the connection is gone
+    let result = client.ping::<()>(PingOptions::default()).await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -1128,17 +1347,22 @@ async fn slowlog_reset() -> Result<()> {
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
 async fn swapdb() -> Result<()> {
-    let client = get_test_client().await?;
-    client.flushall(FlushingMode::Sync).await?;
+    let client0 = get_test_client().await?;
+    client0.flushall(FlushingMode::Sync).await?;
 
-    client.select(1).await?;
-    client.set("key", "value").await?;
+    let mut config = get_default_addr().into_config()?;
+    config.database = 1;
+    let client1 = Client::connect(config).await?;
 
-    client.swapdb(0, 1).await?;
+    client0.set("key", "old").await?;
+    client1.set("key", "new").await?;
 
-    client.select(0).await?;
-    let value: String = client.get("key").await?;
-    assert_eq!("value", value);
+    client0.swapdb(0, 1).await?;
+
+    let value: String = client0.get("key").await?;
+    assert_eq!("new", value);
+    let value: String = client1.get("key").await?;
+    assert_eq!("old", value);
 
     Ok(())
 }
@@ -1149,7 +1373,12 @@ async fn swapdb() -> Result<()> {
 async fn time() -> Result<()> {
     let client = get_test_client().await?;
 
-    let (_unix_timestamp, _microseconds) = client.time().await?;
+    let server_time: SystemTime = client.time().await?.into();
+    let now = SystemTime::now();
+    let delta = now
+        .duration_since(server_time)
+        .unwrap_or_else(|e| e.duration());
+    assert!(delta < Duration::from_secs(5), "delta: {delta:?}");
 
     Ok(())
 }
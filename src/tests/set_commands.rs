@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::{tests::get_test_client, commands::{GenericCommands, SScanOptions, SetCommands}, Result};
+use crate::{client::{Client, RecordingTransport}, tests::{assert_encoding, get_test_client}, commands::{GenericCommands, SScanOptions, SetCommands}, Error, Result};
 use serial_test::serial;
 
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
@@ -18,6 +18,26 @@ async fn sadd() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn set_encoding() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.del("key").await?;
+
+    client.sadd("key", [1, 2, 3]).await?;
+    assert_encoding(&client, "key", "intset").await?;
+
+    // a member too large for a listpack, whatever the Redis version
+    let member = "m".repeat(100);
+    client.sadd("key", member).await?;
+    assert_encoding(&client, "key", "hashtable").await?;
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -115,6 +135,12 @@ async fn sintercard() -> Result<()> {
     let len = client.sintercard(["key1", "key2", "key3"], 0).await?;
     assert_eq!(1, len);
 
+    let len = client.sintercard(["key1", "key3"], 0).await?;
+    assert_eq!(2, len);
+
+    let len = client.sintercard(["key1", "key3"], 1).await?;
+    assert_eq!(1, len);
+
     Ok(())
 }
 
@@ -181,6 +207,36 @@ async fn smembers() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn smembers_typed() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.del("key").await?;
+
+    let values: HashSet<i64> = [1, -2, 300, i64::MAX].into_iter().collect();
+    let len = client.sadd_typed("key", values.iter().copied()).await?;
+    assert_eq!(4, len);
+
+    let members = client.smembers_typed::<_, i64>("key").await?;
+    assert_eq!(values, members);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn smembers_typed_conversion_error() {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    transport.push_reply("*2\r\n$1\r\n1\r\n$3\r\nabc\r\n");
+    let result = client.smembers_typed::<_, i64>("key").await;
+    assert!(matches!(result, Err(Error::Conversion(_))), "{result:?}");
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -215,6 +271,12 @@ async fn smove() -> Result<()> {
     let result = client.smove("key1", "key2", "value3").await?;
     assert!(result);
 
+    let members: HashSet<String> = client.smembers("key2").await?;
+    assert!(members.contains("value3"));
+
+    let result = client.smove("key1", "key2", "unknown").await?;
+    assert!(!result);
+
     Ok(())
 }
 
@@ -229,8 +291,22 @@ async fn spop() -> Result<()> {
 
     client.sadd("key", ["value1", "value2", "value3"]).await?;
 
-    let result: HashSet<String> = client.spop("key", 2).await?;
-    assert_eq!(2, result.len());
+    let result: Option<String> = client.spop("key").await?;
+    assert!(result.is_some());
+
+    let result: HashSet<String> = client.spop_count("key", 1).await?;
+    assert_eq!(1, result.len());
+
+    // count greater than the set
+    let result: HashSet<String> = client.spop_count("key", 10).await?;
+    assert_eq!(1, result.len());
+    assert_eq!(0, client.exists("key").await?);
+
+    let result: Option<String> = client.spop("key").await?;
+    assert_eq!(None, result);
+
+    let result: Vec<String> = client.spop_count("key", 10).await?;
+    assert!(result.is_empty());
 
     Ok(())
 }
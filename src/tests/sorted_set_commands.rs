@@ -1,8 +1,9 @@
 use crate::{
+    client::{Client, RecordingTransport},
     commands::{
         BZpopMinMaxResult, BlockingCommands, FlushingMode, GenericCommands, ServerCommands,
-        SortedSetCommands, ZAddOptions, ZRangeOptions, ZRangeSortBy, ZScanOptions, ZScanResult,
-        ZWhere,
+        SortedSetCommands, ZAddComparison, ZAddCondition, ZAddOptions, ZAggregate, ZRangeOptions,
+        ZRangeSortBy, ZScanOptions, ZScanResult, ZWhere,
     },
     sleep, spawn,
     tests::get_test_client,
@@ -279,6 +280,71 @@ async fn zadd() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn zadd_incr() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.del("key").await?;
+
+    let score = client
+        .zadd_incr(
+            "key",
+            ZAddCondition::None,
+            ZAddComparison::None,
+            false,
+            0.0,
+            "one",
+        )
+        .await?;
+    assert_eq!(Some(0.0), score);
+
+    let score = client
+        .zadd_incr(
+            "key",
+            ZAddCondition::None,
+            ZAddComparison::None,
+            false,
+            1.5,
+            "one",
+        )
+        .await?;
+    assert_eq!(Some(1.5), score);
+
+    // NX on an existing member: not applied
+    let score = client
+        .zadd_incr(
+            "key",
+            ZAddCondition::NX,
+            ZAddComparison::None,
+            false,
+            1.0,
+            "one",
+        )
+        .await?;
+    assert_eq!(None, score);
+
+    // XX on a missing member: not applied
+    let score = client
+        .zadd_incr(
+            "key",
+            ZAddCondition::XX,
+            ZAddComparison::None,
+            false,
+            1.0,
+            "two",
+        )
+        .await?;
+    assert_eq!(None, score);
+
+    let score = client.zscore("key", "one").await?;
+    assert_eq!(Some(1.5), score);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -450,6 +516,42 @@ async fn zinter() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn zintercard() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.del(["key1", "key2"]).await?;
+
+    client
+        .zadd(
+            "key1",
+            [(1.0, "one"), (2.0, "two"), (3.0, "three"), (4.0, "four")],
+            ZAddOptions::default(),
+        )
+        .await?;
+    client
+        .zadd(
+            "key2",
+            [(1.0, "one"), (2.0, "two"), (3.0, "three")],
+            ZAddOptions::default(),
+        )
+        .await?;
+
+    let len = client.zintercard(["key1", "key2"], 0).await?;
+    assert_eq!(3, len);
+
+    let len = client.zintercard(["key1", "key2"], 2).await?;
+    assert_eq!(2, len);
+
+    let len = client.zintercard(["key1", "key2"], 10).await?;
+    assert_eq!(3, len);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -1091,5 +1193,38 @@ async fn zunionstore() -> Result<()> {
     assert_eq!(("three".to_owned(), 9.0), values[1]);
     assert_eq!(("two".to_owned(), 10.0), values[2]);
 
+    let len = client
+        .zunionstore("out", ["key1", "key2"], Some([2.0, 3.0]), ZAggregate::Min)
+        .await?;
+    assert_eq!(3, len);
+
+    let values: Vec<(String, f64)> = client
+        .zrange_with_scores("out", 0, -1, ZRangeOptions::default())
+        .await?;
+    assert_eq!(3, values.len());
+    assert_eq!(("one".to_owned(), 2.0), values[0]);
+    assert_eq!(("two".to_owned(), 4.0), values[1]);
+    assert_eq!(("three".to_owned(), 9.0), values[2]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn zunionstore_weights_aggregate() -> Result<()> {
+    let transport = RecordingTransport::new();
+    transport.push_reply(":3\r\n");
+    let client = Client::with_transport(transport.clone());
+
+    let len = client
+        .zunionstore("out", ["key1", "key2"], Some([1.5, 2.5]), ZAggregate::Max)
+        .await?;
+    assert_eq!(3, len);
+
+    assert_eq!(
+        vec![b"*10\r\n$11\r\nZUNIONSTORE\r\n$3\r\nout\r\n$1\r\n2\r\n$4\r\nkey1\r\n$4\r\nkey2\r\n$7\r\nWEIGHTS\r\n$3\r\n1.5\r\n$3\r\n2.5\r\n$9\r\nAGGREGATE\r\n$3\r\nMAX\r\n".to_vec()],
+        transport.commands()
+    );
+
     Ok(())
 }
@@ -1,13 +1,17 @@
 use crate::{
+    client::{Client, RecordingTransport},
     commands::{
-        FlushingMode, ServerCommands, StreamCommands, StreamEntry, XAddOptions, XAutoClaimOptions,
-        XAutoClaimResult, XGroupCreateOptions, XInfoStreamOptions, XPendingOptions,
-        XReadGroupOptions, XReadOptions, XTrimOperator, XTrimOptions,
+        BlockingCommands, FlushingMode, GenericCommands, ServerCommands, StreamCommands,
+        StreamEntry, StreamEntryId, StreamRangeBound, XAddOptions, XAutoClaimOptions,
+        XAutoClaimResult, XGroupCreateOptions, XPendingOptions, XReadGroupOptions, XReadOptions,
+        XSetIdOptions, XTrimOperator, XTrimOptions,
     },
+    sleep, spawn,
     tests::get_test_client,
-    Result,
+    timeout, Error, Result,
 };
 use serial_test::serial;
+use std::time::Duration;
 
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
@@ -16,7 +20,7 @@ async fn xadd() -> Result<()> {
     let client = get_test_client().await?;
     client.flushdb(FlushingMode::Sync).await?;
 
-    let id1: String = client
+    let id1: StreamEntryId = client
         .xadd(
             "mystream",
             "123456-0",
@@ -24,9 +28,9 @@ async fn xadd() -> Result<()> {
             XAddOptions::default(),
         )
         .await?;
-    assert_eq!("123456-0", &id1);
+    assert_eq!(StreamEntryId::new(123456, 0), id1);
 
-    let id2: String = client
+    let id2: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -38,19 +42,113 @@ async fn xadd() -> Result<()> {
             XAddOptions::default(),
         )
         .await?;
-    assert!(!id2.is_empty());
+    assert!(id2 > StreamEntryId::MIN);
 
-    let result = client
-        .xinfo_stream("mystream", XInfoStreamOptions::default())
-        .await?;
+    let result = client.xinfo_stream("mystream").await?;
     assert_eq!(2, result.length);
     assert_eq!(id2, result.last_generated_id);
     assert_eq!(0, result.groups);
-    assert_eq!("0-0", result.max_deleted_entry_id);
+    assert_eq!(StreamEntryId::MIN, result.max_deleted_entry_id);
     assert_eq!(2, result.entries_added);
     assert_eq!(id1, result.recorded_first_entry_id);
-    assert_eq!(id1, result.first_entry.stream_id);
-    assert_eq!(id2, result.last_entry.stream_id);
+    assert_eq!(Some(id1), result.first_entry.map(|e| e.stream_id));
+    assert_eq!(Some(id2), result.last_entry.map(|e| e.stream_id));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn xadd_no_mk_stream() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    let id: Option<String> = client
+        .xadd(
+            "mystream",
+            "*",
+            ("name", "John"),
+            XAddOptions::default().no_mk_stream(),
+        )
+        .await?;
+    assert_eq!(None, id);
+    assert_eq!(0, client.exists("mystream").await?);
+
+    let id: Option<String> = client
+        .xadd("mystream", "1-1", ("name", "John"), XAddOptions::default())
+        .await?;
+    assert_eq!(Some("1-1".to_owned()), id);
+
+    let id: Option<String> = client
+        .xadd(
+            "mystream",
+            "1-2",
+            ("name", "Jane"),
+            XAddOptions::default().no_mk_stream(),
+        )
+        .await?;
+    assert_eq!(Some("1-2".to_owned()), id);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn xadd_trim() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    for i in 1..=1000 {
+        let _id: String = client
+            .xadd(
+                "mystream",
+                format!("{i}-0"),
+                ("field", i),
+                XAddOptions::default(),
+            )
+            .await?;
+    }
+
+    // exact trimming
+    let _id: String = client
+        .xadd(
+            "mystream",
+            "1001-0",
+            ("field", 1001),
+            XAddOptions::default().trim_options(XTrimOptions::max_len(XTrimOperator::Equal, 500)),
+        )
+        .await?;
+    assert_eq!(500, client.xlen("mystream").await?);
+
+    // approximate trimming evicts only whole macro nodes,
+    // so the stream length is at least the threshold
+    let _id: String = client
+        .xadd(
+            "mystream",
+            "1002-0",
+            ("field", 1002),
+            XAddOptions::default()
+                .trim_options(XTrimOptions::max_len(XTrimOperator::Approximately, 10)),
+        )
+        .await?;
+    let len = client.xlen("mystream").await?;
+    assert!((10..=501).contains(&len));
+
+    // MINID with an explicit ID
+    let _id: String = client
+        .xadd(
+            "mystream",
+            "1003-0",
+            ("field", 1003),
+            XAddOptions::default()
+                .trim_options(XTrimOptions::min_id(XTrimOperator::Equal, "1001-0")),
+        )
+        .await?;
+    let results: Vec<StreamEntry<String>> = client.xrange("mystream", "-", "+", None).await?;
+    assert_eq!(3, results.len());
+    assert_eq!(StreamEntryId::new(1001, 0), results[0].stream_id);
 
     Ok(())
 }
@@ -62,7 +160,7 @@ async fn xdel() -> Result<()> {
     let client = get_test_client().await?;
     client.flushdb(FlushingMode::Sync).await?;
 
-    let id1: String = client
+    let id1: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -71,7 +169,7 @@ async fn xdel() -> Result<()> {
         )
         .await?;
 
-    let id2: String = client
+    let id2: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -121,7 +219,7 @@ async fn xgroup() -> Result<()> {
     assert_eq!("mygroup", results[0].name);
     assert_eq!(1, results[0].consumers);
     assert_eq!(0, results[0].pending);
-    assert_eq!("0-0", results[0].last_delivered_id);
+    assert_eq!(StreamEntryId::MIN, results[0].last_delivered_id);
     assert_eq!(None, results[0].entries_read);
     assert_eq!(Some(0), results[0].lag);
 
@@ -137,6 +235,126 @@ async fn xgroup() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn xinfo() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    client
+        .xgroup_create(
+            "mystream",
+            "mygroup",
+            "$",
+            XGroupCreateOptions::default().mk_stream(),
+        )
+        .await?;
+
+    let id1: StreamEntryId = client
+        .xadd(
+            "mystream",
+            "*",
+            ("message", "apple"),
+            XAddOptions::default(),
+        )
+        .await?;
+    #[cfg_attr(not(feature = "xinfo-full"), allow(unused_variables))]
+    let id2: StreamEntryId = client
+        .xadd(
+            "mystream",
+            "*",
+            ("message", "orange"),
+            XAddOptions::default(),
+        )
+        .await?;
+    let id3: StreamEntryId = client
+        .xadd(
+            "mystream",
+            "*",
+            ("message", "banana"),
+            XAddOptions::default(),
+        )
+        .await?;
+
+    let _results: Vec<(String, Vec<StreamEntry<String>>)> = client
+        .xreadgroup(
+            "mygroup",
+            "Bob",
+            XReadGroupOptions::default().count(2),
+            "mystream",
+            ">",
+        )
+        .await?;
+    let _results: Vec<(String, Vec<StreamEntry<String>>)> = client
+        .xreadgroup(
+            "mygroup",
+            "Alice",
+            XReadGroupOptions::default().count(2),
+            "mystream",
+            ">",
+        )
+        .await?;
+
+    let result = client.xinfo_stream("mystream").await?;
+    assert_eq!(3, result.length);
+    assert_eq!(1, result.groups);
+    assert_eq!(id3, result.last_generated_id);
+    assert_eq!(Some(id1.clone()), result.first_entry.map(|e| e.stream_id));
+    assert_eq!(Some(id3.clone()), result.last_entry.map(|e| e.stream_id));
+
+    let results = client.xinfo_groups("mystream").await?;
+    assert_eq!(1, results.len());
+    assert_eq!("mygroup", results[0].name);
+    assert_eq!(2, results[0].consumers);
+    assert_eq!(3, results[0].pending);
+    assert_eq!(id3, results[0].last_delivered_id);
+    assert_eq!(Some(3), results[0].entries_read);
+    assert_eq!(Some(0), results[0].lag);
+
+    let mut results = client.xinfo_consumers("mystream", "mygroup").await?;
+    results.sort_by(|c1, c2| c1.name.cmp(&c2.name));
+    assert_eq!(2, results.len());
+    assert_eq!("Alice", results[0].name);
+    assert_eq!(1, results[0].pending);
+    assert!(results[0].idle_millis < 100);
+    assert_eq!("Bob", results[1].name);
+    assert_eq!(2, results[1].pending);
+    assert!(results[1].idle_millis < 100);
+
+    #[cfg(feature = "xinfo-full")]
+    {
+        let result = client.xinfo_stream_full("mystream", None).await?;
+        assert_eq!(3, result.length);
+        assert_eq!(3, result.entries.len());
+        assert_eq!(id1, result.entries[0].stream_id);
+        assert_eq!(id2, result.entries[1].stream_id);
+        assert_eq!(id3, result.entries[2].stream_id);
+        assert_eq!(1, result.groups.len());
+        let group = &result.groups[0];
+        assert_eq!("mygroup", group.name);
+        assert_eq!(3, group.pel_count);
+        assert_eq!(3, group.pending.len());
+        assert_eq!(id1, group.pending[0].id);
+        assert_eq!("Bob", group.pending[0].consumer);
+        assert_eq!(1, group.pending[0].delivery_count);
+        assert_eq!(2, group.consumers.len());
+        let bob = group.consumers.iter().find(|c| c.name == "Bob").unwrap();
+        assert_eq!(2, bob.pel_count);
+        assert_eq!(2, bob.pending.len());
+        assert_eq!(id2, bob.pending[1].id);
+        let alice = group.consumers.iter().find(|c| c.name == "Alice").unwrap();
+        assert_eq!(1, alice.pel_count);
+        assert_eq!(id3, alice.pending[0].id);
+
+        let result = client.xinfo_stream_full("mystream", Some(1)).await?;
+        assert_eq!(1, result.entries.len());
+        assert_eq!(1, result.groups[0].pending.len());
+    }
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -144,7 +362,7 @@ async fn xlen() -> Result<()> {
     let client = get_test_client().await?;
     client.flushdb(FlushingMode::Sync).await?;
 
-    let id1: String = client
+    let id1: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -152,9 +370,9 @@ async fn xlen() -> Result<()> {
             XAddOptions::default(),
         )
         .await?;
-    assert!(!id1.is_empty());
+    assert!(id1 > StreamEntryId::MIN);
 
-    let id2: String = client
+    let id2: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -166,7 +384,7 @@ async fn xlen() -> Result<()> {
             XAddOptions::default(),
         )
         .await?;
-    assert!(!id2.is_empty());
+    assert!(id2 > StreamEntryId::MIN);
 
     let len = client.xlen("mystream").await?;
     assert_eq!(2, len);
@@ -181,7 +399,7 @@ async fn xrange() -> Result<()> {
     let client = get_test_client().await?;
     client.flushdb(FlushingMode::Sync).await?;
 
-    let id1: String = client
+    let id1: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -189,9 +407,9 @@ async fn xrange() -> Result<()> {
             XAddOptions::default(),
         )
         .await?;
-    assert!(!id1.is_empty());
+    assert!(id1 > StreamEntryId::MIN);
 
-    let id2: String = client
+    let id2: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -203,7 +421,7 @@ async fn xrange() -> Result<()> {
             XAddOptions::default(),
         )
         .await?;
-    assert!(!id2.is_empty());
+    assert!(id2 > StreamEntryId::MIN);
 
     let results: Vec<StreamEntry<String>> = client.xrange("mystream", "-", "+", None).await?;
     assert_eq!(2, results.len());
@@ -218,6 +436,65 @@ async fn xrange() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn xrange_bounds() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    let ids = [
+        StreamEntryId::new(1, 0),
+        StreamEntryId::new(2, 0),
+        StreamEntryId::new(3, 0),
+    ];
+    for (i, id) in ids.iter().enumerate() {
+        let _: StreamEntryId = client
+            .xadd("mystream", *id, [("field", i)], XAddOptions::default())
+            .await?;
+    }
+
+    let results: Vec<StreamEntry<usize>> = client
+        .xrange(
+            "mystream",
+            StreamRangeBound::Exclusive(ids[0]),
+            StreamRangeBound::Max,
+            Some(1),
+        )
+        .await?;
+    assert_eq!(1, results.len());
+    assert_eq!(ids[1], results[0].stream_id);
+    assert_eq!(Some(&1), results[0].items.get("field"));
+
+    let results: Vec<StreamEntry<usize>> = client
+        .xrange(
+            "mystream",
+            StreamRangeBound::Min,
+            StreamRangeBound::Exclusive(ids[2]),
+            None,
+        )
+        .await?;
+    assert_eq!(
+        vec![ids[0], ids[1]],
+        results.iter().map(|e| e.stream_id).collect::<Vec<_>>()
+    );
+
+    let results: Vec<StreamEntry<usize>> = client
+        .xrevrange(
+            "mystream",
+            StreamRangeBound::Inclusive(ids[2]),
+            StreamRangeBound::Exclusive(ids[0]),
+            Some(2),
+        )
+        .await?;
+    assert_eq!(
+        vec![ids[2], ids[1]],
+        results.iter().map(|e| e.stream_id).collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -225,7 +502,7 @@ async fn xread() -> Result<()> {
     let client = get_test_client().await?;
     client.flushdb(FlushingMode::Sync).await?;
 
-    let id1: String = client
+    let id1: StreamEntryId = client
         .xadd(
             "mystream",
             "123456-0",
@@ -234,7 +511,7 @@ async fn xread() -> Result<()> {
         )
         .await?;
 
-    let id2: String = client
+    let id2: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -291,6 +568,50 @@ async fn xread() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn xread_block() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    let _id: String = client
+        .xadd("stream1", "*", [("field", "old")], XAddOptions::default())
+        .await?;
+
+    // timeout
+    let result: Option<Vec<(String, Vec<StreamEntry<String>>)>> = client
+        .xread_block(10, None, ["stream1", "stream2"], ["$", "$"])
+        .await?;
+    assert!(result.is_none());
+
+    // wake up on a new entry in any stream
+    let blocking_client = get_test_client().await?;
+    let join_handle = spawn(async move {
+        let result: Result<Option<Vec<(String, Vec<StreamEntry<String>>)>>> = blocking_client
+            .xread_block(5000, Some(10), ["stream1", "stream2"], ["$", "$"])
+            .await;
+        result
+    });
+
+    sleep(Duration::from_millis(100)).await;
+    let id: StreamEntryId = client
+        .xadd("stream2", "*", [("field", "new")], XAddOptions::default())
+        .await?;
+
+    let result = timeout(Duration::from_secs(1), join_handle).await???;
+    let Some(streams) = result else {
+        panic!("xread_block timed out");
+    };
+    assert_eq!(1, streams.len());
+    assert_eq!("stream2", streams[0].0);
+    assert_eq!(1, streams[0].1.len());
+    assert_eq!(id, streams[0].1[0].stream_id);
+    assert_eq!(Some(&"new".to_owned()), streams[0].1[0].items.get("field"));
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -318,7 +639,7 @@ async fn xreadgroup() -> Result<()> {
         .await?;
     assert!(result);
 
-    let id1: String = client
+    let id1: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -327,7 +648,7 @@ async fn xreadgroup() -> Result<()> {
         )
         .await?;
 
-    let id2: String = client
+    let id2: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -336,7 +657,7 @@ async fn xreadgroup() -> Result<()> {
         )
         .await?;
 
-    let id3: String = client
+    let id3: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -345,7 +666,7 @@ async fn xreadgroup() -> Result<()> {
         )
         .await?;
 
-    let id4: String = client
+    let id4: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -354,7 +675,7 @@ async fn xreadgroup() -> Result<()> {
         )
         .await?;
 
-    let id5: String = client
+    let id5: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -401,8 +722,8 @@ async fn xreadgroup() -> Result<()> {
 
     let result = client.xpending("mystream", "mygroup").await?;
     assert_eq!(5, result.num_pending_messages);
-    assert_eq!(id1, result.smallest_id);
-    assert_eq!(id5, result.greatest_id);
+    assert_eq!(Some(id1.clone()), result.smallest_id);
+    assert_eq!(Some(id5.clone()), result.greatest_id);
     assert_eq!(2, result.consumers.len());
     assert_eq!("Alice", result.consumers[0].consumer);
     assert_eq!(2, result.consumers[0].num_messages);
@@ -410,10 +731,13 @@ async fn xreadgroup() -> Result<()> {
     assert_eq!(3, result.consumers[1].num_messages);
 
     let results = client
-        .xpending_with_options(
+        .xpending_detailed(
             "mystream",
             "mygroup",
-            XPendingOptions::default().start("-").end("+").count(10),
+            "-",
+            "+",
+            10,
+            XPendingOptions::default(),
         )
         .await?;
     assert_eq!(5, results.len());
@@ -472,6 +796,126 @@ async fn xreadgroup() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn xpending() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    client
+        .xgroup_create(
+            "mystream",
+            "mygroup",
+            "$",
+            XGroupCreateOptions::default().mk_stream(),
+        )
+        .await?;
+
+    let result = client.xpending("mystream", "mygroup").await?;
+    assert_eq!(0, result.num_pending_messages);
+    assert_eq!(None, result.smallest_id);
+    assert_eq!(None, result.greatest_id);
+    assert_eq!(0, result.consumers.len());
+
+    let id1: StreamEntryId = client
+        .xadd(
+            "mystream",
+            "*",
+            ("message", "apple"),
+            XAddOptions::default(),
+        )
+        .await?;
+    let id2: StreamEntryId = client
+        .xadd(
+            "mystream",
+            "*",
+            ("message", "orange"),
+            XAddOptions::default(),
+        )
+        .await?;
+    let id3: StreamEntryId = client
+        .xadd(
+            "mystream",
+            "*",
+            ("message", "banana"),
+            XAddOptions::default(),
+        )
+        .await?;
+
+    // read without acknowledging
+    let _results: Vec<(String, Vec<StreamEntry<String>>)> = client
+        .xreadgroup(
+            "mygroup",
+            "Bob",
+            XReadGroupOptions::default().count(2),
+            "mystream",
+            ">",
+        )
+        .await?;
+    let _results: Vec<(String, Vec<StreamEntry<String>>)> = client
+        .xreadgroup(
+            "mygroup",
+            "Alice",
+            XReadGroupOptions::default(),
+            "mystream",
+            ">",
+        )
+        .await?;
+
+    let result = client.xpending("mystream", "mygroup").await?;
+    assert_eq!(3, result.num_pending_messages);
+    assert_eq!(Some(id1.clone()), result.smallest_id);
+    assert_eq!(Some(id3.clone()), result.greatest_id);
+    assert_eq!(2, result.consumers.len());
+
+    let results = client
+        .xpending_detailed(
+            "mystream",
+            "mygroup",
+            "-",
+            "+",
+            10,
+            XPendingOptions::default(),
+        )
+        .await?;
+    assert_eq!(3, results.len());
+    assert_eq!(id1, results[0].message_id);
+    assert_eq!("Bob", results[0].consumer);
+    assert_eq!(1, results[0].times_delivered);
+    assert_eq!(id2, results[1].message_id);
+    assert_eq!("Bob", results[1].consumer);
+    assert_eq!(id3, results[2].message_id);
+    assert_eq!("Alice", results[2].consumer);
+
+    let results = client
+        .xpending_detailed(
+            "mystream",
+            "mygroup",
+            "-",
+            "+",
+            10,
+            XPendingOptions::default().consumer("Alice"),
+        )
+        .await?;
+    assert_eq!(1, results.len());
+    assert_eq!(id3, results[0].message_id);
+
+    let results = client
+        .xpending_detailed(
+            "mystream",
+            "mygroup",
+            "-",
+            "+",
+            10,
+            XPendingOptions::default().idle(60_000).consumer("Bob"),
+        )
+        .await?;
+    assert_eq!(0, results.len());
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -499,7 +943,7 @@ async fn xclaim() -> Result<()> {
         .await?;
     assert!(result);
 
-    let id1: String = client
+    let id1: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -508,7 +952,7 @@ async fn xclaim() -> Result<()> {
         )
         .await?;
 
-    let id2: String = client
+    let id2: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -517,7 +961,7 @@ async fn xclaim() -> Result<()> {
         )
         .await?;
 
-    let id3: String = client
+    let id3: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -526,7 +970,7 @@ async fn xclaim() -> Result<()> {
         )
         .await?;
 
-    let id4: String = client
+    let id4: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -535,7 +979,7 @@ async fn xclaim() -> Result<()> {
         )
         .await?;
 
-    let id5: String = client
+    let id5: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -628,7 +1072,7 @@ async fn xautoclaim() -> Result<()> {
         .await?;
     assert!(result);
 
-    let id1: String = client
+    let id1: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -637,7 +1081,7 @@ async fn xautoclaim() -> Result<()> {
         )
         .await?;
 
-    let id2: String = client
+    let id2: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -646,7 +1090,7 @@ async fn xautoclaim() -> Result<()> {
         )
         .await?;
 
-    let id3: String = client
+    let id3: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -655,7 +1099,7 @@ async fn xautoclaim() -> Result<()> {
         )
         .await?;
 
-    let id4: String = client
+    let id4: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -664,7 +1108,7 @@ async fn xautoclaim() -> Result<()> {
         )
         .await?;
 
-    let id5: String = client
+    let id5: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -720,7 +1164,7 @@ async fn xautoclaim() -> Result<()> {
             XAutoClaimOptions::default().count(1),
         )
         .await?;
-    assert_eq!("0-0", result.start_stream_id);
+    assert_eq!(StreamEntryId::MIN, result.start_stream_id);
     assert_eq!(1, result.entries.len());
     assert_eq!(id5, result.entries[0].stream_id);
 
@@ -751,7 +1195,7 @@ async fn xrevrange() -> Result<()> {
     let client = get_test_client().await?;
     client.flushdb(FlushingMode::Sync).await?;
 
-    let id1: String = client
+    let id1: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -759,9 +1203,9 @@ async fn xrevrange() -> Result<()> {
             XAddOptions::default(),
         )
         .await?;
-    assert!(!id1.is_empty());
+    assert!(id1 > StreamEntryId::MIN);
 
-    let id2: String = client
+    let id2: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -773,7 +1217,7 @@ async fn xrevrange() -> Result<()> {
             XAddOptions::default(),
         )
         .await?;
-    assert!(!id2.is_empty());
+    assert!(id2 > StreamEntryId::MIN);
 
     let results: Vec<StreamEntry<String>> = client.xrevrange("mystream", "+", "-", None).await?;
     assert_eq!(2, results.len());
@@ -804,7 +1248,7 @@ async fn xtrim() -> Result<()> {
         )
         .await?;
 
-    let id2: String = client
+    let id2: StreamEntryId = client
         .xadd(
             "mystream",
             "*",
@@ -829,6 +1273,65 @@ async fn xtrim() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn xsetid() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    let _: StreamEntryId = client
+        .xadd(
+            "mystream",
+            "1-0",
+            [("field", "value")],
+            XAddOptions::default(),
+        )
+        .await?;
+    let _: StreamEntryId = client
+        .xadd(
+            "mystream",
+            "2-0",
+            [("field", "value")],
+            XAddOptions::default(),
+        )
+        .await?;
+
+    client
+        .xsetid(
+            "mystream",
+            StreamEntryId::new(10, 0),
+            XSetIdOptions::default()
+                .entries_added(5)
+                .max_deleted_id(StreamEntryId::new(3, 0)),
+        )
+        .await?;
+
+    let info = client.xinfo_stream("mystream").await?;
+    assert_eq!(StreamEntryId::new(10, 0), info.last_generated_id);
+    assert_eq!(StreamEntryId::new(3, 0), info.max_deleted_entry_id);
+    assert_eq!(5, info.entries_added);
+
+    // new ids must be greater than the last id
+    let result: Result<StreamEntryId> = client
+        .xadd(
+            "mystream",
+            "5-0",
+            [("field", "value")],
+            XAddOptions::default(),
+        )
+        .await;
+    assert!(result.is_err());
+
+    // the last id cannot be smaller than the last entry
+    let result = client
+        .xsetid("mystream", "1-0", XSetIdOptions::default())
+        .await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -847,3 +1350,116 @@ async fn xadd_ignore_result() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn stream_entry_id() -> Result<()> {
+    let id: StreamEntryId = "1526919030474-55".parse()?;
+    assert_eq!(StreamEntryId::new(1526919030474, 55), id);
+    assert_eq!("1526919030474-55", id.to_string());
+
+    let id: StreamEntryId = "1526919030474".parse()?;
+    assert_eq!(StreamEntryId::new(1526919030474, 0), id);
+
+    assert!("1526919030474-".parse::<StreamEntryId>().is_err());
+    assert!("abc-1".parse::<StreamEntryId>().is_err());
+
+    assert!(StreamEntryId::new(1526919030474, 55) < StreamEntryId::new(1526919030474, 56));
+    assert!(StreamEntryId::new(1526919030474, 55) < StreamEntryId::new(1526919030475, 0));
+    assert!(StreamEntryId::new(1526919030475, 0) > StreamEntryId::new(1526919030474, u64::MAX));
+
+    assert_eq!(
+        StreamEntryId::new(1526919030474, 56),
+        StreamEntryId::new(1526919030474, 55).next()
+    );
+    assert_eq!(
+        StreamEntryId::new(1526919030475, 0),
+        StreamEntryId::new(1526919030474, u64::MAX).next()
+    );
+    assert_eq!(StreamEntryId::MAX, StreamEntryId::MAX.next());
+    assert_eq!(
+        StreamEntryId::new(1526919030474, u64::MAX),
+        StreamEntryId::new(1526919030475, 0).previous()
+    );
+    assert_eq!(StreamEntryId::MIN, StreamEntryId::MIN.previous());
+
+    Ok(())
+}
+
+#[allow(deprecated)]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn xinfo_stream_options() -> Result<()> {
+    use crate::commands::XInfoStreamOptions;
+
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    // only the sent command is checked, the empty reply is not a valid stream info
+    transport.push_reply("*0\r\n");
+    let _result = client
+        .xinfo_stream_with_options("key", XInfoStreamOptions::default().full().count(2))
+        .await;
+
+    assert_eq!(
+        vec![b"*6\r\n$5\r\nXINFO\r\n$6\r\nSTREAM\r\n$3\r\nkey\r\n$4\r\nFULL\r\n$5\r\nCOUNT\r\n$1\r\n2\r\n".to_vec()],
+        transport.commands()
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn xpending_options_replace() -> Result<()> {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    transport.push_reply("*0\r\n");
+    let options = XPendingOptions::default()
+        .idle(100)
+        .consumer("consumer1")
+        .idle(200)
+        .consumer("consumer2");
+    client
+        .xpending_detailed("key", "group", "-", "+", 10, options)
+        .await?;
+
+    assert_eq!(
+        vec![b"*9\r\n$8\r\nXPENDING\r\n$3\r\nkey\r\n$5\r\ngroup\r\n$4\r\nIDLE\r\n$3\r\n200\r\n$1\r\n-\r\n$1\r\n+\r\n$2\r\n10\r\n$9\r\nconsumer2\r\n".to_vec()],
+        transport.commands()
+    );
+
+    Ok(())
+}
+
+#[allow(deprecated)]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn xpending_with_options() -> Result<()> {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    transport.push_reply("*0\r\n");
+    let options = XPendingOptions::default()
+        .idle(200)
+        .start("-")
+        .end("+")
+        .count(10)
+        .consumer("consumer1");
+    client
+        .xpending_with_options("key", "group", options)
+        .await?;
+
+    assert_eq!(
+        vec![b"*9\r\n$8\r\nXPENDING\r\n$3\r\nkey\r\n$5\r\ngroup\r\n$4\r\nIDLE\r\n$3\r\n200\r\n$1\r\n-\r\n$1\r\n+\r\n$2\r\n10\r\n$9\r\nconsumer1\r\n".to_vec()],
+        transport.commands()
+    );
+
+    // start, end and count are required
+    let options = XPendingOptions::default().start("-").end("+");
+    let result = client.xpending_with_options("key", "group", options).await;
+    assert!(matches!(result, Err(Error::Client(_))));
+    assert_eq!(1, transport.commands().len());
+
+    Ok(())
+}
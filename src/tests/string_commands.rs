@@ -1,10 +1,12 @@
 use crate::{
+    client::{Client, RecordingTransport},
     commands::{
-        GenericCommands, GetExOptions, LcsMatch, SetCondition, SetExpiration, StringCommands,
+        FlushingMode, GenericCommands, GetExOptions, InfoSection, LcsMatch, ServerCommands,
+        SetCondition, SetExpiration, StringCommands, TtlStatus,
     },
-    resp::Value,
+    resp::{BulkString, Value},
     tests::get_test_client,
-    Error, RedisError, RedisErrorKind, Result,
+    ConversionError, Error, RedisError, RedisErrorKind, Result,
 };
 use serial_test::serial;
 use std::time::{Duration, SystemTime};
@@ -23,6 +25,14 @@ async fn append() -> Result<()> {
     let value: String = client.get("key").await?;
     assert_eq!("value12", value);
 
+    // missing key
+    client.del("key2").await?;
+    let new_size = client.append("key2", "value").await?;
+    assert_eq!(5, new_size);
+
+    let value: String = client.get("key2").await?;
+    assert_eq!("value", value);
+
     client.close().await?;
 
     Ok(())
@@ -221,6 +231,41 @@ async fn get_persist() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn get_with_ttl() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    let result: Option<(String, TtlStatus)> = client.get_with_ttl("key").await?;
+    assert_eq!(None, result);
+
+    client
+        .set_with_options(
+            "key",
+            "value",
+            SetCondition::None,
+            SetExpiration::Ex(10),
+            false,
+        )
+        .await?;
+    let result: Option<(String, TtlStatus)> = client.get_with_ttl("key").await?;
+    let Some((value, TtlStatus::Expires(ttl))) = result else {
+        panic!("Unexpected result {result:?}");
+    };
+    assert_eq!("value", value);
+    assert!(ttl > Duration::from_secs(9) && ttl <= Duration::from_secs(10));
+
+    client.set("key", "value").await?;
+    let result: Option<(String, TtlStatus)> = client.get_with_ttl("key").await?;
+    assert_eq!(Some(("value".to_owned(), TtlStatus::NoExpiry)), result);
+
+    client.close().await?;
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -235,6 +280,15 @@ async fn getrange() -> Result<()> {
     let value: String = client.getrange("key", 1, -3).await?;
     assert_eq!("al", value);
 
+    let value: String = client.getrange("key", 0, -1).await?;
+    assert_eq!("value", value);
+
+    let value: String = client.getrange("key", -3, -1).await?;
+    assert_eq!("lue", value);
+
+    let value: String = client.getrange("key", 10, 20).await?;
+    assert_eq!("", value);
+
     client.close().await?;
 
     Ok(())
@@ -354,6 +408,32 @@ async fn incrbyfloat() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn incrbyfloat_str() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.del("key").await?;
+
+    let mut value = String::new();
+    for _ in 0..10 {
+        value = client.incrbyfloat_str("key", "0.1").await?;
+    }
+    assert_eq!("1", value);
+
+    let value = client.incrbyfloat_str("key", "-0.25").await?;
+    assert_eq!("0.75", value);
+
+    let value: String = client.get("key").await?;
+    assert_eq!("0.75", value);
+
+    client.close().await?;
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -420,6 +500,38 @@ async fn mget_mset() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn mget_mset_chunked() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.flushdb(FlushingMode::Sync).await?;
+    client.config_resetstat().await?;
+
+    let items = (0..50_000)
+        .map(|i| (format!("key{i}"), format!("value{i}")))
+        .collect::<Vec<_>>();
+    client.mset_chunked(&items, 1000).await?;
+
+    let mut keys = items.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+    keys.push("unknown".to_owned());
+    let values: Vec<Option<String>> = client.mget_chunked(&keys, 1000).await?;
+    assert_eq!(50_001, values.len());
+    for (i, (_, value)) in items.iter().enumerate() {
+        assert_eq!(Some(value), values[i].as_ref());
+    }
+    assert_eq!(None, values[50_000]);
+
+    // one command per chunk
+    let stats = client.info([InfoSection::Commandstats]).await?;
+    assert!(stats.contains("cmdstat_mset:calls=50,"));
+    assert!(stats.contains("cmdstat_mget:calls=51,"));
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -671,6 +783,14 @@ async fn setrange() -> Result<()> {
     let value: String = client.get("key").await?;
     assert_eq!("Hello Redis", value);
 
+    // empty key is zero-padded
+    client.del("key").await?;
+    let new_len = client.setrange("key", 3, "abc").await?;
+    assert_eq!(6, new_len);
+
+    let value: BulkString = client.get("key").await?;
+    assert_eq!(b"\0\0\0abc", value.as_bytes());
+
     client.close().await?;
 
     Ok(())
@@ -694,3 +814,31 @@ async fn strlen() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn get_conversion_error() -> Result<()> {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    transport.push_reply("$5\r\nhello\r\n");
+    let result = client.get::<_, i64>("key").await;
+    assert_eq!(
+        Some(ConversionError {
+            target: "i64".to_owned(),
+            value: "bulk string `hello`".to_owned(),
+            command: Some("GET"),
+        }),
+        match &result {
+            Err(Error::Conversion(e)) => Some(e.clone()),
+            _ => None,
+        },
+        "{result:?}"
+    );
+    assert_eq!(
+        "Conversion error: Cannot parse bulk string `hello` to i64 in reply to GET",
+        result.unwrap_err().to_string()
+    );
+
+    Ok(())
+}
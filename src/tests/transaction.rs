@@ -1,5 +1,5 @@
 use crate::{
-    client::BatchPreparedCommand,
+    client::{BatchPreparedCommand, Client, RecordingTransport},
     commands::{FlushingMode, ListCommands, ServerCommands, StringCommands, TransactionCommands},
     resp::cmd,
     tests::{get_test_client, get_cluster_test_client},
@@ -197,3 +197,71 @@ async fn transaction_on_cluster_connection_with_keys_with_different_slots() -> R
 
     Ok(())
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn dropped_transaction_sends_nothing() -> Result<()> {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    let mut transaction = client.create_transaction();
+    transaction.set("key", "value").forget();
+    transaction.get::<_, String>("key").queue();
+    drop(transaction);
+
+    // commands are processed in order, so anything sent on drop would be recorded first
+    transport.push_reply("$5\r\nvalue\r\n");
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    assert_eq!(
+        vec![b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n".to_vec()],
+        transport.commands()
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn transaction_results_offline() -> Result<()> {
+    let transport = RecordingTransport::new();
+    let client = Client::with_transport(transport.clone());
+
+    // single result
+    transport.push_reply("+OK\r\n");
+    transport.push_reply("+QUEUED\r\n");
+    transport.push_reply("+QUEUED\r\n");
+    transport.push_reply("*2\r\n+OK\r\n$5\r\nvalue\r\n");
+
+    let mut transaction = client.create_transaction();
+    transaction.set("key", "value").forget();
+    transaction.get::<_, String>("key").queue();
+    let value: String = transaction.execute().await?;
+    assert_eq!("value", value);
+
+    // several results, with nested arrays
+    transport.push_reply("+OK\r\n");
+    transport.push_reply("+QUEUED\r\n");
+    transport.push_reply("+QUEUED\r\n");
+    transport.push_reply("*2\r\n*2\r\n$1\r\na\r\n$1\r\nb\r\n:2\r\n");
+
+    let mut transaction = client.create_transaction();
+    transaction.lrange::<_, String, Vec<String>>("list", 0, -1).queue();
+    transaction.llen("list").queue();
+    let (values, len): (Vec<String>, usize) = transaction.execute().await?;
+    assert_eq!(vec!["a".to_owned(), "b".to_owned()], values);
+    assert_eq!(2, len);
+
+    // aborted by WATCH
+    transport.push_reply("+OK\r\n");
+    transport.push_reply("+QUEUED\r\n");
+    transport.push_reply("_\r\n");
+
+    let mut transaction = client.create_transaction();
+    transaction.get::<_, String>("key").queue();
+    let result: Result<String> = transaction.execute().await;
+    assert!(matches!(result, Err(Error::Aborted)), "{result:?}");
+
+    Ok(())
+}
@@ -1,6 +1,10 @@
 #[cfg(feature = "tls")]
 use crate::client::IntoConfig;
-use crate::{client::Client, Result};
+use crate::{
+    client::Client,
+    commands::{DebugCommands, GenericCommands, ServerCommands},
+    Result,
+};
 #[cfg(feature = "tls")]
 use native_tls::Certificate;
 
@@ -132,6 +136,84 @@ pub(crate) async fn get_redis_stack_test_client() -> Result<Client> {
     Client::connect(format!("redis://{}:{}", get_default_host(), 8000)).await
 }
 
+/// Asserts that Redis stores the value at `key` with the `expected` internal encoding
+/// (e.g. `intset`, `listpack`, `hashtable`, `skiplist`)
+pub(crate) async fn assert_encoding(client: &Client, key: &str, expected: &str) -> Result<()> {
+    let encoding: String = client.object_encoding(key).await?;
+    assert_eq!(expected, encoding, "unexpected encoding for key '{key}'");
+    Ok(())
+}
+
+/// Default value of `list-max-listpack-size`: nodes of at most 8Kb
+pub(crate) const DEFAULT_LIST_MAX_LISTPACK_SIZE: i64 = -2;
+/// Default quicklist packed threshold: 1Gb
+pub(crate) const DEFAULT_QUICKLIST_PACKED_THRESHOLD: usize = 1 << 30;
+
+/// Sets `list-max-listpack-size` and the quicklist packed threshold,
+/// to choose between `listpack` and `quicklist` encodings in list tests.
+///
+/// A positive `max_listpack_size` is a number of entries per node, a negative one a size class (-1 to -5).
+pub(crate) async fn set_list_encoding_thresholds(
+    client: &Client,
+    max_listpack_size: i64,
+    packed_threshold: usize,
+) -> Result<()> {
+    client
+        .config_set(("list-max-listpack-size", max_listpack_size))
+        .await?;
+    client
+        .debug_quicklist_packed_threshold(packed_threshold)
+        .await?;
+    Ok(())
+}
+
+/// Reply of a fake Redis 7.2 standalone master to `HELLO 3`
+#[cfg(feature = "tokio-runtime")]
+const FAKE_HELLO_REPLY: &[u8] =
+    b"%7\r\n+server\r\n+redis\r\n+version\r\n+7.2.0\r\n+proto\r\n:3\r\n\
+    +id\r\n:1\r\n+mode\r\n+standalone\r\n+role\r\n+master\r\n+modules\r\n*0\r\n";
+
+/// Binds a fake server on a free local port, to script the replies of a connection in a test
+#[cfg(feature = "tokio-runtime")]
+pub(crate) async fn bind_fake_server() -> Result<(tokio::net::TcpListener, u16)> {
+    log_try_init();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let port = listener.local_addr()?.port();
+    Ok((listener, port))
+}
+
+/// Accepts a connection on a fake server and answers its `HELLO`
+#[cfg(feature = "tokio-runtime")]
+pub(crate) async fn accept_fake_connection(
+    listener: &tokio::net::TcpListener,
+) -> Result<tokio::net::TcpStream> {
+    use tokio::io::AsyncWriteExt;
+
+    let (mut socket, _) = listener.accept().await?;
+    read_command(&mut socket, b"HELLO").await?;
+    socket.write_all(FAKE_HELLO_REPLY).await?;
+    Ok(socket)
+}
+
+/// Reads from a fake server connection until `name` has been received,
+/// and returns all the bytes read
+#[cfg(feature = "tokio-runtime")]
+pub(crate) async fn read_command(
+    socket: &mut tokio::net::TcpStream,
+    name: &[u8],
+) -> Result<Vec<u8>> {
+    use tokio::io::AsyncReadExt;
+
+    let mut buffer = Vec::new();
+    while !buffer.windows(name.len()).any(|w| w == name) {
+        let mut chunk = [0u8; 1024];
+        let len = socket.read(&mut chunk).await?;
+        assert!(len > 0, "connection closed by the client");
+        buffer.extend_from_slice(&chunk[..len]);
+    }
+    Ok(buffer)
+}
+
 pub fn log_try_init() {
     let _ = env_logger::builder()
         .format_target(false)
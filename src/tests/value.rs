@@ -2,7 +2,7 @@ use crate::{
     commands::{GenericCommands, SetCommands},
     resp::Value,
     tests::{get_test_client, log_try_init},
-    RedisError, RedisErrorKind, Result,
+    Error, RedisError, RedisErrorKind, Result,
 };
 use serial_test::serial;
 use std::collections::{BTreeSet, HashMap, HashSet};
@@ -102,3 +102,103 @@ fn display() {
         ])
     );
 }
+
+#[test]
+fn as_bytes() {
+    assert_eq!(
+        Some(&b"value"[..]),
+        Value::BulkString(b"value".to_vec()).as_bytes()
+    );
+    assert_eq!(
+        Some(&b"OK"[..]),
+        Value::SimpleString("OK".to_owned()).as_bytes()
+    );
+    assert_eq!(None, Value::Integer(12).as_bytes());
+    assert_eq!(None, Value::Nil.as_bytes());
+}
+
+#[test]
+fn to_debug_string() {
+    let value = Value::Array(vec![
+        Value::BulkString(b"key1".to_vec()),
+        Value::Array(vec![
+            Value::BulkString(b"field".to_vec()),
+            Value::BulkString(b"quoted \"value\"\r\n".to_vec()),
+            Value::Array(vec![]),
+        ]),
+        Value::Integer(12),
+        Value::Double(12.5),
+        Value::SimpleString("OK".to_owned()),
+        Value::Boolean(true),
+        Value::Error(RedisError {
+            kind: RedisErrorKind::Err,
+            description: "my error".to_owned(),
+        }),
+        Value::Nil,
+        Value::Map(HashMap::from([(
+            Value::BulkString(b"field".to_vec()),
+            Value::Array(vec![Value::Integer(1), Value::Integer(2)]),
+        )])),
+        Value::BulkString(b"key10".to_vec()),
+    ]);
+
+    assert_eq!(
+        r#" 1) "key1"
+ 2) 1) "field"
+    2) "quoted \"value\"\r\n"
+    3) (empty array)
+ 3) (integer) 12
+ 4) (double) 12.5
+ 5) OK
+ 6) (true)
+ 7) (error) ERR my error
+ 8) (nil)
+ 9) 1# "field" => 1) (integer) 1
+                  2) (integer) 2
+10) "key10""#,
+        value.to_debug_string()
+    );
+}
+
+#[test]
+fn into_pairs() -> Result<()> {
+    let key = |k: &str| Value::BulkString(k.as_bytes().to_vec());
+
+    // RESP2
+    let array = Value::Array(vec![
+        key("field1"),
+        Value::Integer(1),
+        key("field2"),
+        Value::Integer(2),
+    ]);
+    let array_pairs = array.into_pairs()?;
+    assert_eq!(
+        vec![
+            (key("field1"), Value::Integer(1)),
+            (key("field2"), Value::Integer(2))
+        ],
+        array_pairs
+    );
+
+    // RESP3
+    let map = Value::Map(HashMap::from([
+        (key("field1"), Value::Integer(1)),
+        (key("field2"), Value::Integer(2)),
+    ]));
+    let map_pairs = map.into_pairs()?;
+    assert_eq!(
+        array_pairs.into_iter().collect::<HashMap<_, _>>(),
+        map_pairs.into_iter().collect::<HashMap<_, _>>()
+    );
+
+    assert_eq!(Vec::<(Value, Value)>::new(), Value::Nil.into_pairs()?);
+
+    let odd_array = Value::Array(vec![key("field1"), Value::Integer(1), key("field2")]);
+    assert!(matches!(odd_array.into_pairs(), Err(Error::Client(_))));
+    assert!(matches!(
+        Value::Integer(12).into_pairs(),
+        Err(Error::Client(_))
+    ));
+
+    Ok(())
+}
@@ -72,6 +72,55 @@ fn i64() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn conversion_error() -> Result<()> {
+    log_try_init();
+
+    let result = i64::deserialize(&Value::BulkString(b"hello".to_vec()));
+    assert!(
+        matches!(&result, Err(Error::Conversion(e)) if e.to_string() == "Cannot parse bulk string `hello` to i64"),
+        "{result:?}"
+    );
+
+    let result = String::deserialize(&Value::Array(vec![Value::Integer(1), Value::Integer(2)]));
+    assert!(
+        matches!(&result, Err(Error::Conversion(e)) if e.to_string() == "Cannot parse array `[1, 2]` to String"),
+        "{result:?}"
+    );
+
+    let result = f64::deserialize(&Value::BulkString(vec![b'a'; 100]));
+    assert!(
+        matches!(&result, Err(Error::Conversion(e)) if e.to_string() == format!("Cannot parse bulk string `{}...` to f64", "a".repeat(50))),
+        "{result:?}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn integer_out_of_range() -> Result<()> {
+    log_try_init();
+
+    let value = Value::Integer(i32::MAX as i64 + 1);
+
+    let result = i32::deserialize(&value);
+    assert!(
+        matches!(&result, Err(Error::Client(e)) if e.contains("out of range for i32")),
+        "{result:?}"
+    );
+
+    let result = i64::deserialize(&value)?;
+    assert_eq!(i32::MAX as i64 + 1, result);
+
+    let result = u64::deserialize(&Value::Integer(-1));
+    assert!(matches!(result, Err(Error::Client(_))));
+
+    let result = usize::deserialize(&Value::Integer(12))?;
+    assert_eq!(12, result);
+
+    Ok(())
+}
+
 #[test]
 fn u64() -> Result<()> {
     log_try_init();
@@ -378,6 +427,24 @@ fn tuple() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn array() -> Result<()> {
+    log_try_init();
+
+    let result =
+        <[f64; 2]>::deserialize(&Value::Array(vec![Value::Double(1.5), Value::Double(2.5)]))?;
+    assert_eq!([1.5, 2.5], result);
+
+    let result = <[f64; 2]>::deserialize(&Value::Array(vec![
+        Value::Double(1.5),
+        Value::Double(2.5),
+        Value::Double(3.5),
+    ]));
+    assert!(matches!(result, Err(Error::Client(_))));
+
+    Ok(())
+}
+
 #[test]
 fn tuple_struct() -> Result<()> {
     log_try_init();
@@ -512,6 +579,108 @@ fn _struct() -> Result<()> {
     assert_eq!(12, result.id);
     assert_eq!("foo", result.name);
 
+    #[derive(Debug, Deserialize)]
+    pub struct PartialPerson {
+        pub id: Option<u64>,
+        pub name: String,
+    }
+
+    // RESP2 flat array with less entries than fields
+    let value = Value::Array(vec![
+        Value::BulkString(b"name".to_vec()),
+        Value::BulkString(b"foo".to_vec()),
+    ]);
+
+    let result = PartialPerson::deserialize(&value)?;
+    assert_eq!(None, result.id);
+    assert_eq!("foo", result.name);
+
+    Ok(())
+}
+
+#[test]
+fn xinfo_stream_struct() -> Result<()> {
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub struct StreamInfo {
+        pub length: usize,
+        pub radix_tree_keys: usize,
+        pub groups: usize,
+        pub last_generated_id: String,
+        pub first_entry: Option<(String, Vec<String>)>,
+        pub last_entry: Option<(String, Vec<String>)>,
+    }
+
+    log_try_init();
+
+    let entry = |id: &str, field: &str, value: &str| {
+        Value::Array(vec![
+            Value::BulkString(id.as_bytes().to_vec()),
+            Value::Array(vec![
+                Value::BulkString(field.as_bytes().to_vec()),
+                Value::BulkString(value.as_bytes().to_vec()),
+            ]),
+        ])
+    };
+
+    // RESP3 map, with fields unknown to the struct
+    let value = Value::Map(HashMap::from([
+        (Value::BulkString(b"length".to_vec()), Value::Integer(2)),
+        (
+            Value::BulkString(b"radix-tree-keys".to_vec()),
+            Value::Integer(1),
+        ),
+        (
+            Value::BulkString(b"radix-tree-nodes".to_vec()),
+            Value::Integer(2),
+        ),
+        (Value::BulkString(b"groups".to_vec()), Value::Integer(0)),
+        (
+            Value::BulkString(b"last-generated-id".to_vec()),
+            Value::BulkString(b"1-1".to_vec()),
+        ),
+        (
+            Value::BulkString(b"first-entry".to_vec()),
+            entry("1-0", "name", "foo"),
+        ),
+        (
+            Value::BulkString(b"last-entry".to_vec()),
+            entry("1-1", "name", "bar"),
+        ),
+    ]));
+
+    let result: StreamInfo = value.into()?;
+    assert_eq!(2, result.length);
+    assert_eq!(1, result.radix_tree_keys);
+    assert_eq!(0, result.groups);
+    assert_eq!("1-1", result.last_generated_id);
+    assert_eq!(
+        Some(("1-0".to_owned(), vec!["name".to_owned(), "foo".to_owned()])),
+        result.first_entry
+    );
+    assert_eq!(
+        Some(("1-1".to_owned(), vec!["name".to_owned(), "bar".to_owned()])),
+        result.last_entry
+    );
+
+    // RESP2 flat array, with missing optional fields
+    let value = Value::Array(vec![
+        Value::BulkString(b"length".to_vec()),
+        Value::Integer(0),
+        Value::BulkString(b"radix-tree-keys".to_vec()),
+        Value::Integer(0),
+        Value::BulkString(b"groups".to_vec()),
+        Value::Integer(0),
+        Value::BulkString(b"last-generated-id".to_vec()),
+        Value::BulkString(b"0-0".to_vec()),
+    ]);
+
+    let result: StreamInfo = value.into()?;
+    assert_eq!(0, result.length);
+    assert_eq!("0-0", result.last_generated_id);
+    assert_eq!(None, result.first_entry);
+    assert_eq!(None, result.last_entry);
+
     Ok(())
 }
 